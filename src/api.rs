@@ -0,0 +1,419 @@
+use std::{
+	collections::HashMap,
+	convert::Infallible,
+	sync::Arc
+};
+use hyper::{
+	Body,
+	Method,
+	Request,
+	Response,
+	StatusCode
+};
+use serde_json::{
+	json,
+	Value
+};
+use serenity::prelude::*;
+
+use crate::{
+	commands::poketcg::{
+		card::{
+			get_card,
+			get_cards_by_set,
+			Card
+		},
+		config,
+		decks::get_decks_by_player,
+		guild::{
+			find_guild_settings,
+			GLOBAL_ECONOMY
+		},
+		items::find_item,
+		player::find_player,
+		player_card::player_cards,
+		sets::{
+			get_set,
+			get_sets,
+			Set
+		},
+		slot::{
+			find_token_shop,
+			to_tokens
+		},
+		store::find_store,
+		Idable
+	},
+	error::Error,
+	http,
+	logging
+};
+
+static DEFAULT_ADDR: &str = "127.0.0.1:9185";
+
+// Same as the PokeTCG API's defaults
+static DEFAULT_PAGE_SIZE: usize = 250;
+static MAX_PAGE_SIZE: usize = 250;
+
+struct ApiError {
+	status: StatusCode,
+	message: String
+}
+
+impl ApiError {
+	fn new(status: StatusCode, message: &str) -> Self {
+		Self {
+			status,
+			message: String::from(message)
+		}
+	}
+}
+
+impl From<Error> for ApiError {
+	fn from(why: Error) -> Self {
+		match why {
+			Error::NotFound(_) => ApiError::new(StatusCode::NOT_FOUND, &why.user_message()),
+			_ => {
+				error!(target: logging::BOT, "HTTP API request failed: {}", why);
+				ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, &why.user_message())
+			}
+		}
+	}
+}
+
+type ApiResult = Result<Value, ApiError>;
+
+fn query_params(req: &Request<Body>) -> Result<HashMap<String, String>, ApiError> {
+	serde_urlencoded::from_str(req.uri().query().unwrap_or(""))
+		.map_err(|_| ApiError::new(StatusCode::BAD_REQUEST, "The query string couldn't be read"))
+}
+
+fn query_number<T: std::str::FromStr>(params: &HashMap<String, String>, name: &str) -> Result<Option<T>, ApiError> {
+	match params.get(name) {
+		Some(x) => match x.parse::<T>() {
+			Ok(x) => Ok(Some(x)),
+			Err(_) => Err(ApiError::new(StatusCode::BAD_REQUEST, &format!("{} must be a number", name)))
+		},
+		None => Ok(None)
+	}
+}
+
+/// The economy from ?economy= or ?guild=, the global one if neither is given.
+/// A guild that has never used the bot is a 404, looking it up doesn't add it.
+async fn economy_param(params: &HashMap<String, String>) -> Result<i64, ApiError> {
+	if let Some(economy) = query_number::<i64>(params, "economy")? {
+		return Ok(economy);
+	}
+	let guild_id = match query_number::<i64>(params, "guild")? {
+		Some(x) => x,
+		None => return Ok(GLOBAL_ECONOMY)
+	};
+	let guild_settings = find_guild_settings(guild_id)
+		.await?
+		.ok_or(Error::NotFound(format!("guild {}", guild_id)))?;
+
+	Ok(guild_settings.economy())
+}
+
+/// Cuts out the page asked for with ?page= and ?page_size=, the way the PokeTCG API does.
+fn paginate(items: Vec<Value>, params: &HashMap<String, String>) -> ApiResult {
+	let page = query_number::<usize>(params, "page")?.unwrap_or(1).max(1);
+	let page_size = query_number::<usize>(params, "page_size")?
+		.unwrap_or(DEFAULT_PAGE_SIZE)
+		.clamp(1, MAX_PAGE_SIZE);
+	let total_count = items.len();
+	let data = items
+		.into_iter()
+		.skip((page - 1) * page_size)
+		.take(page_size)
+		.collect::<Vec<Value>>();
+
+	Ok(json!({
+		"data": data,
+		"page": page,
+		"page_size": page_size,
+		"count": data.len(),
+		"total_count": total_count
+	}))
+}
+
+fn set_json(set: &Set) -> Value {
+	json!({
+		"id": set.id(),
+		"name": set.name,
+		"series": set.series,
+		"printed": set.printed,
+		"total": set.total,
+		"logo": set.logo,
+		"symbol": set.symbol,
+		"release_date": set.release_date.to_rfc3339(),
+		"pack_price": set.pack_price()
+	})
+}
+
+fn card_json(card: &Card) -> Value {
+	json!({
+		"id": card.card_id,
+		"name": card.name,
+		"set": card.set.id(),
+		"number": card.number,
+		"rarity": card.rarity,
+		"price": card.price,
+		"image": card.image
+	})
+}
+
+async fn player_param(id: &str, params: &HashMap<String, String>) -> Result<(i64, i64), ApiError> {
+	let discord_id = match id.parse::<i64>() {
+		Ok(x) => x,
+		Err(_) => return Err(ApiError::new(StatusCode::BAD_REQUEST, "The player id must be a discord id"))
+	};
+
+	Ok((discord_id, economy_param(params).await?))
+}
+
+async fn get_player_stats(id: &str, params: &HashMap<String, String>) -> ApiResult {
+	let (discord_id, economy) = player_param(id, params).await?;
+	let player = find_player(discord_id, economy)
+		.await?
+		.ok_or(Error::NotFound(format!("player {}", discord_id)))?;
+
+	Ok(json!({
+		"discord_id": player.discord_id.to_string(),
+		"economy": player.economy,
+		"cash": player.cash,
+		"total_cash": player.total_cash,
+		"tokens": player.tokens,
+		"total_tokens": player.total_tokens,
+		"packs": player.packs,
+		"packs_opened": player.packs_opened,
+		"packs_bought": player.packs_bought,
+		"unique_cards": player.cards.len(),
+		"total_cards": player.total_cards,
+		"cards_sold": player.cards_sold,
+		"daily_packs": player.daily_packs,
+		"daily_slots": player.daily_slots,
+		"daily_streak": player.daily_streak,
+		"daily_reset": player.daily_reset.to_rfc3339(),
+		"quiz_correct": player.quiz_correct,
+		"quiz_questions": player.quiz_questions,
+		"slots_rolled": player.slots_rolled,
+		"jackpots": player.jackpots,
+		"multiplier": player.perm_multiplier + player.current_multiplier,
+		"prestige": player.prestige,
+		"prestige_points": player.prestige_points,
+		"upgrades": player.upgrades,
+		"items": player.items,
+		"current_binder": player.current_binder.set,
+		"completed_binders": player.completed_binders,
+		"savelist": player.savelist
+	}))
+}
+
+async fn get_player_cards(ctx: &Context, id: &str, params: &HashMap<String, String>) -> ApiResult {
+	let (discord_id, economy) = player_param(id, params).await?;
+	let player = find_player(discord_id, economy)
+		.await?
+		.ok_or(Error::NotFound(format!("player {}", discord_id)))?;
+	let mut cards = player_cards(ctx, player.cards).await;
+	cards.sort_by(|a, b| a.card.card_id.cmp(&b.card.card_id));
+	let cards = cards
+		.iter()
+		.map(|pc| {
+			let mut card = card_json(&pc.card);
+			card["amount"] = json!(pc.amount);
+			card
+		})
+		.collect();
+
+	paginate(cards, params)
+}
+
+async fn get_player_decks(ctx: &Context, id: &str, params: &HashMap<String, String>) -> ApiResult {
+	let (discord_id, economy) = player_param(id, params).await?;
+	let mut decks = get_decks_by_player(discord_id, economy).await;
+	decks.sort_by(|a, b| a.name.cmp(&b.name));
+	let mut ret = vec![];
+	for d in &decks {
+		let legality = d.legality(ctx)
+			.await
+			.into_iter()
+			.map(|(format, violations)| (format.name(), violations))
+			.collect::<HashMap<&str, Vec<String>>>();
+		ret.push(json!({
+			"name": d.name,
+			"display_card": d.display_card,
+			"draft": d.draft,
+			"cards": d.cards,
+			"card_count": d.cards.values().sum::<i64>(),
+			"legality": legality
+		}));
+	}
+
+	paginate(ret, params)
+}
+
+async fn get_all_sets(params: &HashMap<String, String>) -> ApiResult {
+	let mut sets = get_sets().await;
+	sets.sort_by(|a, b| a.release_date.cmp(&b.release_date));
+	let sets = sets
+		.iter()
+		.map(set_json)
+		.collect();
+
+	paginate(sets, params)
+}
+
+async fn get_set_cards(ctx: &Context, id: &str, params: &HashMap<String, String>) -> ApiResult {
+	let set = get_set(id).await?;
+	let mut cards = get_cards_by_set(ctx, &set).await;
+	cards.sort_by(|a, b| a.card_id.cmp(&b.card_id));
+	let cards = cards
+		.iter()
+		.map(card_json)
+		.collect();
+
+	paginate(cards, params)
+}
+
+// Prices are before any of a player's discounts
+async fn get_store_rotation(params: &HashMap<String, String>) -> ApiResult {
+	let economy = economy_param(params).await?;
+	let store = find_store(economy)
+		.await?
+		.ok_or(Error::NotFound(format!("store in economy {}", economy)))?;
+	let economy_config = config::get();
+	let mut slots = vec![];
+	for (i, set_id) in store.sets.iter().enumerate() {
+		let set = get_set(set_id).await?;
+		let store_slot = economy_config.store_slot(i);
+		slots.push(json!({
+			"slot": i + 1,
+			"set": set_json(&set),
+			"pack_type": store_slot.name,
+			"price": set.pack_price() * store_slot.price_mult
+		}));
+	}
+
+	Ok(json!({
+		"economy": economy,
+		"slots": slots,
+		"reset": store.reset.to_rfc3339()
+	}))
+}
+
+// Every set is listed, how many a player can buy from depends on their upgrades
+async fn get_token_shop_rotation(ctx: &Context, params: &HashMap<String, String>) -> ApiResult {
+	let economy = economy_param(params).await?;
+	let token_shop = find_token_shop(economy)
+		.await?
+		.ok_or(Error::NotFound(format!("token shop in economy {}", economy)))?;
+	let mut sets = vec![];
+	for set_id in &token_shop.sets {
+		let set = get_set(set_id).await?;
+		sets.push(json!({
+			"set": set_json(&set),
+			"tokens": to_tokens(set.pack_price())
+		}));
+	}
+	let rare_card = get_card(ctx, &token_shop.rare_card).await;
+	let rainbow_card = get_card(ctx, &token_shop.rainbow_card).await;
+	let item = find_item(&token_shop.item).map(|i| json!({
+		"key": i.key,
+		"name": i.name,
+		"tokens": i.price().tokens.unwrap_or(0)
+	}));
+
+	Ok(json!({
+		"economy": economy,
+		"sets": sets,
+		"visible_sets": config::get().effects.tokenshop_slots,
+		"rare_card": { "card": card_json(&rare_card), "tokens": to_tokens(rare_card.price) * 10 },
+		"rainbow_card": { "card": card_json(&rainbow_card), "tokens": to_tokens(rainbow_card.price) * 10 },
+		"item": item,
+		"reset": token_shop.reset.to_rfc3339()
+	}))
+}
+
+fn is_authorized(req: &Request<Body>, keys: &[String]) -> bool {
+	let headers = req.headers();
+	let key = match headers.get("X-Api-Key") {
+		Some(x) => x.to_str().ok(),
+		None => headers
+			.get("Authorization")
+			.and_then(|x| x.to_str().ok())
+			.and_then(|x| x.strip_prefix("Bearer "))
+	};
+
+	match key {
+		Some(key) => keys.iter().fold(false, |found, k| found | constant_time_eq(k.as_bytes(), key.as_bytes())),
+		None => false
+	}
+}
+
+// Compares every byte whatever the first difference is, so the time taken doesn't give a key away
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+	if a.len() != b.len() {
+		return false;
+	}
+
+	a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+async fn route(ctx: &Context, req: &Request<Body>) -> ApiResult {
+	if req.method() != Method::GET {
+		return Err(ApiError::new(StatusCode::METHOD_NOT_ALLOWED, "The API is read only"));
+	}
+	let params = query_params(req)?;
+	let path = req
+		.uri()
+		.path()
+		.trim_matches('/')
+		.split('/')
+		.collect::<Vec<&str>>();
+	match path.as_slice() {
+		["players", id] => get_player_stats(id, &params).await,
+		["players", id, "cards"] => get_player_cards(ctx, id, &params).await,
+		["players", id, "decks"] => get_player_decks(ctx, id, &params).await,
+		["sets"] => get_all_sets(&params).await,
+		["sets", id, "cards"] => get_set_cards(ctx, id, &params).await,
+		["store"] => get_store_rotation(&params).await,
+		["tokenshop"] => get_token_shop_rotation(ctx, &params).await,
+		_ => Err(ApiError::new(StatusCode::NOT_FOUND, "No such endpoint"))
+	}
+}
+
+async fn handle(ctx: Arc<Context>, keys: Arc<Vec<String>>, req: Request<Body>) -> Result<Response<Body>, Infallible> {
+	let result = match is_authorized(&req, &keys) {
+		true => route(&ctx, &req).await,
+		false => Err(ApiError::new(StatusCode::UNAUTHORIZED, "A valid API key is needed in the X-Api-Key header"))
+	};
+	let (status, body) = match result {
+		Ok(x) => (StatusCode::OK, x),
+		Err(why) => (why.status, json!({ "error": why.message }))
+	};
+	let response = Response::builder()
+		.status(status)
+		.header("Content-Type", "application/json")
+		.body(Body::from(body.to_string()))
+		.unwrap();
+
+	Ok(response)
+}
+
+/// Serves the read only JSON API on API_ADDR (default 127.0.0.1:9185) until the bot shuts down.
+/// Requests need one of the comma separated keys in API_KEYS, without any the API isn't served.
+pub async fn serve(ctx: Arc<Context>) {
+	let keys = dotenv::var("API_KEYS")
+		.unwrap_or(String::from(""))
+		.split(',')
+		.map(|k| String::from(k.trim()))
+		.filter(|k| k != "")
+		.collect::<Vec<String>>();
+	if keys.len() == 0 {
+		warn!(target: logging::BOT, "Not serving the HTTP API, there are no API_KEYS");
+		return;
+	}
+	let keys = Arc::new(keys);
+	http::serve("the HTTP API", "API_ADDR", DEFAULT_ADDR, "", move |req| handle(Arc::clone(&ctx), Arc::clone(&keys), req)).await;
+}
@@ -6,10 +6,11 @@ use mongodb::{
 		ClientOptions,
 	},
 };
-use std::error::Error;
+
+use crate::error::BotResult;
 
 // Database queries
-async fn get_client() -> Result<Client, Box<dyn Error>> {
+async fn get_client() -> BotResult<Client> {
 	let mon_client_uri = dotenv::var("MONGODB_URI").expect("No mongodb uri");
 	let options = ClientOptions::parse(&mon_client_uri).await?;
 	let client = Client::with_options(options)?;
@@ -0,0 +1,440 @@
+use std::{
+	collections::BTreeMap,
+	time::Duration
+};
+use mongodb::bson::{
+	doc,
+	Document
+};
+use serenity::{
+	framework::{
+		standard::{
+			macros::{
+				command
+			},
+			Args,
+			CommandResult
+		},
+	},
+	model::{
+		channel::{
+			Message,
+		},
+	},
+	utils::{
+		Colour
+	},
+	prelude::*
+};
+
+use super::{
+	card::{
+		get_multiple_cards_by_id,
+		Card
+	},
+	decklist::format_decklist,
+	decks::{
+		add_deck,
+		find_basic_energy,
+		get_deck,
+		is_basic_energy,
+		BasicEnergy,
+		BASIC_ENERGIES,
+		Deck,
+		DeckCards
+	},
+	legality::{
+		self,
+		is_basic_pokemon,
+		with_amounts,
+		Format
+	},
+	player::{
+		get_player,
+		update_player
+	}
+};
+
+static DECK_SIZE: i64 = 60;
+// Extra evolution lines are added until the deck has this many Pokémon
+static POKEMON_TARGET: i64 = 16;
+static POKEMON_MAX: i64 = 20;
+static ENERGY_TARGET: i64 = 12;
+// A deck that's short on Pokémon and trainers gets a few more energy than the target, but not more than this
+static ENERGY_MAX: i64 = 16;
+// How many of each kind of trainer the deck aims for, the rest is filled with whatever trainers are left
+const TRAINER_TARGETS: &'static [(&str, i64)] = &[("Supporter", 12), ("Item", 14), ("Pokémon Tool", 3), ("Stadium", 2)];
+// Copies of each stage of an evolution line, eg. 4-3-3 for a Stage 2 line
+const STAGE_COPIES: &'static [i64] = &[4, 3, 3];
+
+// The formats a deck is tried in, newest card pool first
+const BUILD_FORMATS: &'static [Format] = &[Format::Standard, Format::Expanded, Format::Unlimited];
+
+const POKEMON_TYPES: &'static [&str] = &["Grass", "Fire", "Water", "Lightning", "Psychic", "Fighting", "Darkness", "Metal", "Fairy", "Dragon", "Colorless"];
+
+/// The Pokémon type for a name, also taking the basic energy aliases like "dark" and "electric".
+fn find_type(name: &str) -> Option<&'static str> {
+	let name = match find_basic_energy(name) {
+		Some(energy) => energy.names[0],
+		None => name
+	};
+
+	POKEMON_TYPES
+		.iter()
+		.find(|t| t.eq_ignore_ascii_case(name))
+		.map(|t| *t)
+}
+
+fn trainer_kind(card: &Card) -> &'static str {
+	let has = |subtype: &str| card.subtypes.iter().any(|s| s.starts_with(subtype));
+	match () {
+		_ if has("Supporter") => "Supporter",
+		_ if has("Pokémon Tool") => "Pokémon Tool",
+		_ if has("Stadium") => "Stadium",
+		_ => "Item"
+	}
+}
+
+// An evolution line from its Basic to its last stage
+struct Line {
+	names: Vec<String>,
+	types: Vec<String>,
+	// The last stage's HP, then what the line is worth as a tiebreaker
+	score: (i64, i64)
+}
+
+struct Builder {
+	// The cards the player owns that can be played in the format, by name, most owned printing first
+	pool: BTreeMap<String, Vec<(Card, i64)>>,
+	cards: Vec<(Card, i64)>
+}
+
+impl Builder {
+	fn new(format: Format, owned: &[(Card, i64)]) -> Self {
+		let mut pool: BTreeMap<String, Vec<(Card, i64)>> = BTreeMap::new();
+		for (card, amount) in owned {
+			if *amount <= 0 || card.supertype == "Energy" || card.supertype == "" || !format.is_legal(card) {
+				continue;
+			}
+			pool.entry(card.name.clone()).or_insert(vec![]).push((card.clone(), *amount));
+		}
+		for printings in pool.values_mut() {
+			printings.sort_by(|(c1, a1), (c2, a2)| a2.cmp(a1).then(c2.price.partial_cmp(&c1.price).unwrap_or(std::cmp::Ordering::Equal)));
+		}
+
+		Self {
+			pool,
+			cards: vec![]
+		}
+	}
+
+	fn total(&self) -> i64 {
+		self.cards.iter().map(|(_, a)| a).sum()
+	}
+
+	fn count(&self, supertype: &str) -> i64 {
+		self.cards.iter().filter(|(c, _)| c.supertype == supertype).map(|(_, a)| a).sum()
+	}
+
+	/// Puts up to `wanted` copies of a card in the deck, using the printings the player has the most of first.
+	/// Returns how many were put in, fewer if the player doesn't own enough or more would break the deck rules.
+	fn add(&mut self, name: &str, wanted: i64) -> i64 {
+		let printings = match self.pool.get(name) {
+			Some(x) => x.clone(),
+			None => return 0
+		};
+		for amount in (1..=wanted.min(DECK_SIZE - self.total())).rev() {
+			let mut trial = self.cards.clone();
+			let mut left = amount;
+			for (card, owned) in &printings {
+				if left == 0 {
+					break;
+				}
+				let taken = left.min(*owned);
+				trial.push((card.clone(), taken));
+				left -= taken;
+			}
+			if left > 0 || legality::addition_violations(&trial).len() > 0 {
+				continue;
+			}
+			self.cards = trial;
+			self.pool.remove(name);
+
+			return amount;
+		}
+
+		0
+	}
+
+	/// Every evolution line the player can make, strongest first.
+	fn lines(&self) -> Vec<Line> {
+		let mut ret = vec![];
+		for (name, printings) in &self.pool {
+			if printings.iter().any(|(c, _)| is_basic_pokemon(c)) {
+				self.extend_line(vec![name.clone()], &mut ret);
+			}
+		}
+		ret.sort_by(|l1, l2| l2.score.cmp(&l1.score));
+
+		ret
+	}
+
+	fn extend_line(&self, names: Vec<String>, lines: &mut Vec<Line>) {
+		let last = names.last().unwrap();
+		let evolutions = self.pool
+			.iter()
+			.filter(|(_, printings)| printings[0].0.supertype == "Pokémon" && printings[0].0.evolves_from == *last)
+			.map(|(name, _)| name.clone())
+			.collect::<Vec<String>>();
+		if evolutions.len() == 0 || names.len() > STAGE_COPIES.len() {
+			let last_stage = &self.pool[last];
+			let hp = last_stage.iter().map(|(c, _)| c.hp).max().unwrap_or(0);
+			let price = names
+				.iter()
+				.map(|n| self.pool[n].iter().map(|(c, _)| (c.price * 100.0) as i64).max().unwrap_or(0))
+				.sum::<i64>();
+			lines.push(Line {
+				types: last_stage[0].0.types.clone(),
+				names,
+				score: (hp, price)
+			});
+			return;
+		}
+		for evolution in evolutions {
+			let mut names = names.clone();
+			names.push(evolution);
+			self.extend_line(names, lines);
+		}
+	}
+
+	/// Puts a line in the deck from its Basic up, stopping at the first stage that can't be added.
+	/// Returns whether any of it was added.
+	fn add_line(&mut self, line: &Line) -> bool {
+		for (i, name) in line.names.iter().enumerate() {
+			let copies = *STAGE_COPIES.get(i).unwrap_or(&2);
+			if self.add(name, copies) == 0 {
+				return i > 0;
+			}
+		}
+
+		true
+	}
+
+	fn add_trainers(&mut self) {
+		let mut trainers = self.pool
+			.iter()
+			.filter(|(_, printings)| printings[0].0.supertype == "Trainer")
+			.map(|(name, printings)| {
+				let owned = printings.iter().map(|(_, a)| a).sum::<i64>().min(4);
+				let price = printings.iter().map(|(c, _)| (c.price * 100.0) as i64).max().unwrap_or(0);
+				(name.clone(), trainer_kind(&printings[0].0), owned, price)
+			})
+			.collect::<Vec<(String, &str, i64, i64)>>();
+		// Cards the player has a playset of come first, and price is the best guess at how good a card is
+		trainers.sort_by(|t1, t2| (t2.2, t2.3).cmp(&(t1.2, t1.3)));
+		let room = |builder: &Self| DECK_SIZE - ENERGY_TARGET - builder.total();
+		for (kind, target) in TRAINER_TARGETS {
+			let mut added = 0;
+			for (name, _, _, _) in trainers.iter().filter(|t| t.1 == *kind) {
+				let wanted = 4.min(target - added).min(room(self));
+				if wanted <= 0 {
+					break;
+				}
+				added += self.add(name, wanted);
+			}
+		}
+		for (name, _, _, _) in &trainers {
+			let wanted = 4.min(room(self));
+			if wanted <= 0 {
+				break;
+			}
+			self.add(name, wanted);
+		}
+	}
+}
+
+struct AutoBuild {
+	format: Format,
+	// The last stage of the line the deck is built around
+	main: Card,
+	cards: Vec<(Card, i64)>
+}
+
+impl AutoBuild {
+	/// How many cards the deck is short of a full deck, because the player doesn't own enough to fill it.
+	fn missing(&self) -> i64 {
+		DECK_SIZE - self.cards.iter().map(|(_, a)| a).sum::<i64>()
+	}
+}
+
+fn energy_for(types: &[String]) -> Option<&'static BasicEnergy> {
+	types.iter().find_map(|t| find_basic_energy(t))
+}
+
+fn build(format: Format, owned: &[(Card, i64)], energies: &[Card], wanted_type: Option<&str>) -> Option<AutoBuild> {
+	let mut builder = Builder::new(format, owned);
+	let lines = builder
+		.lines()
+		.into_iter()
+		.filter(|l| wanted_type.is_none() || l.types.iter().any(|t| Some(t.as_str()) == wanted_type))
+		.collect::<Vec<Line>>();
+	let main = lines.iter().find(|l| builder.add_line(l))?;
+	let main_card = builder.cards
+		.iter()
+		.rev()
+		.find(|(c, _)| main.names.contains(&c.name))
+		.map(|(c, _)| c.clone())?;
+	// Other lines are only added if they can use the same energy
+	for line in &lines {
+		if builder.count("Pokémon") >= POKEMON_TARGET {
+			break;
+		}
+		let planned = (0..line.names.len()).map(|i| *STAGE_COPIES.get(i).unwrap_or(&2)).sum::<i64>();
+		let shares_type = line.types.iter().any(|t| t == "Colorless" || main.types.contains(t));
+		if !shares_type || builder.count("Pokémon") + planned > POKEMON_MAX || line.names.iter().any(|n| !builder.pool.contains_key(n)) {
+			continue;
+		}
+		builder.add_line(line);
+	}
+	builder.add_trainers();
+
+	// Colorless and Dragon Pokémon don't have a basic energy, so any other type in the deck is used instead
+	let deck_types = builder.cards
+		.iter()
+		.filter(|(c, _)| c.supertype == "Pokémon")
+		.flat_map(|(c, _)| c.types.clone())
+		.collect::<Vec<String>>();
+	let energy = energy_for(&main.types)
+		.or(wanted_type.and_then(|t| find_basic_energy(t)))
+		.or(energy_for(&deck_types))
+		.unwrap_or(&BASIC_ENERGIES[0]);
+	let energy_card = energies.iter().find(|c| c.card_id == energy.card_id)?;
+	let room = DECK_SIZE - builder.total();
+	// Basic energy can go in any deck, so a deck that's legal when filled up with it only lacks cards
+	let mut filled = builder.cards.clone();
+	filled.push((energy_card.clone(), room));
+	if legality::violations(format, &filled).len() > 0 {
+		return None;
+	}
+	builder.cards.push((energy_card.clone(), room.min(ENERGY_MAX)));
+
+	Some(AutoBuild {
+		format,
+		main: main_card,
+		cards: builder.cards
+	})
+}
+
+#[command("autobuild")]
+#[aliases("auto", "ab")]
+#[description("Builds a deck from your collection around the strongest evolution line you have. Shows you the deck before making it")]
+#[usage("<name> [type]")]
+#[example("blastoise water")]
+#[min_args(1)]
+async fn deck_autobuild(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+	let mut deck_name = args.rest().to_lowercase();
+	let mut wanted_type = None;
+	if let Some((name, last)) = deck_name.rsplit_once(' ') {
+		if let Some(x) = find_type(last) {
+			wanted_type = Some(x);
+			deck_name = String::from(name);
+		}
+	}
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	if get_deck(player.discord_id, player.economy, deck_name.clone()).await.is_some() {
+		msg.reply(&ctx.http, "You already have a deck with that name!").await?;
+		return Ok(());
+	}
+	let owned = with_amounts(get_multiple_cards_by_id(ctx, player.cards.keys().cloned().collect()).await, &player.cards);
+	let energies = get_multiple_cards_by_id(ctx, BASIC_ENERGIES.iter().map(|e| String::from(e.card_id)).collect()).await;
+	let mut builds = BUILD_FORMATS
+		.iter()
+		.filter_map(|f| build(*f, &owned, &energies, wanted_type))
+		.collect::<Vec<AutoBuild>>();
+	// A full deck in an older format beats a short one in a newer format
+	let full = builds.iter().position(|b| b.missing() == 0).unwrap_or(0);
+	let auto_build = match builds.len() {
+		0 => None,
+		_ => Some(builds.swap_remove(full))
+	};
+	let auto_build = match auto_build {
+		Some(x) => x,
+		None => {
+			let type_str = match wanted_type {
+				Some(t) => format!(" {}", t),
+				None => String::from("")
+			};
+			msg.reply(&ctx.http, format!("You don't have the cards to build a{} deck yet. Open some more packs!", type_str)).await?;
+			return Ok(());
+		}
+	};
+
+	let mut deck = Deck::empty(player.discord_id, player.economy, deck_name.clone());
+	for (card, amount) in &auto_build.cards {
+		*deck.cards.entry(card.card_id.clone()).or_insert(0) += amount;
+	}
+	deck.display_card = auto_build.main.card_id.clone();
+	let cards = auto_build.cards.iter().map(|(c, _)| c.clone()).collect::<Vec<Card>>();
+	let badges = legality::badges(&legality::legality(&auto_build.cards));
+	let mut desc = format!(
+		"Built for **{}** around **{}**\n{}\n",
+		auto_build.format.name(),
+		auto_build.main.name,
+		badges
+	);
+	if auto_build.missing() > 0 {
+		desc.push_str(&format!("You don't own enough cards to fill it, it's **{}** cards short of {}. Add more with **.deck add** before playing it\n", auto_build.missing(), DECK_SIZE));
+	}
+	desc.push_str(&format!("```\n{}\n```", format_decklist(&deck, &cards)));
+	msg
+		.channel_id
+		.send_message(&ctx.http, |m| {
+			m
+				.content(format!("<@{}> do you want to make this deck? Its cards will be moved out of your collection. (y/n)", msg.author.id))
+				.embed(|e| {
+					e
+						.title(format!("{} - Preview", deck.name))
+						.description(&desc)
+						.thumbnail(&auto_build.main.image)
+						.colour(Colour::from_rgb(255, 50, 20))
+				})
+		})
+		.await?;
+	if let Some(confirmation_reply) = &msg.author.await_reply(&ctx).timeout(Duration::from_secs(30)).await {
+		if confirmation_reply.content.to_lowercase() != "y" {
+			msg.reply(&ctx.http, format!("You did not build **{}**.", deck.name)).await?;
+			return Ok(());
+		}
+	} else {
+		msg.reply(&ctx.http, format!("You did not build **{}**.", deck.name)).await?;
+		return Ok(());
+	}
+	// The player could have traded or sold cards while looking at the preview
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let deckcards = DeckCards {
+		cards: deck.cards
+			.iter()
+			.filter(|(card_id, _)| !is_basic_energy(card_id))
+			.map(|(card_id, amount)| (card_id.clone(), *amount))
+			.collect()
+	};
+	if !deckcards.player_has_all(&player) || get_deck(player.discord_id, player.economy, deck_name.clone()).await.is_some() {
+		msg.reply(&ctx.http, "Your collection changed since the preview, try building the deck again.").await?;
+		return Ok(());
+	}
+	for (card_id, amount) in &deckcards.cards {
+		*player.cards.entry(card_id.clone()).or_insert(0) -= amount;
+	}
+	player.cards.retain(|_, v| *v > 0);
+	let mut player_update = Document::new();
+	let mut player_cards_update = Document::new();
+	for (crd, amt) in player.cards.iter() {
+		player_cards_update.insert(crd, amt);
+	}
+	player_update.insert("cards", player_cards_update);
+	update_player(&player, doc! { "$set": player_update }).await?;
+	add_deck(&deck).await;
+	msg.reply(&ctx.http, format!("You built **{}** with **{}** cards", deck.name, deck.cards.values().sum::<i64>())).await?;
+
+	Ok(())
+}
@@ -5,6 +5,7 @@ use mongodb::{
 	},
 };
 use serde::{Serialize, Deserialize};
+use crate::error::BotResult;
 use super::{
 	sets,
 	card,
@@ -15,6 +16,7 @@ use super::{
 	player_card,
 	HasSet,
 	Scrollable,
+	config,
 };
 use serenity::{
 	framework::{
@@ -38,6 +40,31 @@ use serenity::{
 };
 use std::time::Duration;
 
+/// Moves the player's current binder into their completed binders, paying out
+/// the binder_completion_bonus upgrade. Returns the cash that was paid out.
+pub fn complete_binder(player: &mut player::Player, player_update: &mut Document) -> f64 {
+	let bonus = player.upgrades.level("binder_completion_bonus") as f64 * config::get().effects.binder_bonus_per_level;
+	player.completed_binders.push(player.current_binder.set.clone());
+	player.current_binder = Binder::empty();
+	player_update.insert("completed_binders", player.completed_binders.clone());
+	if bonus > 0.0 {
+		player.cash += bonus;
+		player.total_cash += bonus;
+		player_update.insert("cash", player.cash);
+		player_update.insert("total_cash", player.total_cash);
+	}
+
+	bonus
+}
+
+/// The message for completing a binder, mentioning the bonus if there was one.
+pub fn completed_binder_message(set_name: &str, bonus: f64) -> String {
+	match bonus > 0.0 {
+		true => format!("You completed the **{}** binder! You earned a **${:.2}** bonus!", set_name, bonus),
+		false => format!("You completed the **{}** binder!", set_name)
+	}
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Binder {
 	pub set: String,
@@ -67,11 +94,11 @@ impl Binder {
 		d
 	}
 
-	pub async fn is_complete(&self, ctx: &Context) -> bool {
-		let set = sets::get_set(&self.set).await.unwrap();
+	pub async fn is_complete(&self, ctx: &Context) -> BotResult<bool> {
+		let set = sets::get_set(&self.set).await?;
 		let cards = card::get_cards_by_set(ctx, &set).await;
 
-		cards.len() == self.cards.len()
+		Ok(cards.len() == self.cards.len())
 	}
 
 }
@@ -79,8 +106,9 @@ impl Binder {
 #[command("binder")]
 #[aliases("b")]
 #[sub_commands(binder_start, binder_add, binder_showcase, binder_missing)]
+#[description("Shows your current binder")]
 async fn binder_main(ctx: &Context, msg: &Message) -> CommandResult {
-	let player = player::get_player(msg.author.id.0).await;
+	let player = player::get_player(msg.author.id.0, msg.guild_id).await?;
 	match player.current_binder.set.as_str() {
 		"" => {
 			msg.reply(&ctx.http, "You don't have a binder started! Use **.binder start <set id>** to start one!").await?;
@@ -93,22 +121,19 @@ async fn binder_main(ctx: &Context, msg: &Message) -> CommandResult {
 
 #[command("start")]
 #[aliases("st")]
+#[description("Starts a binder for a set. You can't start another until it's complete")]
+#[usage("<set id>")]
+#[example("swsh1")]
+#[min_args(1)]
 async fn binder_start(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
 	let set_id = match args.find::<String>() {
 		Ok(x) => x,
 		Err(_) => String::from("")
 	};
-	let set = sets::get_set(&set_id).await;
-	match set {
-		Some(_) => (),
-		None => {
-			msg.reply(&ctx.http, "No set found with that id.").await?;
-		}
-	}
-	let set = set.unwrap();
-	let mut player = player::get_player(msg.author.id.0).await;
+	let set = sets::get_set(&set_id).await?;
+	let mut player = player::get_player(msg.author.id.0, msg.guild_id).await?;
 	if player.current_binder.set.as_str() != "" {
-		let current_binder_set = sets::get_set(&player.current_binder.set).await.unwrap();
+		let current_binder_set = sets::get_set(&player.current_binder.set).await?;
 		msg.reply(&ctx.http, format!("You already started a binder for **{}**", current_binder_set.name)).await?;
 		return Ok(());
 	}
@@ -132,7 +157,7 @@ async fn binder_start(ctx: &Context, msg: &Message, mut args: Args) -> CommandRe
 	let mut player_update = Document::new();
 	player_update.insert("current_binder", player.current_binder.to_doc());
 	msg.reply(&ctx.http, format!("You started the binder for **{}**", set.name)).await?;
-	player::update_player(&player, doc! { "$set": player_update }).await;
+	player::update_player(&player, doc! { "$set": player_update }).await?;
 
 	Ok(())
 }
@@ -140,6 +165,10 @@ async fn binder_start(ctx: &Context, msg: &Message, mut args: Args) -> CommandRe
 #[command("add")]
 #[aliases("a", "+")]
 #[sub_commands(binder_add_bulk)]
+#[description("Adds a card you own to your binder")]
+#[usage("<card id>")]
+#[example("swsh1-25")]
+#[min_args(1)]
 async fn binder_add(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
 	let card_id = match args.find::<String>() {
 		Ok(x) => x,
@@ -150,7 +179,7 @@ async fn binder_add(ctx: &Context, msg: &Message, mut args: Args) -> CommandResu
 		return Ok(());
 	}
 	let card = card::get_card(ctx, &card_id).await;
-	let mut player = player::get_player(msg.author.id.0).await;
+	let mut player = player::get_player(msg.author.id.0, msg.guild_id).await?;
 	if player.current_binder.set.as_str() == "" {
 		msg.reply(&ctx.http, "You don't have a binder started! Use **.binder start <set id>** to start one!").await?;
 		return Ok(());
@@ -159,7 +188,7 @@ async fn binder_add(ctx: &Context, msg: &Message, mut args: Args) -> CommandResu
 		msg.reply(&ctx.http, "You don't own that card.").await?;
 		return Ok(());
 	}
-	let current_binder_set = sets::get_set(&player.current_binder.set).await.unwrap();
+	let current_binder_set = sets::get_set(&player.current_binder.set).await?;
 	if current_binder_set.id() != card.set.id() {
 		msg.reply(&ctx.http, "That card doesn't belong in this binder.").await?;
 		return Ok(());
@@ -191,24 +220,23 @@ async fn binder_add(ctx: &Context, msg: &Message, mut args: Args) -> CommandResu
 	}
 	player_update.insert("cards", player_cards);
 	player.current_binder.cards.push(card.card_id());
-	if player.current_binder.is_complete(ctx).await {
-		player.completed_binders.push(player.current_binder.set);
-		player.current_binder = Binder::empty();
-		player_update.insert("completed_binders", player.completed_binders.clone());
-		msg.reply(&ctx.http, format!("You completed the **{}** binder!", current_binder_set.name)).await?;
+	if player.current_binder.is_complete(ctx).await? {
+		let bonus = complete_binder(&mut player, &mut player_update);
+		msg.reply(&ctx.http, completed_binder_message(&current_binder_set.name, bonus)).await?;
 	} else {
 		msg.reply(&ctx.http, format!("You added **{}** to your binder!", card.name)).await?;
 	}
 	player_update.insert("current_binder", player.current_binder.to_doc());
-	player::update_player(&player, doc! { "$set": player_update }).await;
+	player::update_player(&player, doc! { "$set": player_update }).await?;
 
 	Ok(())
 }
 
 #[command("bulk")]
 #[aliases("b")]
+#[description("Adds every card you own that belongs in your binder")]
 async fn binder_add_bulk(ctx: &Context, msg: &Message) -> CommandResult {
-	let mut player = player::get_player(msg.author.id.0).await;
+	let mut player = player::get_player(msg.author.id.0, msg.guild_id).await?;
 	if player.current_binder.set.as_str() == "" {
 		msg.reply(&ctx.http, "You don't have a binder started! Use **.binder start <set id>** to start one!").await?;
 		return Ok(());
@@ -236,17 +264,15 @@ async fn binder_add_bulk(ctx: &Context, msg: &Message) -> CommandResult {
 		player_cards.insert(crd, amt);
 	}
 	player_update.insert("cards", player_cards);
-	if player.current_binder.is_complete(ctx).await {
-		let current_binder_set = sets::get_set(&player.current_binder.set).await.unwrap();
-		player.completed_binders.push(player.current_binder.set);
-		player.current_binder = Binder::empty();
-		player_update.insert("completed_binders", player.completed_binders.clone());
-		msg.reply(&ctx.http, format!("You completed the **{}** binder!", current_binder_set.name)).await?;
+	if player.current_binder.is_complete(ctx).await? {
+		let current_binder_set = sets::get_set(&player.current_binder.set).await?;
+		let bonus = complete_binder(&mut player, &mut player_update);
+		msg.reply(&ctx.http, completed_binder_message(&current_binder_set.name, bonus)).await?;
 	} else {
 		msg.reply(&ctx.http, format!("You added **{}** cards to your binder!", binder_cards.len())).await?;
 	}
 	player_update.insert("current_binder", player.current_binder.to_doc());
-	player::update_player(&player, doc! { "$set": player_update }).await;
+	player::update_player(&player, doc! { "$set": player_update }).await?;
 	binder_cards.scroll_through(ctx, msg).await?;
 
 	Ok(())
@@ -254,15 +280,16 @@ async fn binder_add_bulk(ctx: &Context, msg: &Message) -> CommandResult {
 
 #[command("showcase")]
 #[aliases("sc")]
+#[description("Shows off your completed binders")]
 async fn binder_showcase(ctx: &Context, msg: &Message) -> CommandResult {
-	let player = player::get_player(msg.author.id.0).await;
+	let player = player::get_player(msg.author.id.0, msg.guild_id).await?;
 	if player.completed_binders.len() == 0 {
 		msg.reply(&ctx.http, "You have no completed binders!").await?;
 		return Ok(());
 	}
 	let mut desc = String::from("");
 	for completed_binder in player.completed_binders {
-		let set = sets::get_set(&completed_binder).await.unwrap();
+		let set = sets::get_set(&completed_binder).await?;
 		desc.push_str(&format!(":first_place: **{}** (_{}_)\n", set.name, set.id()));
 	}
 	msg
@@ -282,8 +309,9 @@ async fn binder_showcase(ctx: &Context, msg: &Message) -> CommandResult {
 
 #[command("missing")]
 #[aliases("m")]
+#[description("Shows the cards your binder is still missing")]
 async fn binder_missing(ctx: &Context, msg: &Message) -> CommandResult {
-	let player = player::get_player(msg.author.id.0).await;
+	let player = player::get_player(msg.author.id.0, msg.guild_id).await?;
 	if player.current_binder.set.as_str() == "" {
 		msg.reply(&ctx.http, "You don't have a binder started! Use **.binder start <set id>** to start one!").await?;
 		return Ok(());
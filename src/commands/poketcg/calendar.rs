@@ -0,0 +1,195 @@
+use chrono::Utc;
+use mongodb::bson::{
+	doc,
+	Document
+};
+use rand::seq::IteratorRandom;
+use serde::{Serialize, Deserialize};
+use serenity::{
+	framework::{
+		standard::{
+			macros::{
+				command
+			},
+			CommandResult
+		},
+	},
+	model::{
+		channel::{
+			Message,
+		},
+	},
+	utils::{
+		Colour
+	},
+	prelude::*
+};
+
+use crate::error::{
+	BotResult,
+	Error
+};
+
+use super::{
+	card::get_rare_cards_from_cache,
+	config::{
+		self,
+		CalendarConfig
+	},
+	player::{
+		Player,
+		get_player
+	},
+	sets::{
+		get_set,
+		get_sets
+	},
+	Idable
+};
+
+// Days on the calendar, logins after this in the same month don't give calendar rewards
+static CALENDAR_DAYS: i64 = 28;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoginCalendar {
+	// The month being filled in, as YYYY-MM in the player's timezone
+	pub month: String,
+	pub claimed: i64
+}
+
+fn current_month(player: &Player) -> String {
+	Utc::now().with_timezone(&player.tz()).format("%Y-%m").to_string()
+}
+
+fn day_reward(calendar_config: &CalendarConfig, day: i64) -> String {
+	match day {
+		d if d == calendar_config.pack_day => format!("{} pack(s)", calendar_config.pack_amount),
+		d if d == calendar_config.token_day => format!("{} tokens", calendar_config.tokens),
+		d if d == calendar_config.rare_day => String::from("a rare card"),
+		_ => format!("${:.2}", calendar_config.daily_cash + calendar_config.daily_cash_step * (day - 1) as f64)
+	}
+}
+
+/// Fills in the next day of the player's login calendar and gives its reward.
+/// Returns a message describing the reward, if the calendar wasn't already full.
+pub async fn claim_calendar_day(ctx: &Context, player: &mut Player, update: &mut Document) -> BotResult<Option<String>> {
+	let calendar_config = config::get().calendar.clone();
+	let month = current_month(player);
+	if player.calendar.month != month {
+		player.calendar.month = month;
+		player.calendar.claimed = 0;
+	}
+	if player.calendar.claimed >= CALENDAR_DAYS {
+		return Ok(None);
+	}
+	player.calendar.claimed += 1;
+	let day = player.calendar.claimed;
+	update.insert("calendar", doc! { "month": &player.calendar.month, "claimed": player.calendar.claimed });
+	let reward = match day {
+		d if d == calendar_config.pack_day => {
+			let set = match calendar_config.pack_set.as_str() {
+				"" => None,
+				set_id => get_set(set_id).await.ok()
+			};
+			let set = match set {
+				Some(x) => x,
+				None => match get_sets().await.into_iter().max_by_key(|s| s.release_date) {
+					Some(x) => x,
+					None => return Err(Error::NotFound(String::from("set for the calendar pack")))
+				}
+			};
+			*player.packs.entry(set.id()).or_insert(0) += calendar_config.pack_amount;
+			let mut player_packs = Document::new();
+			for (pck, amt) in player.packs.iter() {
+				player_packs.insert(pck, amt);
+			}
+			update.insert("packs", player_packs);
+			format!("**{}** **{}** pack(s)", calendar_config.pack_amount, set.name)
+		},
+		d if d == calendar_config.token_day => {
+			player.tokens += calendar_config.tokens;
+			player.total_tokens += calendar_config.tokens;
+			update.insert("tokens", player.tokens);
+			update.insert("total_tokens", player.total_tokens);
+			format!("**{}** tokens", calendar_config.tokens)
+		},
+		d if d == calendar_config.rare_day => {
+			let card = get_rare_cards_from_cache(ctx)
+				.await
+				.into_iter()
+				.choose(&mut rand::thread_rng());
+			let card = match card {
+				Some(x) => x,
+				None => return Err(Error::NotFound(String::from("rare card for the calendar")))
+			};
+			*player.cards.entry(card.id()).or_insert(0) += 1;
+			player.total_cards += 1;
+			let mut player_cards = Document::new();
+			for (crd, amt) in player.cards.iter() {
+				player_cards.insert(crd, amt);
+			}
+			update.insert("cards", player_cards);
+			update.insert("total_cards", player.total_cards);
+			format!("a **{}** (_{}_)", card.name, card.id())
+		},
+		_ => {
+			let cash = calendar_config.daily_cash + calendar_config.daily_cash_step * (day - 1) as f64;
+			player.cash += cash;
+			player.total_cash += cash;
+			update.insert("cash", player.cash);
+			update.insert("total_cash", player.total_cash);
+			format!("**${:.2}**", cash)
+		}
+	};
+
+	Ok(Some(format!("Login calendar day **{}**: you got {}!", day, reward)))
+}
+
+#[command("calendar")]
+#[aliases("cal", "login")]
+#[description("Shows this month's login calendar and its rewards")]
+async fn calendar_command(ctx: &Context, msg: &Message) -> CommandResult {
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let calendar_config = config::get().calendar.clone();
+	let claimed = match player.calendar.month == current_month(&player) {
+		true => player.calendar.claimed,
+		false => 0
+	};
+	let milestones = vec![calendar_config.pack_day, calendar_config.token_day, calendar_config.rare_day];
+	let mut desc = String::from("Every daily you claim fills in the next day. The calendar starts over each month.\n\n");
+	for week in 0..(CALENDAR_DAYS / 7) {
+		for day in (week * 7 + 1)..=(week * 7 + 7) {
+			let square = if day <= claimed {
+				"✅"
+			} else if milestones.contains(&day) {
+				"🎁"
+			} else {
+				"⬜"
+			};
+			desc.push_str(square);
+		}
+		desc.push_str(&format!(" _days {}-{}_\n", week * 7 + 1, week * 7 + 7));
+	}
+	desc.push_str("\n");
+	for day in milestones {
+		desc.push_str(&format!("**Day {}:** {}\n", day, day_reward(&calendar_config, day)));
+	}
+	match claimed < CALENDAR_DAYS {
+		true => desc.push_str(&format!("\nYour next daily gives **{}**", day_reward(&calendar_config, claimed + 1))),
+		false => desc.push_str("\nYou've completed this month's calendar!")
+	}
+	msg
+		.channel_id
+		.send_message(&ctx.http, |m| {
+			m.embed(|e| {
+				e
+					.title(format!("Login Calendar - {}", Utc::now().with_timezone(&player.tz()).format("%B %Y")))
+					.description(&desc)
+					.colour(Colour::from_rgb(255, 50, 20))
+			})
+		})
+		.await?;
+
+	Ok(())
+}
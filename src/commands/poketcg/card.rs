@@ -37,7 +37,7 @@ use serenity::{
 	},
 	prelude::*
 };
-use crate::{Cache, CardCache, sets::Set};
+use crate::{Cache, CardCache, logging, sets::Set};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Card {
@@ -49,7 +49,29 @@ pub struct Card {
 	pub number: String,
 	pub price: f64,
 	pub image: String,
-	pub rarity: String
+	pub rarity: String,
+	// Pokémon, Trainer or Energy
+	#[serde(default)]
+	pub supertype: String,
+	// eg. "Basic", "Stage 1", "Item", "ACE SPEC" or "Radiant"
+	#[serde(default)]
+	pub subtypes: Vec<String>,
+	// The energy types of a Pokémon
+	#[serde(default)]
+	pub types: Vec<String>,
+	#[serde(default)]
+	pub regulation_mark: String,
+	// "Legal" or "Banned" by format, formats the card isn't in are left out
+	#[serde(default)]
+	pub legalities: HashMap<String, String>,
+	// The name of the Pokémon this evolves from
+	#[serde(default)]
+	pub evolves_from: String,
+	// How much energy each of the Pokémon's attacks costs
+	#[serde(default)]
+	pub attack_costs: Vec<i64>,
+	#[serde(default)]
+	pub hp: i64
 }
 
 impl Card {
@@ -83,6 +105,28 @@ impl Card {
 			Some(x) => String::from(x.as_str().unwrap()),
 			None => String::from("Unknown")
 		};
+		let strings = |key: &str| match obj[key].as_array() {
+			Some(x) => x
+				.iter()
+				.filter_map(|v| v.as_str())
+				.map(|v| String::from(v))
+				.collect::<Vec<String>>(),
+			None => vec![]
+		};
+		let attack_costs = match obj["attacks"].as_array() {
+			Some(x) => x
+				.iter()
+				.filter_map(|a| a["convertedEnergyCost"].as_i64())
+				.collect::<Vec<i64>>(),
+			None => vec![]
+		};
+		let legalities = match obj["legalities"].as_object() {
+			Some(x) => x
+				.iter()
+				.filter_map(|(format, legality)| Some((format.clone(), String::from(legality.as_str()?))))
+				.collect::<HashMap<String, String>>(),
+			None => HashMap::new()
+		};
 
 		Self {
 			id: None,
@@ -92,7 +136,15 @@ impl Card {
 			number: String::from(obj["number"].as_str().unwrap()),
 			price,
 			image: String::from(obj["images"]["large"].as_str().unwrap()),
-			rarity
+			rarity,
+			supertype: String::from(obj["supertype"].as_str().unwrap_or("")),
+			subtypes: strings("subtypes"),
+			types: strings("types"),
+			regulation_mark: String::from(obj["regulationMark"].as_str().unwrap_or("")),
+			legalities,
+			evolves_from: String::from(obj["evolvesFrom"].as_str().unwrap_or("")),
+			attack_costs,
+			hp: obj["hp"].as_str().unwrap_or("0").parse::<i64>().unwrap_or(0)
 		}
 	}
 }
@@ -152,12 +204,12 @@ impl PartialEq for Card {
 
 #[async_trait]
 impl Scrollable for Vec<Card> {
-	async fn scroll_through(&self, ctx: &Context, msg: &Message) -> Result<(), String> {
+	async fn scroll_through(&self, ctx: &Context, msg: &Message) -> BotResult<()> {
 		let left_arrow = ReactionType::try_from("⬅️").expect("No left arrow");
 		let right_arrow = ReactionType::try_from("➡️").expect("No right arrow");
 		let save_icon = ReactionType::try_from("💾").expect("No floppy disk");
 		let binder_icon = ReactionType::try_from(":pokeball:972277627077423124").expect("No pokeball");
-		let mut player = player::get_player(msg.author.id.0).await;
+		let mut player = player::get_player(msg.author.id.0, msg.guild_id).await?;
 		let embeds = self.iter().map(|e| e.embed()).collect::<Vec<_>>();
 		let mut idx: i16 = 0;
 		let mut content = String::from("");
@@ -215,7 +267,7 @@ impl Scrollable for Vec<Card> {
 							player.savelist.push(card_id.clone());
 							content = format!("**{}** added to your savelist!", &self[idx as usize].card_name());
 						}
-						player::update_player(&player, doc! { "$set": { "savelist": player.savelist.clone()}}).await;
+						player::update_player(&player, doc! { "$set": { "savelist": player.savelist.clone()}}).await?;
 					},
 					"pokeball:972277627077423124" => {
 						let card_id = self[idx as usize].card_id().clone();
@@ -224,7 +276,7 @@ impl Scrollable for Vec<Card> {
 						} else if &self[idx as usize].set().id() != &player.current_binder.set {
 							content = String::from("This card doesn't go in your binder!");
 						} else {
-							let current_binder_set = sets::get_set(&player.current_binder.set).await.unwrap();
+							let current_binder_set = sets::get_set(&player.current_binder.set).await?;
 							let mut player_update = Document::new();
 							*player.cards.entry(self[idx as usize].card_id()).or_insert(0) -= 1;
 							if *player.cards.entry(self[idx as usize].card_id()).or_insert(0) == 0 {
@@ -236,11 +288,9 @@ impl Scrollable for Vec<Card> {
 							}
 							player_update.insert("cards", player_cards);
 							player.current_binder.cards.push(self[idx as usize].card_id().clone());
-							if player.current_binder.is_complete(ctx).await {
-								player.completed_binders.push(player.current_binder.set);
-								player.current_binder = binder::Binder::empty();
-								player_update.insert("completed_binders", player.completed_binders.clone());
-								content = format!("You completed the **{}** binder!", current_binder_set.name);
+							if player.current_binder.is_complete(ctx).await? {
+								let bonus = binder::complete_binder(&mut player, &mut player_update);
+								content = binder::completed_binder_message(&current_binder_set.name, bonus);
 							} else {
 								content = format!("You added **{}** to your binder!", &self[idx as usize].card_name());
 							}
@@ -250,11 +300,11 @@ impl Scrollable for Vec<Card> {
 								player_cards.insert(crd, amt);
 							}
 							player_update.insert("cards", player_cards);
-							player::update_player(&player, doc! { "$set": player_update }).await;
+							player::update_player(&player, doc! { "$set": player_update }).await?;
 						}
 					}
 					_ => {
-						println!("{}", &emoji.as_data().as_str());
+						debug!(target: logging::CATALOG, "Unknown reaction {}", &emoji.as_data().as_str());
 						continue
 					}
 				};
@@ -294,10 +344,17 @@ impl Scrollable for Vec<Card> {
 pub async fn get_multiple_cards_by_id(ctx: &Context, card_ids: Vec<String>) -> Vec<Card> {
 	let mut ret = vec![];
 	let cached_cards = get_multiple_cards_from_cache(ctx, &card_ids).await;
-	if cached_cards.len() == card_ids.len() {
+	// Only ask the API for the cards that aren't cached, once each
+	let mut uncached_ids = card_ids
+		.into_iter()
+		.filter(|id| !cached_cards.iter().any(|c| &c.card_id == id))
+		.collect::<Vec<String>>();
+	uncached_ids.sort();
+	uncached_ids.dedup();
+	if uncached_ids.len() == 0 {
 		return cached_cards;
 	}
-	let card_id_chunks: Vec<Vec<String>> = card_ids.chunks(250).map(|x| x.to_vec()).collect();
+	let card_id_chunks: Vec<Vec<String>> = uncached_ids.chunks(250).map(|x| x.to_vec()).collect();
 	for card_id_chunk in card_id_chunks {
 		let inner_query = card_id_chunk
 			.iter()
@@ -465,6 +522,8 @@ async fn get_card_from_cache(ctx: &Context, id: &str) -> Option<Card> {
 
 		cache.get(id).map_or(None, |x| Some(x.clone()))
 	};
+	#[cfg(feature = "metrics")]
+	crate::metrics::record_cache_lookups(cached_card.is_some() as u64, cached_card.is_none() as u64);
 	let ret = match cached_card {
 		Some(x) => Some(x.card),
 		None => None
@@ -504,6 +563,8 @@ async fn get_multiple_cards_from_cache(ctx: &Context, card_ids: &Vec<String>) ->
 			.map(|(_, cc)| cc.card.clone())
 			.collect::<Vec<Card>>()
 	};
+	#[cfg(feature = "metrics")]
+	crate::metrics::record_cache_lookups(cards.len() as u64, card_ids.len().saturating_sub(cards.len()) as u64);
 
 	cards
 }
@@ -590,8 +651,12 @@ pub async fn get_rainbow_cards_from_cache(ctx: &Context) -> Vec<Card> {
 }
 
 #[command("card")]
+#[description("Searches for a card with a matching name")]
+#[usage("<name>")]
+#[example("charizard")]
+#[min_args(1)]
 async fn search_card(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
-	let _player = player::get_player(msg.author.id.0).await;
+	let _player = player::get_player(msg.author.id.0, msg.guild_id).await?;
 	let search_str = args.rest();
 	let cards = get_cards_with_query(ctx, &format!("{}", search_str))
 		.await;
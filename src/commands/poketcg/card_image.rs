@@ -1,6 +1,10 @@
-use std::io::{Cursor};
+use std::{
+	fs,
+	io::{Cursor},
+	path::Path
+};
 
-use image::{io::Reader, DynamicImage};
+use image::{imageops::FilterType, io::Reader, DynamicImage};
 use mongodb::{
 	bson::{
 		doc,
@@ -10,10 +14,21 @@ use mongodb::{
 };
 use serde::{Serialize, Deserialize};
 
-use crate::commands::get_client;
+use crate::{
+	commands::get_client,
+	error::BotResult,
+	logging
+};
 
 use super::card::Card;
 
+// Card images are 734x1024, thumbnails keep the same ratio
+pub const THUMBNAIL_WIDTH: u32 = 184;
+pub const THUMBNAIL_HEIGHT: u32 = 256;
+
+// Where thumbnails are saved on disk, set CARD_CACHE_DIR to change it
+const DEFAULT_CACHE_DIR: &str = "card_cache";
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CardImage {
 	#[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
@@ -23,11 +38,11 @@ pub struct CardImage {
 }
 
 impl CardImage {
-	pub fn to_dyn_image(&self) -> DynamicImage {
-		let data = base64::decode(&self.data).unwrap();
-		let img = image::load_from_memory(&data).unwrap();
-		
-		img
+	pub fn to_dyn_image(&self) -> BotResult<DynamicImage> {
+		let data = base64::decode(&self.data)?;
+		let img = image::load_from_memory(&data)?;
+
+		Ok(img)
 	}
 }
 
@@ -39,43 +54,98 @@ async fn get_card_image_collection() -> Collection<CardImage> {
 	collection
 }
 
+async fn get_card_thumbnail_collection() -> Collection<CardImage> {
+	let client = get_client().await.unwrap();
+	let collection = client.database("poketcg").collection::<CardImage>("thumbnails");
+
+	collection
+}
+
 
 
-pub async fn get_card_image(card: &Card) -> CardImage {
+pub async fn get_card_image(card: &Card) -> BotResult<CardImage> {
 	let card_image_collection = get_card_image_collection().await;
 	let card_image = card_image_collection
 		.find_one(doc! { "card_id": &card.card_id }, None)
-		.await
-		.unwrap();
+		.await?;
 	match card_image {
-		Some(x) => return x,
-		None => return add_card_image(card).await,
+		Some(x) => Ok(x),
+		None => add_card_image(card).await,
 	}
 }
 
-async fn add_card_image(card: &Card) -> CardImage {
+async fn download_card_image(card: &Card) -> BotResult<DynamicImage> {
 	let resp = reqwest::Client::new()
 		.get(&card.image)
-		.send().await.unwrap()
-		.bytes().await.unwrap();
+		.send().await?
+		.bytes().await?;
 	let reader = Reader::new(Cursor::new(resp))
-		.with_guessed_format()
-		.expect("Can't get image");
-	let image = reader.decode().unwrap().grayscale();
+		.with_guessed_format()?;
+
+	Ok(reader.decode()?)
+}
+
+fn encode_card_image(card: &Card, image: DynamicImage) -> BotResult<CardImage> {
 	let mut buf: Vec<u8> = vec![];
 	let mut bw = Cursor::new(&mut buf);
-	image.write_to(&mut bw, image::ImageOutputFormat::Png).unwrap();
+	image.write_to(&mut bw, image::ImageOutputFormat::Png)?;
 	let img_b64 = base64::encode(&buf);
-	let card_image = CardImage{
+
+	Ok(CardImage{
 		id: None,
 		card_id: String::from(&card.card_id),
 		data: img_b64
-	};
+	})
+}
+
+/// A small full colour image of the card, for putting several cards in one image.
+pub async fn get_card_thumbnail(card: &Card) -> BotResult<CardImage> {
+	let card_thumbnail_collection = get_card_thumbnail_collection().await;
+	let card_thumbnail = card_thumbnail_collection
+		.find_one(doc! { "card_id": &card.card_id }, None)
+		.await?;
+	match card_thumbnail {
+		Some(x) => Ok(x),
+		None => add_card_thumbnail(card).await,
+	}
+}
+
+/// The card's thumbnail, cached on disk so images with a lot of cards don't have to get each one from the database.
+pub async fn get_card_thumbnail_image(card: &Card) -> BotResult<DynamicImage> {
+	let dir = dotenv::var("CARD_CACHE_DIR").unwrap_or(String::from(DEFAULT_CACHE_DIR));
+	let path = Path::new(&dir).join(format!("{}.png", card.card_id));
+	if let Ok(img) = image::open(&path) {
+		return Ok(img);
+	}
+	let img = get_card_thumbnail(card).await?.to_dyn_image()?;
+	// The thumbnail is still in the database, so it's fine if it can't be saved
+	if let Err(why) = fs::create_dir_all(&dir).map_err(|e| e.to_string()).and_then(|_| img.save(&path).map_err(|e| e.to_string())) {
+		warn!(target: logging::CATALOG, "Couldn't cache the thumbnail for {}: {}", card.card_id, why);
+	}
+
+	Ok(img)
+}
+
+async fn add_card_thumbnail(card: &Card) -> BotResult<CardImage> {
+	let image = download_card_image(card)
+		.await?
+		.resize_exact(THUMBNAIL_WIDTH, THUMBNAIL_HEIGHT, FilterType::Triangle);
+	let card_thumbnail = encode_card_image(card, image)?;
+	let card_thumbnail_collection = get_card_thumbnail_collection().await;
+	card_thumbnail_collection
+		.insert_one(card_thumbnail.clone(), None)
+		.await?;
+
+	Ok(card_thumbnail)
+}
+
+async fn add_card_image(card: &Card) -> BotResult<CardImage> {
+	let image = download_card_image(card).await?.grayscale();
+	let card_image = encode_card_image(card, image)?;
 	let card_image_collection = get_card_image_collection().await;
 	card_image_collection
 	.insert_one(card_image.clone(), None)
-		.await
-		.unwrap();
+		.await?;
 
-	card_image
+	Ok(card_image)
 }
\ No newline at end of file
@@ -0,0 +1,141 @@
+use std::{
+	collections::HashSet,
+	sync::Mutex
+};
+use mongodb::bson::doc;
+use serenity::{
+	framework::{
+		standard::{
+			macros::{
+				command
+			},
+			Args,
+			CommandResult
+		},
+	},
+	model::{
+		channel::{
+			Message,
+		},
+	},
+	utils::{
+		Colour
+	},
+	prelude::*
+};
+
+use crate::error::BotResult;
+
+use super::{
+	guild::get_economy,
+	player::{
+		find_player,
+		update_player
+	}
+};
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+static CHANGELOG: &str = include_str!("../../../CHANGELOG.md");
+
+// Discord's limit for an embed description
+static MAX_NOTES_LEN: usize = 4096;
+
+// Commands that don't touch the player, so they don't need to check what's new
+const SKIPPED_COMMANDS: &'static [&str] = &["changelog", "help"];
+
+lazy_static! {
+	// Discord ids of players who already know about this version, so the database isn't read before every command
+	static ref SEEN: Mutex<HashSet<u64>> = Mutex::new(HashSet::new());
+}
+
+/// Every version in the changelog with its patch notes, newest first.
+pub fn versions() -> Vec<(&'static str, &'static str)> {
+	let mut ret = vec![];
+	let mut sections = CHANGELOG.split("\n## ");
+	sections.next(); // Everything before the first version
+	for section in sections {
+		let (version, notes) = match section.split_once('\n') {
+			Some(x) => x,
+			None => (section, "")
+		};
+		ret.push((version.trim(), notes.trim()));
+	}
+
+	ret
+}
+
+pub fn patch_notes(version: &str) -> Option<&'static str> {
+	let version = version.trim_start_matches('v');
+
+	versions()
+		.into_iter()
+		.find(|(v, _)| *v == version)
+		.map(|(_, notes)| notes)
+}
+
+/// Tells the player about the new version the first time they use the bot after an update.
+/// Someone who isn't a player yet starts on the current version, so they're skipped.
+pub async fn check_whats_new(ctx: &Context, msg: &Message, command_name: &str) -> BotResult<()> {
+	if SKIPPED_COMMANDS.contains(&command_name) || SEEN.lock().unwrap().contains(&msg.author.id.0) {
+		return Ok(());
+	}
+	let economy = get_economy(msg.guild_id).await?;
+	let mut player = match find_player(msg.author.id.0 as i64, economy).await? {
+		Some(x) => x,
+		None => return Ok(())
+	};
+	SEEN.lock().unwrap().insert(msg.author.id.0);
+	if player.last_seen_version == VERSION {
+		return Ok(());
+	}
+	player.last_seen_version = String::from(VERSION);
+	update_player(&player, doc! { "$set": { "last_seen_version": &player.last_seen_version } }).await?;
+	let _ = msg.reply(&ctx.http, format!("The bot was updated to **v{}**! Use **.changelog** to see what's new", VERSION)).await;
+
+	Ok(())
+}
+
+#[command("changelog")]
+#[aliases("patchnotes", "whatsnew")]
+#[description("DMs you the patch notes for a version")]
+#[usage("[version - Default: newest]")]
+#[example("0.1.0")]
+async fn changelog_command(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+	let version = match args.single::<String>() {
+		Ok(x) => String::from(x.trim_start_matches('v')),
+		Err(_) => String::from(VERSION)
+	};
+	let notes = match patch_notes(&version) {
+		Some(x) => x,
+		None => {
+			let versions = versions()
+				.iter()
+				.map(|(v, _)| format!("**{}**", v))
+				.collect::<Vec<String>>()
+				.join(", ");
+			msg.reply(&ctx.http, format!("There are no patch notes for **{}**. Here are the versions: {}", version, versions)).await?;
+			return Ok(());
+		}
+	};
+	let notes = match notes.char_indices().nth(MAX_NOTES_LEN) {
+		Some((i, _)) => &notes[..i],
+		None => notes
+	};
+	let dm = msg.author
+		.direct_message(&ctx.http, |m| {
+			m.embed(|e| {
+				e
+					.title(format!("Patch Notes - v{}", version))
+					.description(notes)
+					.colour(Colour::from_rgb(255, 50, 20))
+			})
+		})
+		.await;
+	match dm {
+		Ok(_) => msg.reply(&ctx.http, format!("Sent you the patch notes for **v{}**", version)).await?,
+		Err(_) => msg.reply(&ctx.http, "I couldn't DM you. Make sure you can get DMs from this server!").await?
+	};
+
+	Ok(())
+}
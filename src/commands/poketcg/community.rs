@@ -0,0 +1,372 @@
+use std::collections::HashMap;
+use mongodb::{
+	bson::{
+		doc,
+		oid::ObjectId
+	},
+	options::{
+		FindOneAndUpdateOptions,
+		ReturnDocument
+	},
+	Collection
+};
+use serde::{Serialize, Deserialize};
+use serenity::{
+	framework::{
+		standard::{
+			macros::{
+				command
+			},
+			Args,
+			CommandResult
+		},
+	},
+	model::{
+		channel::{
+			Message,
+		},
+	},
+	utils::{
+		Colour
+	},
+	prelude::*
+};
+
+use crate::{
+	commands::get_client,
+	error::{
+		BotResult,
+		Error
+	},
+	logging
+};
+
+use super::{
+	config,
+	guild::economy_filter,
+	player::{
+		self,
+		get_player,
+		update_player
+	},
+	slot,
+	store
+};
+
+// How many contributors are credited when a goal is met
+static CREDITED_CONTRIBUTORS: usize = 10;
+
+fn def_0() -> i64 {
+	0
+}
+
+pub struct CommunityGoal {
+	pub key: &'static str,
+	pub name: &'static str,
+	pub aliases: &'static [&'static str],
+	pub description: &'static str,
+	pub cost: f64
+}
+
+impl CommunityGoal {
+	/// The goal's cost, from the economy config if it overrides this goal.
+	pub fn cost(&self) -> f64 {
+		config::get().community_cost(self.key).unwrap_or(self.cost)
+	}
+
+	fn matches(&self, name: &str) -> bool {
+		let name = name.to_lowercase();
+
+		self.key == name || self.name.to_lowercase() == name || self.aliases.contains(&name.as_str())
+	}
+}
+
+pub const COMMUNITY_GOALS: &'static [CommunityGoal] = &[
+	CommunityGoal {
+		key: "daily_reset",
+		name: "Daily Reset",
+		aliases: &["daily", "dr"],
+		description: "Everyone's daily is ready right away",
+		cost: 25000.0
+	},
+	CommunityGoal {
+		key: "tokenshop_refresh",
+		name: "Token Shop Refresh",
+		aliases: &["tokenshop", "ts"],
+		description: "The token shop restocks with new prizes",
+		cost: 15000.0
+	},
+	CommunityGoal {
+		key: "store_refresh",
+		name: "Store Refresh",
+		aliases: &["store", "st"],
+		description: "The store restocks with new packs",
+		cost: 10000.0
+	},
+	CommunityGoal {
+		key: "pack_reset",
+		name: "Pack Reset",
+		aliases: &["packs", "pr"],
+		description: "Everyone gets their daily packs again",
+		cost: 50000.0
+	},
+	CommunityGoal {
+		key: "slot_reset",
+		name: "Slot Reset",
+		aliases: &["slots", "sr"],
+		description: "Everyone's slot rolls are refilled",
+		cost: 20000.0
+	},
+];
+
+pub fn find_goal(name: &str) -> Option<&'static CommunityGoal> {
+	COMMUNITY_GOALS.iter().find(|g| g.matches(name))
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CommunityFund {
+	#[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+	id: Option<ObjectId>,
+	pub goal: String,
+	#[serde(default)]
+	pub amount: f64,
+	// Cash put in towards the current goal, keyed by discord id
+	#[serde(default)]
+	pub contributors: HashMap<String, f64>,
+	// How many times the goal has been met
+	#[serde(default = "def_0")]
+	pub completed: i64,
+	#[serde(default = "def_0")]
+	pub economy: i64
+}
+
+impl CommunityFund {
+	fn new(goal: &str, economy: i64) -> Self {
+		Self {
+			id: None,
+			goal: String::from(goal),
+			amount: 0.0,
+			contributors: HashMap::new(),
+			completed: 0,
+			economy
+		}
+	}
+}
+
+fn fund_filter(goal: &str, economy: i64) -> mongodb::bson::Document {
+	let mut filter = economy_filter(economy);
+	filter.insert("goal", goal);
+
+	filter
+}
+
+async fn get_community_fund_collection() -> BotResult<Collection<CommunityFund>> {
+	let client = get_client().await?;
+	let collection = client.database("poketcg").collection::<CommunityFund>("community");
+
+	Ok(collection)
+}
+
+pub async fn get_community_fund(goal: &str, economy: i64) -> BotResult<CommunityFund> {
+	let community_fund_collection = get_community_fund_collection().await?;
+	let community_fund = community_fund_collection
+		.find_one(fund_filter(goal, economy), None)
+		.await?;
+	match community_fund {
+		Some(x) => Ok(x),
+		None => add_community_fund(goal, economy).await
+	}
+}
+
+async fn add_community_fund(goal: &str, economy: i64) -> BotResult<CommunityFund> {
+	let mut ret = CommunityFund::new(goal, economy);
+	let community_fund_collection = get_community_fund_collection().await?;
+	let result = community_fund_collection
+		.insert_one(&ret, None)
+		.await?;
+	// Contributions update the fund by id, so the new fund needs it
+	ret.id = result.inserted_id.as_object_id();
+
+	Ok(ret)
+}
+
+async fn contribute(fund: &CommunityFund, discord_id: i64, amount: f64) -> BotResult<()> {
+	let id = match fund.id {
+		Some(x) => x,
+		None => return Err(Error::NotFound(String::from("community fund")))
+	};
+	let community_fund_collection = get_community_fund_collection().await?;
+	community_fund_collection
+		.update_one(
+			doc! { "_id": id },
+			doc! { "$inc": {
+				"amount": amount,
+				format!("contributors.{}", discord_id): amount
+			}},
+			None)
+		.await?;
+
+	Ok(())
+}
+
+/// Empties the fund if its goal has been met and returns it as it was, so only
+/// one contribution ever completes a goal.
+async fn take_completed_fund(goal: &CommunityGoal, economy: i64) -> BotResult<Option<CommunityFund>> {
+	let mut filter = fund_filter(goal.key, economy);
+	filter.insert("amount", doc! { "$gte": goal.cost() });
+	let options = FindOneAndUpdateOptions::builder()
+		.return_document(ReturnDocument::Before)
+		.build();
+	let community_fund_collection = get_community_fund_collection().await?;
+	let community_fund = community_fund_collection
+		.find_one_and_update(
+			filter,
+			doc! {
+				"$set": { "amount": 0.0, "contributors": {} },
+				"$inc": { "completed": 1 }
+			},
+			options)
+		.await?;
+
+	Ok(community_fund)
+}
+
+async fn apply_goal(ctx: &Context, goal: &CommunityGoal, economy: i64) -> BotResult<()> {
+	match goal.key {
+		"daily_reset" => {
+			let updated = player::reset_daily_timers_in(economy).await?;
+			info!(target: logging::ECONOMY, "Community daily reset for {} players in economy {}", updated, economy);
+		},
+		"tokenshop_refresh" => {
+			slot::refresh_token_shop(ctx, economy).await?;
+			info!(target: logging::ECONOMY, "Community token shop refresh in economy {}", economy);
+		},
+		"store_refresh" => {
+			store::refresh_store(economy).await?;
+			info!(target: logging::ECONOMY, "Community store refresh in economy {}", economy);
+		},
+		"pack_reset" => {
+			let updated = player::reset_packs_in(economy).await?;
+			info!(target: logging::ECONOMY, "Community pack reset for {} players in economy {}", updated, economy);
+		},
+		"slot_reset" => {
+			let updated = player::reset_slots_in(economy).await?;
+			info!(target: logging::ECONOMY, "Community slot reset for {} players in economy {}", updated, economy);
+		},
+		_ => ()
+	}
+
+	Ok(())
+}
+
+fn progress_bar(amount: f64, cost: f64) -> String {
+	let filled = ((amount / cost) * 10.0).floor().min(10.0) as usize;
+
+	format!("{}{}", "🟩".repeat(filled), "⬜".repeat(10 - filled))
+}
+
+#[command("community")]
+#[aliases("comm", "fund")]
+#[sub_commands(community_contribute)]
+#[description("Shows the community fund goals and how close they are")]
+async fn community_main(ctx: &Context, msg: &Message) -> CommandResult {
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let mut desc = String::from("Everyone can put cash towards these goals. When one is met, it happens for every player!\n");
+	desc.push_str("To chip in use **.community contribute <goal> <amount>**\n\n");
+	for goal in COMMUNITY_GOALS {
+		let fund = get_community_fund(goal.key, player.economy).await?;
+		let cost = goal.cost();
+		let yours = fund.contributors.get(&player.discord_id.to_string()).unwrap_or(&0.0);
+		desc.push_str(&format!("**{}** _({})_: {}\n", goal.name, goal.aliases[0], goal.description));
+		desc.push_str(&format!("{} **${:.2}** / ${:.2}", progress_bar(fund.amount, cost), fund.amount, cost));
+		if *yours > 0.0 {
+			desc.push_str(&format!(" _(you: ${:.2})_", yours));
+		}
+		desc.push_str("\n\n");
+	}
+	msg
+		.channel_id
+		.send_message(&ctx.http, |m| {
+			m.embed(|e| {
+				e
+					.title("Community Fund")
+					.description(&desc)
+					.colour(Colour::from_rgb(255, 50, 20))
+			})
+		})
+		.await?;
+
+	Ok(())
+}
+
+#[command("contribute")]
+#[aliases("c", "donate", "give")]
+#[description("Puts cash towards a community goal")]
+#[usage("<goal> <amount>")]
+#[example("store 100")]
+#[min_args(2)]
+async fn community_contribute(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+	let goal = match args.single::<String>() {
+		Ok(x) => find_goal(&x),
+		Err(_) => None
+	};
+	let goal = match goal {
+		Some(x) => x,
+		None => {
+			msg.reply(&ctx.http, "That's not a community goal. Use **.community** to see them").await?;
+			return Ok(());
+		}
+	};
+	let amount = match args.single::<f64>() {
+		Ok(x) if x > 0.0 => x,
+		_ => {
+			msg.reply(&ctx.http, "How much do you want to put in? Use **.community contribute <goal> <amount>**").await?;
+			return Ok(());
+		}
+	};
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let fund = get_community_fund(goal.key, player.economy).await?;
+	// Nobody pays more than what's left of the goal
+	let amount = vec![amount, player.cash, goal.cost() - fund.amount]
+		.into_iter()
+		.fold(f64::INFINITY, f64::min);
+	if amount <= 0.0 {
+		msg.reply(&ctx.http, "You don't have any cash to put in").await?;
+		return Ok(());
+	}
+	// The cash only leaves the player once it's in the fund
+	contribute(&fund, player.discord_id, amount).await?;
+	player.cash -= amount;
+	update_player(&player, doc! { "$set": { "cash": player.cash } }).await?;
+	msg.reply(&ctx.http, format!("You put **${:.2}** towards the **{}**", amount, goal.name)).await?;
+	let completed = match take_completed_fund(goal, player.economy).await? {
+		Some(x) => x,
+		None => return Ok(())
+	};
+	apply_goal(ctx, goal, player.economy).await?;
+	let mut contributors = completed.contributors
+		.iter()
+		.collect::<Vec<(&String, &f64)>>();
+	contributors.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap());
+	let mut desc = format!("The community raised **${:.2}** and the **{}** goal was met!\n{}\n\n", completed.amount, goal.name, goal.description);
+	desc.push_str("**Thanks to:**\n");
+	for (discord_id, amt) in contributors.iter().take(CREDITED_CONTRIBUTORS) {
+		desc.push_str(&format!("<@{}> - ${:.2}\n", discord_id, amt));
+	}
+	if contributors.len() > CREDITED_CONTRIBUTORS {
+		desc.push_str(&format!("_and {} more_\n", contributors.len() - CREDITED_CONTRIBUTORS));
+	}
+	msg
+		.channel_id
+		.send_message(&ctx.http, |m| {
+			m.embed(|e| {
+				e
+					.title(format!("🎉 {}! 🎉", goal.name))
+					.description(&desc)
+					.colour(Colour::from_rgb(255, 50, 20))
+			})
+		})
+		.await?;
+
+	Ok(())
+}
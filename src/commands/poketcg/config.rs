@@ -0,0 +1,426 @@
+use std::{
+	collections::HashMap,
+	sync::{
+		Arc,
+		RwLock
+	},
+	fs,
+};
+use serde::{Serialize, Deserialize};
+use serenity::{
+	framework::{
+		standard::{
+			macros::{
+				command
+			},
+			CommandResult
+		},
+	},
+	model::{
+		channel::{
+			Message,
+		},
+	},
+	prelude::*
+};
+
+use crate::BOTTEST_CHECK;
+
+lazy_static! {
+	static ref CONFIG: RwLock<Arc<EconomyConfig>> = RwLock::new(Arc::new(EconomyConfig::default()));
+}
+
+fn config_path() -> String {
+	dotenv::var("ECONOMY_CONFIG").unwrap_or(String::from("economy.json"))
+}
+
+/// Gets the currently loaded economy config. Cheap to call, it's only an Arc clone.
+pub fn get() -> Arc<EconomyConfig> {
+	CONFIG.read().unwrap().clone()
+}
+
+/// Reads the config file and swaps it in. If the file can't be read or parsed
+/// the config that was already loaded is kept.
+pub fn reload() -> Result<(), String> {
+	let path = config_path();
+	let data = fs::read_to_string(&path)
+		.map_err(|e| format!("Couldn't read {}: {}", path, e))?;
+	let config: EconomyConfig = serde_json::from_str(&data)
+		.map_err(|e| format!("Couldn't parse {}: {}", path, e))?;
+	*CONFIG.write().unwrap() = Arc::new(config);
+
+	Ok(())
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CostCurve {
+	// base
+	Flat,
+	// base + step * level
+	Linear,
+	// base + step * (0 + 1 + ... + level)
+	Triangular,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpgradeTuning {
+	pub curve: CostCurve,
+	pub base: f64,
+	#[serde(default)]
+	pub step: f64,
+	pub max_level: i64
+}
+
+impl UpgradeTuning {
+	pub fn cost(&self, level: i64) -> f64 {
+		match self.curve {
+			CostCurve::Flat => self.base,
+			CostCurve::Linear => self.base + self.step * level as f64,
+			CostCurve::Triangular => self.base + self.step * (0..=level).sum::<i64>() as f64
+		}
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoreSlot {
+	pub name: String,
+	pub price_mult: f64,
+	pub packs: i64
+}
+
+impl StoreSlot {
+	fn new(name: &str, price_mult: f64, packs: i64) -> Self {
+		Self {
+			name: String::from(name),
+			price_mult,
+			packs
+		}
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DailyConfig {
+	pub min_cash: i64,
+	pub min_cash_cap: i64,
+	pub max_cash: i64,
+	pub max_cash_cap: i64,
+	// Percent chance of getting packs instead of cash, each streak day adds 1
+	pub pack_chance: i64,
+	pub pack_chance_cap: i64,
+	pub reward_mult_per_level: f64,
+	pub reset_hours: i64
+}
+
+impl Default for DailyConfig {
+	fn default() -> Self {
+		Self {
+			min_cash: 5,
+			min_cash_cap: 15,
+			max_cash: 20,
+			max_cash_cap: 40,
+			pack_chance: 2,
+			pack_chance_cap: 10,
+			reward_mult_per_level: 0.1,
+			reset_hours: 24
+		}
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PackConfig {
+	pub daily_packs: i64,
+	pub daily_packs_per_level: i64,
+	pub pack_limit: i64,
+	pub pack_limit_per_level: i64,
+	pub daily_slots: i64,
+	pub daily_slots_per_level: i64
+}
+
+impl Default for PackConfig {
+	fn default() -> Self {
+		Self {
+			daily_packs: 50,
+			daily_packs_per_level: 10,
+			pack_limit: 500,
+			pack_limit_per_level: 100,
+			daily_slots: 10,
+			daily_slots_per_level: 1
+		}
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuizConfig {
+	// Rewards are multiplied by the player's current multiplier
+	pub correct_reward: f64,
+	pub generation_reward: f64,
+	pub clefairy_reward: f64,
+	pub questions: i64,
+	pub reset_minutes: i64,
+	pub reset_minutes_per_level: i64,
+	pub mult_limit_per_level: i64
+}
+
+impl Default for QuizConfig {
+	fn default() -> Self {
+		Self {
+			correct_reward: 0.1,
+			generation_reward: 0.1,
+			clefairy_reward: 1.0,
+			questions: 5,
+			reset_minutes: 120,
+			reset_minutes_per_level: 10,
+			mult_limit_per_level: 10
+		}
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SlotConfig {
+	// Keyed by the slot option, "CherryPair" is any two cherries
+	pub payouts: HashMap<String, f64>,
+	pub reward_mult_per_level: f64
+}
+
+impl Default for SlotConfig {
+	fn default() -> Self {
+		let mut payouts = HashMap::new();
+		payouts.insert(String::from("7"), 500.0);
+		payouts.insert(String::from("R"), 200.0);
+		payouts.insert(String::from("Pikachu"), 120.0);
+		payouts.insert(String::from("Slowpoke"), 80.0);
+		payouts.insert(String::from("Magnemite"), 50.0);
+		payouts.insert(String::from("Shellder"), 30.0);
+		payouts.insert(String::from("Cherry"), 15.0);
+		payouts.insert(String::from("CherryPair"), 5.0);
+
+		Self {
+			payouts,
+			reward_mult_per_level: 0.1
+		}
+	}
+}
+
+impl SlotConfig {
+	pub fn payout(&self, option: &str) -> f64 {
+		*self.payouts.get(option).unwrap_or(&0.0)
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemPrice {
+	// Items without a price can't be bought with that currency
+	#[serde(default)]
+	pub cash: Option<f64>,
+	#[serde(default)]
+	pub tokens: Option<i64>
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EffectConfig {
+	// Extra weight given to the 7 and R on each reel
+	pub slot_luck_per_level: i64,
+	pub sell_bonus_per_level: f64,
+	// Fraction of the cash in a trade that is taken as a fee, trades are free at 0
+	pub trade_fee: f64,
+	pub trade_fee_reduction_per_level: f64,
+	// Token shop packs shown before any tokenshop_slot upgrades
+	pub tokenshop_slots: i64,
+	pub binder_bonus_per_level: f64
+}
+
+impl Default for EffectConfig {
+	fn default() -> Self {
+		Self {
+			slot_luck_per_level: 1,
+			sell_bonus_per_level: 0.02,
+			trade_fee: 0.0,
+			trade_fee_reduction_per_level: 0.02,
+			tokenshop_slots: 3,
+			binder_bonus_per_level: 50.0
+		}
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrestigeConfig {
+	// Cash needed for the first prestige, multiplied by cash_growth for each one after
+	pub cash_required: f64,
+	pub cash_growth: f64,
+	// Total upgrade levels needed for the first prestige
+	pub upgrade_levels_required: i64,
+	pub upgrade_levels_per_prestige: i64,
+	pub perm_multiplier_bonus: i64,
+	pub points: i64,
+	// Extra points for also giving up the cards and packs that aren't in a binder
+	pub card_reset_points: i64
+}
+
+impl Default for PrestigeConfig {
+	fn default() -> Self {
+		Self {
+			cash_required: 50000.0,
+			cash_growth: 2.0,
+			upgrade_levels_required: 60,
+			upgrade_levels_per_prestige: 20,
+			perm_multiplier_bonus: 10,
+			points: 1,
+			card_reset_points: 1
+		}
+	}
+}
+
+impl PrestigeConfig {
+	pub fn cash_required(&self, prestige: i64) -> f64 {
+		self.cash_required * self.cash_growth.powi(prestige as i32)
+	}
+
+	pub fn upgrade_levels_required(&self, prestige: i64) -> i64 {
+		self.upgrade_levels_required + self.upgrade_levels_per_prestige * prestige
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CalendarConfig {
+	// Cash for every calendar day, going up by the step each day
+	pub daily_cash: f64,
+	pub daily_cash_step: f64,
+	pub pack_day: i64,
+	// Empty for the newest set
+	pub pack_set: String,
+	pub pack_amount: i64,
+	pub token_day: i64,
+	pub tokens: i64,
+	pub rare_day: i64
+}
+
+impl Default for CalendarConfig {
+	fn default() -> Self {
+		Self {
+			daily_cash: 5.0,
+			daily_cash_step: 1.0,
+			pack_day: 7,
+			pack_set: String::from(""),
+			pack_amount: 1,
+			token_day: 14,
+			tokens: 500,
+			rare_day: 28
+		}
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EconomyConfig {
+	pub rarity_weights: HashMap<String, i64>,
+	pub slots: SlotConfig,
+	pub store_slots: Vec<StoreSlot>,
+	// Overrides for the tuning declared with each upgrade, keyed by the upgrade key
+	pub upgrades: HashMap<String, UpgradeTuning>,
+	pub effects: EffectConfig,
+	// Overrides for the prices declared with each item, keyed by the item key
+	pub items: HashMap<String, ItemPrice>,
+	// Overrides for the cost declared with each community goal, keyed by the goal key
+	pub community: HashMap<String, f64>,
+	pub daily: DailyConfig,
+	pub packs: PackConfig,
+	pub quiz: QuizConfig,
+	pub prestige: PrestigeConfig,
+	pub calendar: CalendarConfig
+}
+
+impl Default for EconomyConfig {
+	fn default() -> Self {
+		let mut rarity_weights = HashMap::new();
+		rarity_weights.insert(String::from("Rare"), 75);
+		rarity_weights.insert(String::from("Rare ACE"), 10);
+		rarity_weights.insert(String::from("Rare BREAK"), 10);
+		rarity_weights.insert(String::from("Rare Holo"), 40);
+		rarity_weights.insert(String::from("Rare Holo EX"), 12);
+		rarity_weights.insert(String::from("Rare Holo GX"), 12);
+		rarity_weights.insert(String::from("Rare Holo LV.X"), 12);
+		rarity_weights.insert(String::from("Rare Holo Star"), 8);
+		rarity_weights.insert(String::from("Rare Holo V"), 15);
+		rarity_weights.insert(String::from("Rare Holo VMAX"), 10);
+		rarity_weights.insert(String::from("Rare Prime"), 10);
+		rarity_weights.insert(String::from("Rare Prism Star"), 10);
+		rarity_weights.insert(String::from("Rare Rainbow"), 5);
+		rarity_weights.insert(String::from("Rare Secret"), 1);
+		rarity_weights.insert(String::from("Rare Shining"), 20);
+		rarity_weights.insert(String::from("Rare Shiny"), 5);
+		rarity_weights.insert(String::from("Rare Shiny GX"), 2);
+		rarity_weights.insert(String::from("Rare Ultra"), 5);
+		rarity_weights.insert(String::from("Amazing Rare"), 15);
+		rarity_weights.insert(String::from("LEGEND"), 3);
+		let mut store_slots = vec![];
+		for _ in 0..4 {
+			store_slots.push(StoreSlot::new("Pack", 1.0, 1));
+		}
+		for _ in 0..3 {
+			store_slots.push(StoreSlot::new("Collection", 2.5, 4));
+		}
+		for _ in 0..2 {
+			store_slots.push(StoreSlot::new("Trainer Box", 10.0, 12));
+		}
+		store_slots.push(StoreSlot::new("Booster Box", 30.0, 36));
+		Self {
+			rarity_weights,
+			slots: SlotConfig::default(),
+			store_slots,
+			upgrades: HashMap::new(),
+			effects: EffectConfig::default(),
+			items: HashMap::new(),
+			community: HashMap::new(),
+			daily: DailyConfig::default(),
+			packs: PackConfig::default(),
+			quiz: QuizConfig::default(),
+			prestige: PrestigeConfig::default(),
+			calendar: CalendarConfig::default()
+		}
+	}
+}
+
+impl EconomyConfig {
+	pub fn rarity_weight(&self, rarity: &str) -> i64 {
+		*self.rarity_weights.get(rarity).unwrap_or(&0)
+	}
+
+	pub fn upgrade(&self, upgrade: &str) -> Option<&UpgradeTuning> {
+		self.upgrades.get(upgrade)
+	}
+
+	pub fn item_price(&self, item: &str) -> Option<&ItemPrice> {
+		self.items.get(item)
+	}
+
+	pub fn community_cost(&self, goal: &str) -> Option<f64> {
+		self.community.get(goal).copied()
+	}
+
+	pub fn store_slot(&self, slot: usize) -> StoreSlot {
+		match self.store_slots.get(slot) {
+			Some(x) => x.clone(),
+			None => StoreSlot::new("Pack", 1.0, 1)
+		}
+	}
+}
+
+#[command("reload")]
+#[checks(BotTest)]
+#[description("Reloads the economy config")]
+async fn admin_reload_config(ctx: &Context, msg: &Message) -> CommandResult {
+	match reload() {
+		Ok(_) => msg.reply(&ctx.http, format!("Reloaded the economy config from **{}**", config_path())).await?,
+		Err(e) => msg.reply(&ctx.http, format!("The config wasn't reloaded: {}", e)).await?
+	};
+
+	Ok(())
+}
@@ -0,0 +1,240 @@
+use std::{
+	borrow::Cow,
+	io::Cursor
+};
+use futures::{
+	stream,
+	StreamExt,
+	TryStreamExt
+};
+use image::{
+	imageops::overlay,
+	DynamicImage,
+	ImageOutputFormat,
+	Rgba,
+	RgbaImage
+};
+use imageproc::drawing::{
+	draw_filled_circle_mut,
+	draw_text_mut
+};
+use rusttype::Scale;
+use serenity::{
+	framework::{
+		standard::{
+			macros::{
+				command
+			},
+			Args,
+			CommandResult
+		},
+	},
+	model::{
+		channel::{
+			AttachmentType,
+			Message,
+		},
+	},
+	utils::{
+		Colour
+	},
+	prelude::*
+};
+
+use crate::error::BotResult;
+
+use super::{
+	card::{
+		get_card,
+		Card
+	},
+	card_image::{
+		get_card_thumbnail_image,
+		THUMBNAIL_HEIGHT,
+		THUMBNAIL_WIDTH
+	},
+	deck_stats::{
+		draw_centered_text,
+		BACKGROUND,
+		FONT,
+		TEXT
+	},
+	decks::{
+		get_deck,
+		Deck
+	},
+	legality::{
+		self,
+		is_basic_energy,
+		with_amounts
+	},
+	player::get_player
+};
+
+const COLUMNS: u32 = 8;
+const GAP: u32 = 12;
+const SECTION_HEADER: u32 = 44;
+const BADGE: Rgba<u8> = Rgba([255, 50, 20, 255]);
+// How many card images are fetched at once
+const CONCURRENT_FETCHES: usize = 8;
+
+const SUPERTYPES: &'static [&str] = &["Pokémon", "Trainer", "Energy"];
+
+// Trainers are ordered Supporters, Items, Tools then Stadiums, and special energy comes before basic energy
+fn card_order(card: &Card) -> (usize, String) {
+	let has = |subtype: &str| card.subtypes.iter().any(|s| s.starts_with(subtype));
+	let rank = match card.supertype.as_str() {
+		"Trainer" if has("Supporter") => 0,
+		"Trainer" if has("Pokémon Tool") => 2,
+		"Trainer" if has("Stadium") => 3,
+		"Trainer" => 1,
+		"Energy" if is_basic_energy(card) => 1,
+		_ => 0
+	};
+
+	(rank, card.name.clone())
+}
+
+/// The deck's cards grouped by supertype in the order they're shown in. Cards without a known supertype go last.
+fn group_cards(cards: Vec<(Card, i64)>) -> Vec<(String, Vec<(Card, i64)>)> {
+	let mut groups: Vec<(String, Vec<(Card, i64)>)> = SUPERTYPES
+		.iter()
+		.map(|s| (String::from(*s), vec![]))
+		.collect();
+	for (card, amount) in cards.into_iter().filter(|(_, a)| *a > 0) {
+		let supertype = match SUPERTYPES.contains(&card.supertype.as_str()) {
+			true => card.supertype.clone(),
+			false => String::from("Other")
+		};
+		match groups.iter_mut().find(|(s, _)| *s == supertype) {
+			Some((_, group)) => group.push((card, amount)),
+			None => groups.push((supertype, vec![(card, amount)]))
+		}
+	}
+	for (_, group) in groups.iter_mut() {
+		group.sort_by_key(|(c, _)| card_order(c));
+	}
+
+	groups
+		.into_iter()
+		.filter(|(_, g)| g.len() > 0)
+		.collect()
+}
+
+async fn display_card(ctx: &Context, deck: &Deck, groups: &[(String, Vec<(Card, i64)>)]) -> Card {
+	let first = groups[0].1[0].0.clone();
+	match deck.display_card.as_str() {
+		"" => first,
+		id => match groups.iter().flat_map(|(_, g)| g.iter()).find(|(c, _)| c.card_id == id) {
+			Some((card, _)) => card.clone(),
+			None => get_card(ctx, &String::from(id)).await
+		}
+	}
+}
+
+async fn render_deck(ctx: &Context, deck: &Deck, cards: Vec<(Card, i64)>) -> BotResult<Vec<u8>> {
+	let legality = legality::legality(&cards);
+	let total = cards.iter().map(|(_, a)| a).sum::<i64>();
+	let groups = group_cards(cards);
+	let display = display_card(ctx, deck, &groups).await;
+
+	let width = COLUMNS * (THUMBNAIL_WIDTH + GAP) + GAP;
+	let header_height = THUMBNAIL_HEIGHT + 2 * GAP;
+	let height = header_height + groups
+		.iter()
+		.map(|(_, g)| SECTION_HEADER + (g.len() as u32 + COLUMNS - 1) / COLUMNS * (THUMBNAIL_HEIGHT + GAP))
+		.sum::<u32>();
+	let mut img = RgbaImage::from_pixel(width, height, BACKGROUND);
+
+	overlay(&mut img, &get_card_thumbnail_image(&display).await?.to_rgba8(), GAP as i64, GAP as i64);
+	let text_x = (2 * GAP + THUMBNAIL_WIDTH) as i32;
+	draw_text_mut(&mut img, TEXT, text_x, (GAP + 8) as i32, Scale::uniform(48.0), &FONT, &deck.name);
+	let counts = groups
+		.iter()
+		.map(|(supertype, g)| format!("{} {}", g.iter().map(|(_, a)| a).sum::<i64>(), supertype))
+		.collect::<Vec<String>>()
+		.join(", ");
+	draw_text_mut(&mut img, TEXT, text_x, (GAP + 76) as i32, Scale::uniform(26.0), &FONT, &format!("{} cards: {}", total, counts));
+	let legal_formats = legality
+		.iter()
+		.filter(|(_, v)| v.len() == 0)
+		.map(|(f, _)| f.name())
+		.collect::<Vec<&str>>();
+	let legal = match legal_formats.len() {
+		0 => String::from("Not legal in any format"),
+		_ => format!("Legal in {}", legal_formats.join(", "))
+	};
+	draw_text_mut(&mut img, TEXT, text_x, (GAP + 116) as i32, Scale::uniform(26.0), &FONT, &legal);
+
+	let mut y = header_height;
+	for (supertype, group) in &groups {
+		let amount = group.iter().map(|(_, a)| a).sum::<i64>();
+		draw_text_mut(&mut img, TEXT, GAP as i32, (y + 8) as i32, Scale::uniform(28.0), &FONT, &format!("{} ({})", supertype, amount));
+		y += SECTION_HEADER;
+		let fetches = group
+			.iter()
+			.map(|(c, _)| get_card_thumbnail_image(c))
+			.collect::<Vec<_>>();
+		let thumbnails = stream::iter(fetches)
+			.buffered(CONCURRENT_FETCHES)
+			.try_collect::<Vec<DynamicImage>>()
+			.await?;
+		for (i, (thumbnail, (_, amount))) in thumbnails.iter().zip(group.iter()).enumerate() {
+			let x = GAP + (i as u32 % COLUMNS) * (THUMBNAIL_WIDTH + GAP);
+			let card_y = y + (i as u32 / COLUMNS) * (THUMBNAIL_HEIGHT + GAP);
+			overlay(&mut img, &thumbnail.to_rgba8(), x as i64, card_y as i64);
+			let badge_x = (x + THUMBNAIL_WIDTH) as i32 - 26;
+			let badge_y = (card_y + THUMBNAIL_HEIGHT) as i32 - 26;
+			draw_filled_circle_mut(&mut img, (badge_x, badge_y), 20, BADGE);
+			draw_centered_text(&mut img, TEXT, badge_x, badge_y - 14, 26.0, &amount.to_string());
+		}
+		y += (group.len() as u32 + COLUMNS - 1) / COLUMNS * (THUMBNAIL_HEIGHT + GAP);
+	}
+
+	// A JPEG keeps a whole deck small enough to share
+	let mut buf: Vec<u8> = vec![];
+	DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(img).to_rgb8()).write_to(&mut Cursor::new(&mut buf), ImageOutputFormat::Jpeg(85))?;
+
+	Ok(buf)
+}
+
+#[command("image")]
+#[aliases("img")]
+#[description("Shows a whole deck in one image")]
+#[usage("<name>")]
+#[example("blastoise")]
+#[min_args(1)]
+async fn deck_image(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+	let deck_name = args.rest().to_lowercase();
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let deck = match get_deck(player.discord_id, player.economy, deck_name).await {
+		Some(x) => x,
+		None => {
+			msg.reply(&ctx.http, "You don't have a deck with that name.").await?;
+			return Ok(());
+		}
+	};
+	let cards = with_amounts(deck.get_cards(ctx).await, &deck.cards);
+	if !cards.iter().any(|(_, a)| *a > 0) {
+		msg.reply(&ctx.http, "That deck doesn't have any cards yet.").await?;
+		return Ok(());
+	}
+	let image = render_deck(ctx, &deck, cards).await?;
+	msg
+		.channel_id
+		.send_message(&ctx.http, |m| {
+			m.add_file(AttachmentType::Bytes {
+				data: Cow::from(image.as_slice()),
+				filename: String::from("deck.jpg")
+			});
+			m.embed(|e| {
+				e
+					.title(&deck.name)
+					.image("attachment://deck.jpg")
+					.colour(Colour::from_rgb(255, 50, 20))
+			})
+		})
+		.await?;
+
+	Ok(())
+}
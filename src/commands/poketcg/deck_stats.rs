@@ -0,0 +1,456 @@
+use std::{
+	borrow::Cow,
+	collections::BTreeMap,
+	io::Cursor
+};
+use image::{
+	DynamicImage,
+	ImageOutputFormat,
+	Rgba,
+	RgbaImage
+};
+use imageproc::{
+	drawing::{
+		draw_filled_circle_mut,
+		draw_filled_rect_mut,
+		draw_line_segment_mut,
+		draw_text_mut,
+		text_size
+	},
+	rect::Rect
+};
+use rusttype::{
+	Font,
+	Scale
+};
+use serenity::{
+	framework::{
+		standard::{
+			macros::{
+				command
+			},
+			Args,
+			CommandResult
+		},
+	},
+	model::{
+		channel::{
+			AttachmentType,
+			Message,
+		},
+	},
+	utils::{
+		Colour
+	},
+	prelude::*
+};
+
+use crate::error::BotResult;
+
+use super::{
+	card::Card,
+	decks::{
+		get_deck,
+		Deck
+	},
+	legality::{
+		is_basic_energy,
+		is_basic_pokemon,
+		with_amounts
+	},
+	player::get_player
+};
+
+lazy_static! {
+	pub static ref FONT: Font<'static> = Font::try_from_bytes(include_bytes!("../../../assets/fonts/DejaVuSans.ttf") as &[u8])
+		.expect("Couldn't load the chart font");
+}
+
+pub static HAND_SIZE: i64 = 7;
+// How many turns the draw odds are shown for
+static TURNS: i64 = 8;
+
+const CHART_WIDTH: u32 = 900;
+const CHART_HEIGHT: u32 = 580;
+pub const BACKGROUND: Rgba<u8> = Rgba([47, 49, 54, 255]);
+pub const TEXT: Rgba<u8> = Rgba([235, 235, 235, 255]);
+const GRID: Rgba<u8> = Rgba([80, 83, 90, 255]);
+// One colour per line on the draw odds chart, for 1 to 4 copies
+const LINE_COLOURS: &'static [Rgba<u8>] = &[
+	Rgba([237, 66, 69, 255]),
+	Rgba([254, 231, 92, 255]),
+	Rgba([87, 242, 135, 255]),
+	Rgba([88, 101, 242, 255])
+];
+
+struct DeckStats {
+	total: i64,
+	basics: i64,
+	// Pokémon, Supporter, Item, Tool, Stadium and Energy
+	composition: Vec<(&'static str, i64)>,
+	types: BTreeMap<String, i64>,
+	energy: BTreeMap<String, i64>,
+	// Each Pokémon's name with how many copies it has and what it evolves from
+	pokemon: BTreeMap<String, (i64, String)>,
+	// How many attacks cost 0, 1, 2, 3, 4 and 5+ energy
+	attack_costs: [i64; 6]
+}
+
+impl DeckStats {
+	fn new(cards: &[(Card, i64)]) -> Self {
+		let mut composition = vec![("Pokémon", 0), ("Supporter", 0), ("Item", 0), ("Tool", 0), ("Stadium", 0), ("Energy", 0)];
+		let mut types = BTreeMap::new();
+		let mut energy = BTreeMap::new();
+		let mut pokemon: BTreeMap<String, (i64, String)> = BTreeMap::new();
+		let mut attack_costs = [0; 6];
+		for (card, amount) in cards {
+			let idx = match card.supertype.as_str() {
+				"Pokémon" => 0,
+				"Energy" => 5,
+				_ if card.subtypes.iter().any(|s| s == "Supporter") => 1,
+				_ if card.subtypes.iter().any(|s| s.starts_with("Pokémon Tool")) => 3,
+				_ if card.subtypes.iter().any(|s| s == "Stadium") => 4,
+				_ => 2
+			};
+			composition[idx].1 += amount;
+			match idx {
+				0 => {
+					for t in &card.types {
+						*types.entry(t.clone()).or_insert(0) += amount;
+					}
+					let entry = pokemon.entry(card.name.clone()).or_insert((0, card.evolves_from.clone()));
+					entry.0 += amount;
+					for cost in &card.attack_costs {
+						attack_costs[(*cost).clamp(0, 5) as usize] += amount;
+					}
+				},
+				5 => {
+					let name = match is_basic_energy(card) {
+						true => card.name.replace("Basic ", "").replace(" Energy", ""),
+						false => String::from("Special")
+					};
+					*energy.entry(name).or_insert(0) += amount;
+				},
+				_ => ()
+			}
+		}
+
+		DeckStats {
+			total: cards.iter().map(|(_, a)| a).sum(),
+			basics: cards.iter().filter(|(c, _)| is_basic_pokemon(c)).map(|(_, a)| a).sum(),
+			composition,
+			types,
+			energy,
+			pokemon,
+			attack_costs
+		}
+	}
+
+	/// Each evolution line as eg. "Charmander (4) → Charmeleon (2) → Charizard ex (3)",
+	/// with a warning on lines whose first stage isn't in the deck.
+	fn evolution_lines(&self) -> Vec<String> {
+		let mut ret = vec![];
+		for (name, (_, from)) in &self.pokemon {
+			if from == "" {
+				ret.push(self.evolution_line(name));
+			} else if !self.pokemon.contains_key(from) {
+				ret.push(format!("⚠️ {} _(missing {})_", self.evolution_line(name), from));
+			}
+		}
+
+		ret
+	}
+
+	fn evolution_line(&self, name: &str) -> String {
+		let mut ret = format!("{} ({})", name, self.pokemon[name].0);
+		let evolutions = self.pokemon
+			.iter()
+			.filter(|(_, (_, from))| from == name)
+			.map(|(n, _)| self.evolution_line(n))
+			.collect::<Vec<String>>();
+		if evolutions.len() > 0 {
+			ret.push_str(&format!(" → {}", evolutions.join(" / ")));
+		}
+
+		ret
+	}
+}
+
+fn choose(n: i64, k: i64) -> f64 {
+	if k < 0 || k > n {
+		return 0.0;
+	}
+	let k = k.min(n - k);
+
+	(0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+}
+
+/// The hypergeometric chance of seeing at least one of `copies` cards when drawing `drawn` cards from the deck.
+fn chance_to_draw(deck_size: i64, copies: i64, drawn: i64) -> f64 {
+	if deck_size == 0 {
+		return 0.0;
+	}
+	let drawn = drawn.min(deck_size);
+
+	1.0 - choose(deck_size - copies, drawn) / choose(deck_size, drawn)
+}
+
+// The player going first doesn't draw on their first turn
+fn cards_seen(turn: i64, going_first: bool) -> i64 {
+	match going_first {
+		true => HAND_SIZE + turn - 1,
+		false => HAND_SIZE + turn
+	}
+}
+
+fn percent(chance: f64) -> String {
+	format!("{:.1}%", chance * 100.0)
+}
+
+pub fn draw_centered_text(img: &mut RgbaImage, colour: Rgba<u8>, x: i32, y: i32, size: f32, text: &str) {
+	let (width, _) = text_size(Scale::uniform(size), &FONT, text);
+	draw_text_mut(img, colour, x - width / 2, y, Scale::uniform(size), &FONT, text);
+}
+
+fn draw_bar_chart(img: &mut RgbaImage, area: Rect, title: &str, bars: &[(String, i64, Rgba<u8>)]) {
+	draw_text_mut(img, TEXT, area.left(), area.top(), Scale::uniform(20.0), &FONT, title);
+	let top = area.top() + 50;
+	let bottom = area.bottom() - 24;
+	let max = bars.iter().map(|(_, v, _)| *v).max().unwrap_or(0).max(1);
+	let slot = area.width() as i32 / bars.len().max(1) as i32;
+	let bar_width = slot * 3 / 5;
+	draw_line_segment_mut(img, (area.left() as f32, bottom as f32), (area.right() as f32, bottom as f32), GRID);
+	for (i, (label, value, colour)) in bars.iter().enumerate() {
+		let center = area.left() + slot * i as i32 + slot / 2;
+		let height = ((bottom - top) as f64 * *value as f64 / max as f64) as i32;
+		if height > 0 {
+			draw_filled_rect_mut(img, Rect::at(center - bar_width / 2, bottom - height).of_size(bar_width as u32, height as u32), *colour);
+		}
+		draw_centered_text(img, TEXT, center, bottom - height - 20, 16.0, &value.to_string());
+		draw_centered_text(img, TEXT, center, bottom + 6, 14.0, label);
+	}
+}
+
+fn draw_odds_chart(img: &mut RgbaImage, area: Rect, deck_size: i64) {
+	draw_text_mut(img, TEXT, area.left(), area.top(), Scale::uniform(20.0), &FONT, "Chance to draw a card by turn (going first)");
+	let left = area.left() + 48;
+	let right = area.right() - 120;
+	let top = area.top() + 40;
+	let bottom = area.bottom() - 24;
+	let x = |turn: i64| left as f32 + (right - left) as f32 * (turn - 1) as f32 / (TURNS - 1) as f32;
+	let y = |chance: f64| bottom as f32 - (bottom - top) as f32 * chance as f32;
+	for pct in (0..=100).step_by(25) {
+		let py = y(pct as f64 / 100.0);
+		draw_line_segment_mut(img, (left as f32, py), (right as f32, py), GRID);
+		draw_text_mut(img, TEXT, area.left(), py as i32 - 8, Scale::uniform(14.0), &FONT, &format!("{}%", pct));
+	}
+	for turn in 1..=TURNS {
+		draw_centered_text(img, TEXT, x(turn) as i32, bottom + 6, 14.0, &format!("T{}", turn));
+	}
+	for (i, colour) in LINE_COLOURS.iter().enumerate() {
+		let copies = i as i64 + 1;
+		let points = (1..=TURNS)
+			.map(|t| (x(t), y(chance_to_draw(deck_size, copies, cards_seen(t, true)))))
+			.collect::<Vec<(f32, f32)>>();
+		for pair in points.windows(2) {
+			let ((x1, y1), (x2, y2)) = (pair[0], pair[1]);
+			// Drawn a few times over to make the line thicker
+			for offset in [-1.0, 0.0, 1.0] {
+				draw_line_segment_mut(img, (x1, y1 + offset), (x2, y2 + offset), *colour);
+			}
+		}
+		for (px, py) in &points {
+			draw_filled_circle_mut(img, (*px as i32, *py as i32), 4, *colour);
+		}
+		let legend_y = top + 24 * i as i32;
+		draw_filled_rect_mut(img, Rect::at(right + 20, legend_y + 2).of_size(14, 14), *colour);
+		let label = match copies {
+			1 => String::from("1 copy"),
+			_ => format!("{} copies", copies)
+		};
+		draw_text_mut(img, TEXT, right + 40, legend_y, Scale::uniform(16.0), &FONT, &label);
+	}
+}
+
+fn render_chart(deck: &Deck, stats: &DeckStats) -> BotResult<Vec<u8>> {
+	let mut img = RgbaImage::from_pixel(CHART_WIDTH, CHART_HEIGHT, BACKGROUND);
+	draw_text_mut(&mut img, TEXT, 20, 12, Scale::uniform(26.0), &FONT, &format!("{} - {} cards", deck.name, stats.total));
+	let composition = stats.composition
+		.iter()
+		.zip(LINE_COLOURS.iter().cycle())
+		.map(|((label, value), colour)| (String::from(*label), *value, *colour))
+		.collect::<Vec<(String, i64, Rgba<u8>)>>();
+	draw_bar_chart(&mut img, Rect::at(20, 56).of_size(420, 240), "Composition", &composition);
+	let attack_costs = stats.attack_costs
+		.iter()
+		.enumerate()
+		.map(|(cost, value)| match cost {
+			5 => (String::from("5+"), *value, LINE_COLOURS[3]),
+			_ => (cost.to_string(), *value, LINE_COLOURS[3])
+		})
+		.collect::<Vec<(String, i64, Rgba<u8>)>>();
+	draw_bar_chart(&mut img, Rect::at(460, 56).of_size(420, 240), "Attack costs", &attack_costs);
+	draw_odds_chart(&mut img, Rect::at(20, 316).of_size(860, 250), stats.total);
+
+	let mut buf: Vec<u8> = vec![];
+	DynamicImage::ImageRgba8(img).write_to(&mut Cursor::new(&mut buf), ImageOutputFormat::Png)?;
+
+	Ok(buf)
+}
+
+fn counts(map: &BTreeMap<String, i64>) -> String {
+	match map.len() {
+		0 => String::from("None"),
+		_ => map
+			.iter()
+			.map(|(k, v)| format!("**{}:** {}", k, v))
+			.collect::<Vec<String>>()
+			.join("\n")
+	}
+}
+
+// Discord's limit for an embed field
+fn field(mut value: String) -> String {
+	if let Some((i, _)) = value.char_indices().nth(1024) {
+		value.truncate(i);
+	}
+
+	value
+}
+
+#[command("stats")]
+#[aliases("s")]
+#[description("Breaks a deck down into its card types, evolution lines and the odds of drawing its cards")]
+#[usage("<name> [card id]")]
+#[example("blastoise swsh1-1")]
+#[min_args(1)]
+async fn deck_stats(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+	let deck_name = args.rest().to_lowercase();
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let mut deck = get_deck(player.discord_id, player.economy, deck_name.clone()).await;
+	let mut card_id = None;
+	// The last word is a card to show the odds for, if the deck has it
+	if let Some((name, last)) = deck_name.rsplit_once(' ') {
+		if let Some(x) = get_deck(player.discord_id, player.economy, String::from(name)).await {
+			if let Some(id) = x.cards.keys().find(|id| id.eq_ignore_ascii_case(last)) {
+				card_id = Some(id.clone());
+				deck = Some(x);
+			}
+		}
+	}
+	let deck = match deck {
+		Some(x) => x,
+		None => {
+			msg.reply(&ctx.http, "You don't have a deck with that name.").await?;
+			return Ok(());
+		}
+	};
+	let cards = with_amounts(deck.get_cards(ctx).await, &deck.cards);
+	let stats = DeckStats::new(&cards);
+	if stats.total == 0 {
+		msg.reply(&ctx.http, "That deck doesn't have any cards yet.").await?;
+		return Ok(());
+	}
+	let chart = render_chart(&deck, &stats)?;
+
+	let composition = stats.composition
+		.iter()
+		.map(|(k, v)| format!("**{}:** {}", k, v))
+		.collect::<Vec<String>>()
+		.join("\n");
+	let lines = match stats.evolution_lines() {
+		x if x.len() == 0 => String::from("None"),
+		x => x.join("\n")
+	};
+	let basic_chance = chance_to_draw(stats.total, stats.basics, HAND_SIZE);
+	let opening_hand = format!(
+		"**Basic Pokémon in the opening {}:** {}\n**Mulligan:** {}",
+		HAND_SIZE,
+		percent(basic_chance),
+		percent(1.0 - basic_chance)
+	);
+	let selected = card_id.map(|id| {
+		let (card, amount) = cards.iter().find(|(c, _)| c.card_id == id).unwrap();
+		let odds = (1..=4)
+			.map(|t| format!(
+				"**Turn {}:** {} going first, {} going second",
+				t,
+				percent(chance_to_draw(stats.total, *amount, cards_seen(t, true))),
+				percent(chance_to_draw(stats.total, *amount, cards_seen(t, false)))
+			))
+			.collect::<Vec<String>>()
+			.join("\n");
+
+		(format!("Drawing {} ({} of {})", card.name, amount, stats.total), odds)
+	});
+	msg
+		.channel_id
+		.send_message(&ctx.http, |m| {
+			m.add_file(AttachmentType::Bytes {
+				data: Cow::from(chart.as_slice()),
+				filename: String::from("deckstats.png")
+			});
+			m.embed(|e| {
+				e
+					.title(format!("{} - Stats", deck.name))
+					.field("Composition", composition, true)
+					.field("Types", counts(&stats.types), true)
+					.field("Energy", counts(&stats.energy), true)
+					.field("Evolution lines", field(lines), false)
+					.field("Opening hand", opening_hand, false);
+				if let Some((name, odds)) = selected {
+					e.field(name, odds, false);
+				}
+
+				e
+					.image("attachment://deckstats.png")
+					.colour(Colour::from_rgb(255, 50, 20))
+			})
+		})
+		.await?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn close(a: f64, b: f64) -> bool {
+		(a - b).abs() < 0.0001
+	}
+
+	#[test]
+	fn choose_counts_combinations() {
+		assert_eq!(choose(5, 2), 10.0);
+		assert_eq!(choose(60, 0), 1.0);
+		assert_eq!(choose(60, 60), 1.0);
+		assert!(close(choose(60, 7), 386206920.0));
+		assert_eq!(choose(5, 6), 0.0);
+		assert_eq!(choose(5, -1), 0.0);
+	}
+
+	#[test]
+	fn chance_to_draw_a_basic_in_the_opening_hand() {
+		// 1 - C(50, 7) / C(60, 7)
+		assert!(close(chance_to_draw(60, 10, HAND_SIZE), 0.7414));
+		assert!(close(chance_to_draw(60, 4, HAND_SIZE), 0.3995));
+	}
+
+	#[test]
+	fn chance_to_draw_edges() {
+		assert_eq!(chance_to_draw(60, 0, HAND_SIZE), 0.0);
+		assert_eq!(chance_to_draw(0, 0, HAND_SIZE), 0.0);
+		assert_eq!(chance_to_draw(60, 60, HAND_SIZE), 1.0);
+		// Drawing more cards than the deck has draws the whole deck
+		assert_eq!(chance_to_draw(5, 1, 10), 1.0);
+	}
+
+	#[test]
+	fn cards_seen_by_turn() {
+		assert_eq!(cards_seen(1, true), 7);
+		assert_eq!(cards_seen(1, false), 8);
+		assert_eq!(cards_seen(3, true), 9);
+		assert_eq!(cards_seen(3, false), 10);
+	}
+}
@@ -0,0 +1,405 @@
+use std::{
+	borrow::Cow,
+	collections::HashMap
+};
+use mongodb::bson::{
+	doc,
+	Document
+};
+use serenity::{
+	framework::{
+		standard::{
+			macros::{
+				command
+			},
+			Args,
+			CommandResult
+		},
+	},
+	model::{
+		channel::{
+			AttachmentType,
+			Message,
+		},
+	},
+	prelude::*
+};
+
+use super::{
+	card::{
+		get_cards_with_query,
+		Card
+	},
+	decks::{
+		add_deck,
+		find_basic_energy,
+		get_deck,
+		is_basic_energy,
+		BASIC_ENERGIES,
+		Deck,
+		DeckCards
+	},
+	player::{
+		get_player,
+		update_player
+	},
+	Idable
+};
+
+// How many cards are looked up in one API query
+static QUERY_CHUNK_SIZE: usize = 25;
+
+// How many unresolved or missing cards are listed in a reply
+static MAX_LISTED: usize = 15;
+
+// Discord's limit for a message
+static MAX_MESSAGE_LEN: usize = 2000;
+
+/// A card line of a PTCGL decklist, eg. "4 Pikachu SVI 63".
+#[derive(Debug, Clone)]
+pub struct DecklistLine {
+	pub amount: i64,
+	pub name: String,
+	pub set_code: String,
+	pub number: String
+}
+
+impl DecklistLine {
+	fn parse(line: &str) -> Option<Self> {
+		// PTCGO lists start every card with "* "
+		let line = line.trim().trim_start_matches("* ");
+		let words = line.split_whitespace().collect::<Vec<&str>>();
+		if words.len() < 4 {
+			return None;
+		}
+		let amount = match words[0].parse::<i64>() {
+			Ok(x) if x > 0 => x,
+			_ => return None
+		};
+
+		Some(Self {
+			amount,
+			name: words[1..words.len() - 2].join(" "),
+			set_code: String::from(words[words.len() - 2]),
+			number: String::from(words[words.len() - 1])
+		})
+	}
+
+	/// The type of basic energy the line is, eg. "Basic {L} Energy" or "Lightning Energy".
+	fn basic_energy(&self) -> Option<&'static str> {
+		let name = self.name.to_lowercase();
+		let energy_type = name
+			.trim_start_matches("basic ")
+			.strip_suffix(" energy")?;
+
+		find_basic_energy(energy_type).map(|e| e.card_id)
+	}
+
+	fn matches(&self, card: &Card) -> bool {
+		let set_matches = card.set.ptcgo_code.eq_ignore_ascii_case(&self.set_code) || card.set.set_id.eq_ignore_ascii_case(&self.set_code);
+		let number_matches = card.number.trim_start_matches('0').eq_ignore_ascii_case(self.number.trim_start_matches('0'));
+
+		set_matches && number_matches
+	}
+
+	fn display(&self) -> String {
+		format!("{} {} {} {}", self.amount, self.name, self.set_code, self.number)
+	}
+}
+
+/// The card lines of a decklist, and the lines that aren't cards or section headers.
+pub fn parse_decklist(list: &str) -> (Vec<DecklistLine>, Vec<String>) {
+	let mut cards = vec![];
+	let mut unparsed = vec![];
+	for line in list.lines().map(|l| l.trim()) {
+		// Blank lines, code blocks and headers like "Pokémon: 12" or "Total Cards: 60"
+		if line == "" || line.starts_with("```") || line.ends_with(':') {
+			continue;
+		}
+		if let Some((_, count)) = line.split_once(':') {
+			if count.trim().parse::<i64>().is_ok() {
+				continue;
+			}
+		}
+		match DecklistLine::parse(line) {
+			Some(x) => cards.push(x),
+			None => unparsed.push(String::from(line))
+		}
+	}
+
+	(cards, unparsed)
+}
+
+/// Looks up each line's card by its set code and number. Returns the card id for every
+/// line that was found and the lines that weren't.
+pub async fn resolve_decklist(ctx: &Context, lines: Vec<DecklistLine>) -> (Vec<(String, DecklistLine)>, Vec<DecklistLine>) {
+	let mut resolved = vec![];
+	let mut to_find = vec![];
+	for line in lines {
+		match line.basic_energy() {
+			Some(card_id) => resolved.push((String::from(card_id), line)),
+			None => to_find.push(line)
+		}
+	}
+	let mut found = vec![];
+	for chunk in to_find.chunks(QUERY_CHUNK_SIZE) {
+		let query = chunk
+			.iter()
+			.map(|l| format!("((set.ptcgoCode:{} OR set.id:{}) number:{})", l.set_code, l.set_code.to_lowercase(), l.number))
+			.collect::<Vec<String>>()
+			.join(" OR ");
+		found.extend(get_cards_with_query(ctx, &query).await);
+	}
+	let mut unresolved = vec![];
+	for line in to_find {
+		match found.iter().find(|c| line.matches(c)) {
+			Some(card) => resolved.push((card.id(), line)),
+			None => unresolved.push(line)
+		}
+	}
+
+	(resolved, unresolved)
+}
+
+fn section(cards: &Vec<(String, i64)>) -> i64 {
+	cards.iter().map(|(_, amt)| amt).sum()
+}
+
+/// The deck as a PTCGL decklist. Basic energy is listed as PTCGL's own basic energy.
+pub fn format_decklist(deck: &Deck, cards: &Vec<Card>) -> String {
+	let mut pokemon = vec![];
+	let mut trainers = vec![];
+	let mut energy = vec![];
+	for card in cards {
+		let amount = *deck.cards.get(&card.card_id).unwrap_or(&0);
+		if let Some(basic) = BASIC_ENERGIES.iter().find(|e| e.card_id == card.card_id) {
+			energy.push((format!("Basic {{{}}} Energy SVE {}", basic.symbol, basic.sve_number), amount));
+			continue;
+		}
+		let set_code = match card.set.ptcgo_code.as_str() {
+			"" => card.set.set_id.to_uppercase(),
+			x => String::from(x)
+		};
+		let line = format!("{} {} {}", card.name, set_code, card.number);
+		match card.supertype.as_str() {
+			"Pokémon" => pokemon.push((line, amount)),
+			"Energy" => energy.push((line, amount)),
+			_ => trainers.push((line, amount))
+		}
+	}
+	let mut ret = vec![];
+	for (header, mut cards) in [("Pokémon", pokemon), ("Trainer", trainers), ("Energy", energy)] {
+		if cards.len() == 0 {
+			continue;
+		}
+		cards.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+		let mut lines = vec![format!("{}: {}", header, section(&cards))];
+		lines.extend(cards.iter().map(|(line, amt)| format!("{} {}", amt, line)));
+		ret.push(lines.join("\n"));
+	}
+	ret.push(format!("Total Cards: {}", deck.cards.values().sum::<i64>()));
+
+	ret.join("\n\n")
+}
+
+fn listed(lines: &Vec<String>) -> String {
+	let mut ret = lines
+		.iter()
+		.take(MAX_LISTED)
+		.map(|l| format!("- {}", l))
+		.collect::<Vec<String>>()
+		.join("\n");
+	if lines.len() > MAX_LISTED {
+		ret.push_str(&format!("\n_and {} more_", lines.len() - MAX_LISTED));
+	}
+
+	ret
+}
+
+#[command("import")]
+#[aliases("i")]
+#[description("Creates a deck from a PTCG Live decklist pasted on the lines after the name. Only the cards you own are put in")]
+#[usage("<name>\n<decklist>")]
+#[example("pikachu\nPokémon: 4\n4 Pikachu SVI 63")]
+#[min_args(1)]
+async fn deck_import(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+	let (deck_name, list) = match args.rest().split_once('\n') {
+		Some((name, list)) => (name.trim().to_lowercase(), list),
+		None => {
+			msg.reply(&ctx.http, "Paste the decklist on the lines after the deck name, eg.\n**.deck import pikachu**\n**4 Pikachu SVI 63**").await?;
+			return Ok(());
+		}
+	};
+	if deck_name == "" {
+		msg.reply(&ctx.http, "You didn't provide a deck name.").await?;
+		return Ok(());
+	}
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
+	if get_deck(player.discord_id, player.economy, deck_name.clone()).await.is_some() {
+		msg.reply(&ctx.http, "You already have a deck with that name!").await?;
+		return Ok(());
+	}
+	let (lines, mut unresolved) = parse_decklist(list);
+	if lines.len() == 0 {
+		msg.reply(&ctx.http, "I couldn't find any cards in that decklist.").await?;
+		return Ok(());
+	}
+	let (resolved, not_found) = resolve_decklist(ctx, lines).await;
+	unresolved.extend(not_found.iter().map(|l| l.display()));
+	let mut wanted: HashMap<String, (i64, DecklistLine)> = HashMap::new();
+	for (card_id, line) in resolved {
+		wanted.entry(card_id).or_insert((0, line.clone())).0 += line.amount;
+	}
+	let mut deck = Deck::empty(player.discord_id, player.economy, deck_name.clone());
+	let deckcards = DeckCards {
+		cards: wanted
+			.iter()
+			.map(|(card_id, (amount, _))| (card_id.clone(), *amount))
+			.collect()
+	};
+	let violations = deckcards.addition_violations(ctx, &deck).await;
+	if violations.len() > 0 {
+		msg.reply(&ctx.http, format!("That decklist can't be made into a deck, it has:\n{}", listed(&violations))).await?;
+		return Ok(());
+	}
+	let mut missing = vec![];
+	for (card_id, (amount, line)) in wanted {
+		// Basic energy is free, like with .deck energy add
+		if is_basic_energy(&card_id) {
+			deck.cards.insert(card_id, amount);
+			continue;
+		}
+		let owned = *player.cards.get(&card_id).unwrap_or(&0);
+		let taken = amount.min(owned);
+		if taken < amount {
+			missing.push(format!("{}x {} ({} {})", amount - taken, line.name, line.set_code, line.number));
+		}
+		if taken == 0 {
+			continue;
+		}
+		*player.cards.entry(card_id.clone()).or_insert(0) -= taken;
+		deck.cards.insert(card_id, taken);
+	}
+	player.cards.retain(|_, v| *v > 0);
+	if deck.cards.len() == 0 {
+		let mut content = String::from("None of the cards in that decklist could be put in a deck.");
+		if unresolved.len() > 0 {
+			content.push_str(&format!("\n\n**Couldn't find:**\n{}", listed(&unresolved)));
+		}
+		if missing.len() > 0 {
+			content.push_str(&format!("\n\n**You don't own:**\n{}", listed(&missing)));
+		}
+		msg.reply(&ctx.http, content).await?;
+		return Ok(());
+	}
+	let mut player_update = Document::new();
+	let mut player_cards_update = Document::new();
+	for (crd, amt) in player.cards.iter() {
+		player_cards_update.insert(crd, amt);
+	}
+	player_update.insert("cards", player_cards_update);
+	update_player(&player, doc! { "$set": player_update }).await?;
+	add_deck(&deck).await;
+
+	let mut content = format!("You imported **{}** cards into **{}**", deck.cards.values().sum::<i64>(), deck_name);
+	if unresolved.len() > 0 {
+		content.push_str(&format!("\n\n**Couldn't find:**\n{}", listed(&unresolved)));
+	}
+	if missing.len() > 0 {
+		content.push_str(&format!("\n\n**You don't own:**\n{}", listed(&missing)));
+	}
+	msg.reply(&ctx.http, content).await?;
+
+	Ok(())
+}
+
+#[command("export")]
+#[aliases("ex")]
+#[description("Shows one of your decks as a PTCG Live decklist to share")]
+#[usage("<name>")]
+#[example("blastoise")]
+#[min_args(1)]
+async fn deck_export(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+	let deck_name = args.rest().to_lowercase();
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let deck = match get_deck(player.discord_id, player.economy, deck_name.clone()).await {
+		Some(x) => x,
+		None => {
+			msg.reply(&ctx.http, "You don't have a deck with that name.").await?;
+			return Ok(());
+		}
+	};
+	if deck.cards.len() == 0 {
+		msg.reply(&ctx.http, format!("**{}** doesn't have any cards yet.", deck.name)).await?;
+		return Ok(());
+	}
+	let cards = deck.get_cards(ctx).await;
+	let decklist = format_decklist(&deck, &cards);
+	let content = format!("```\n{}\n```", decklist);
+	if content.len() <= MAX_MESSAGE_LEN {
+		msg.reply(&ctx.http, content).await?;
+		return Ok(());
+	}
+	// Too long for a message, so it's sent as a file instead
+	msg
+		.channel_id
+		.send_message(&ctx.http, |m| {
+			m
+				.content(format!("Here's **{}**", deck.name))
+				.add_file(AttachmentType::Bytes {
+					data: Cow::from(decklist.as_bytes()),
+					filename: format!("{}.txt", deck.name)
+				})
+		})
+		.await?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_a_card_line() {
+		let line = DecklistLine::parse("4 Pikachu ex SVI 63").unwrap();
+		assert_eq!(line.amount, 4);
+		assert_eq!(line.name, "Pikachu ex");
+		assert_eq!(line.set_code, "SVI");
+		assert_eq!(line.number, "63");
+	}
+
+	#[test]
+	fn parses_ptcgo_lines_and_extra_whitespace() {
+		let line = DecklistLine::parse("* 2 Professor's Research  SVI   189 ").unwrap();
+		assert_eq!(line.amount, 2);
+		assert_eq!(line.name, "Professor's Research");
+		assert_eq!(line.set_code, "SVI");
+		assert_eq!(line.number, "189");
+	}
+
+	#[test]
+	fn rejects_lines_that_arent_cards() {
+		assert!(DecklistLine::parse("").is_none());
+		assert!(DecklistLine::parse("4 Pikachu SVI").is_none());
+		assert!(DecklistLine::parse("Pikachu ex SVI 63").is_none());
+		assert!(DecklistLine::parse("0 Pikachu ex SVI 63").is_none());
+		assert!(DecklistLine::parse("-1 Pikachu ex SVI 63").is_none());
+	}
+
+	#[test]
+	fn finds_basic_energy_by_name() {
+		assert!(DecklistLine::parse("8 Basic {L} Energy SVE 4").unwrap().basic_energy().is_some());
+		assert!(DecklistLine::parse("8 Lightning Energy SVE 4").unwrap().basic_energy().is_some());
+		assert!(DecklistLine::parse("2 Double Turbo Energy BRS 151").unwrap().basic_energy().is_none());
+	}
+
+	#[test]
+	fn skips_headers_and_keeps_unparsed_lines() {
+		let list = "```\nPokémon: 4\n4 Pikachu ex SVI 63\n\nTrainer:\nnot a card\nTotal Cards: 60\n```";
+		let (cards, unparsed) = parse_decklist(list);
+		assert_eq!(cards.len(), 1);
+		assert_eq!(cards[0].name, "Pikachu ex");
+		assert_eq!(unparsed, vec![String::from("not a card")]);
+	}
+}
@@ -50,6 +50,29 @@ use crate::{
 		Card
 	},
 	commands::poketcg::Scrollable,
+	guild::economy_filter,
+};
+
+use super::decklist::{
+	DECK_IMPORT_COMMAND,
+	DECK_EXPORT_COMMAND
+};
+use super::autobuild::DECK_AUTOBUILD_COMMAND;
+use super::deck_image::DECK_IMAGE_COMMAND;
+use super::deck_stats::DECK_STATS_COMMAND;
+use super::drafts::{
+	DECK_COMMIT_COMMAND,
+	DECK_DIFF_COMMAND,
+	DECK_DRAFT_COMMAND
+};
+use super::goldfish::{
+	DECK_DRAW_COMMAND,
+	DECK_SIM_COMMAND
+};
+use super::legality::{
+	self,
+	Format,
+	DECK_LEGALITY_COMMAND
 };
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -59,22 +82,74 @@ pub struct Deck {
 	pub discord_id: i64,
 	pub name: String,
 	pub cards: HashMap<String, i64>,
-	pub display_card: String
+	pub display_card: String,
+	#[serde(default = "def_0")]
+	pub economy: i64,
+	// Draft decks are for planning, their cards don't have to be owned and aren't taken from the player
+	#[serde(default = "def_false")]
+	pub draft: bool
+}
+
+fn def_0() -> i64 {
+	0
+}
+
+fn def_false() -> bool {
+	false
+}
+
+pub struct BasicEnergy {
+	pub names: &'static [&'static str],
+	// The symbol PTCGL uses in its name, eg. "L" for "Basic {L} Energy"
+	pub symbol: &'static str,
+	// Basic energy in decks is free, it's always this card
+	pub card_id: &'static str,
+	// The card's number in PTCGL's basic energy set, SVE
+	pub sve_number: &'static str
+}
+
+pub const BASIC_ENERGIES: &'static [BasicEnergy] = &[
+	BasicEnergy { names: &["grass"], symbol: "G", card_id: "col1-88", sve_number: "1" },
+	BasicEnergy { names: &["fire"], symbol: "R", card_id: "col1-89", sve_number: "2" },
+	BasicEnergy { names: &["water"], symbol: "W", card_id: "col1-90", sve_number: "3" },
+	BasicEnergy { names: &["lightning", "electric"], symbol: "L", card_id: "col1-91", sve_number: "4" },
+	BasicEnergy { names: &["psychic"], symbol: "P", card_id: "col1-92", sve_number: "5" },
+	BasicEnergy { names: &["fighting"], symbol: "F", card_id: "col1-93", sve_number: "6" },
+	BasicEnergy { names: &["darkness", "dark"], symbol: "D", card_id: "col1-94", sve_number: "7" },
+	BasicEnergy { names: &["metal", "steel"], symbol: "M", card_id: "col1-95", sve_number: "8" },
+];
+
+/// The basic energy for a type name or PTCGL symbol, eg. "water", "dark" or "{L}".
+pub fn find_basic_energy(name: &str) -> Option<&'static BasicEnergy> {
+	let name = name.to_lowercase();
+
+	BASIC_ENERGIES
+		.iter()
+		.find(|e| e.names.contains(&name.as_str()) || format!("{{{}}}", e.symbol.to_lowercase()) == name)
+}
+
+pub fn is_basic_energy(card_id: &str) -> bool {
+	BASIC_ENERGIES.iter().any(|e| e.card_id == card_id)
 }
 
 impl Deck {
-	pub fn empty(discord_id: i64, name: String) -> Self {
+	pub fn empty(discord_id: i64, economy: i64, name: String) -> Self {
 		Self {
 			id: None,
 			discord_id,
 			name,
 			cards: HashMap::new(),
-			display_card: "".into()
+			display_card: "".into(),
+			economy,
+			draft: false
 		}
 	}
 
-	pub fn is_valid(&self) -> bool {
-		self.cards.values().sum::<i64>() == 60
+	/// What keeps the deck from being played in each format, see legality::violations.
+	pub async fn legality(&self, ctx: &Context) -> Vec<(Format, Vec<String>)> {
+		let cards = legality::with_amounts(self.get_cards(ctx).await, &self.cards);
+
+		legality::legality(&cards)
 	}
 
 	pub async fn get_cards(&self, ctx: &Context) -> Vec<Card> {
@@ -119,12 +194,21 @@ impl PaginateEmbed for Deck {
 
 #[async_trait]
 impl Scrollable for Vec<Deck> {
-	async fn scroll_through(&self, ctx: &Context, msg: &Message) -> Result<(), String> {
+	async fn scroll_through(&self, ctx: &Context, msg: &Message) -> BotResult<()> {
 		let left_arrow = ReactionType::try_from("⬅️").expect("No left arrow");
 		let right_arrow = ReactionType::try_from("➡️").expect("No right arrow");
 		let pokemon_card = ReactionType::try_from("<:poketcg:965802882433703936>").expect("No TCG Back");
 		let decks = &self.clone();
-		let embeds = self.iter().map(|e| e.embed()).collect::<Vec<_>>();
+		let mut embeds = vec![];
+		for deck in self {
+			let mut embed = deck.embed();
+			let mut desc = legality::badges(&deck.legality(ctx).await);
+			if deck.draft {
+				desc = format!("📝 Draft\n{}", desc);
+			}
+			embed.description(desc);
+			embeds.push(embed);
+		}
 		let mut idx: i16 = 0;
 		let mut deck = decks.into_iter().nth(idx as usize).unwrap();
 		let mut deck_display = deck.get_display_image(ctx).await;
@@ -204,10 +288,12 @@ pub async fn add_deck(deck: &Deck) {
 		.unwrap();
 }
 
-pub async fn get_decks_by_player(discord_id: i64) -> Vec<Deck> {
+pub async fn get_decks_by_player(discord_id: i64, economy: i64) -> Vec<Deck> {
 	let deck_collection = get_deck_collection().await;
+	let mut filter = doc! { "discord_id": discord_id };
+	filter.extend(economy_filter(economy));
 	let decks = deck_collection
-		.find(doc! { "discord_id": discord_id }, None)
+		.find(filter, None)
 		.await
 		.unwrap()
 		.try_collect::<Vec<Deck>>()
@@ -217,10 +303,12 @@ pub async fn get_decks_by_player(discord_id: i64) -> Vec<Deck> {
 	decks
 }
 
-pub async fn get_deck(discord_id: i64, name: String) -> Option<Deck> {
+pub async fn get_deck(discord_id: i64, economy: i64, name: String) -> Option<Deck> {
 	let deck_collection = get_deck_collection().await;
+	let mut filter = doc! { "discord_id": discord_id, "name": name };
+	filter.extend(economy_filter(economy));
 	let deck = deck_collection
-		.find_one(doc! { "discord_id": discord_id, "name": name }, None)
+		.find_one(filter, None)
 		.await
 		.unwrap();
 
@@ -252,9 +340,10 @@ pub async fn delete_deck(deck: &Deck) {
 
 #[command("decks")]
 #[aliases("dks")]
+#[description("Shows all your decks")]
 async fn decks_command(ctx: &Context, msg: &Message) -> CommandResult {
-	let player = get_player(msg.author.id.0).await;
-	let decks = get_decks_by_player(player.discord_id).await;
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let decks = get_decks_by_player(player.discord_id, player.economy).await;
 	match decks.len() {
 		0 => {
 			msg.reply(&ctx.http, "You don't have any decks! Use **.deck create <name>** to create one!").await?;
@@ -267,18 +356,30 @@ async fn decks_command(ctx: &Context, msg: &Message) -> CommandResult {
 
 #[command("deck")]
 #[aliases("dk")]
-#[sub_commands(deck_view, deck_create, deck_delete, deck_add, deck_remove, deck_energy_main, deck_display)]
+#[sub_commands(deck_view, deck_create, deck_delete, deck_add, deck_remove, deck_energy_main, deck_display, deck_import, deck_export, deck_legality, deck_stats, deck_draw, deck_sim, deck_image, deck_autobuild, deck_draft, deck_diff, deck_commit)]
+#[description("Shows the deck commands")]
 async fn deck_main(ctx: &Context, msg: &Message) -> CommandResult {
 	let content = "Here are the available deck commands:
 	**.decks** to see all your current decks.
 	**.deck view <name>** to view a specific deck
+	**.deck image <name>** to see a whole deck in one image.
 	**.deck create <name>** to create a new deck.
+	**.deck autobuild <name> [type]** to build a deck from your collection.
+	**.deck draft <name>** to create a draft deck you can add any card to, owned or not.
+	**.deck diff <name>** to see which cards of a draft deck you own and what the rest cost.
+	**.deck commit <name>** to move the cards you own into a draft deck and wishlist the rest.
 	**.deck delete <name>** to delete a deck that you've created.
 	**.deck add <name> [<cardID:amount>/...]** to add cards to a deck.
 	**.deck remove <name> [<cardID:amount>/...]** to remove cards from a deck.
 	**.deck energy add <name> <type> [amount - Default: 1]** to add a basic energy to a deck.
 	**.deck energy remove <name> <type> [amount - Default: 1]** to remove a basic energy from a deck.
-	**.deck display <name> <cardID>** to set the display card of the deck";
+	**.deck display <name> <cardID>** to set the display card of the deck
+	**.deck import <name>** with a PTCG Live decklist on the next lines to make a deck from it.
+	**.deck export <name>** to get a deck as a PTCG Live decklist.
+	**.deck legality <name> [format]** to see which formats a deck can be played in.
+	**.deck stats <name> [cardID]** to see a deck's breakdown and the odds of drawing its cards.
+	**.deck draw <name>** to draw a sample opening hand and prizes.
+	**.deck sim <name> [games]** to see how often a deck mulligans and prizes its key cards.";
 	msg
 		.channel_id
 		.send_message(&ctx.http, |m| m.content(content))
@@ -289,13 +390,16 @@ async fn deck_main(ctx: &Context, msg: &Message) -> CommandResult {
 
 #[command("view")]
 #[aliases("v")]
+#[description("Shows one of your decks")]
+#[usage("<name>")]
+#[example("blastoise")]
 async fn deck_view(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
 	let deck_name = args.rest().to_lowercase();
-	let player = get_player(msg.author.id.0).await;
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
 	if deck_name == String::from("") {
 		return decks_command(ctx, msg, args).await;
 	}
-	let deck = get_deck(player.discord_id, deck_name.clone()).await;
+	let deck = get_deck(player.discord_id, player.economy, deck_name.clone()).await;
 	match deck {
 		Some(_) => (),
 		None => {
@@ -310,6 +414,12 @@ async fn deck_view(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
 		.map(|c| c.into())
 		.collect::<Vec<String>>();
 	let cards = get_multiple_cards_by_id(ctx, card_ids).await;
+	let legality = legality::legality(&legality::with_amounts(cards.clone(), &deck.cards));
+	let draft = match deck.draft {
+		true => " (draft)",
+		false => ""
+	};
+	msg.reply(&ctx.http, format!("**{}**{}\n{}", deck.name, draft, legality::badges(&legality))).await?;
 	cards.scroll_through(ctx, msg).await?;
 	
 	Ok(())
@@ -317,21 +427,25 @@ async fn deck_view(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
 
 #[command("create")]
 #[aliases("c")]
+#[description("Creates a new deck")]
+#[usage("<name>")]
+#[example("blastoise")]
+#[min_args(1)]
 async fn deck_create(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
 	let deck_name = args.rest().to_lowercase();
 	if deck_name == String::from("") {
 		msg.reply(&ctx.http, "You didn't provide a deck name.").await?;
 		return Ok(());
 	}
-	let player = get_player(msg.author.id.0).await;
-	match get_deck(player.discord_id, deck_name.clone()).await {
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	match get_deck(player.discord_id, player.economy, deck_name.clone()).await {
 		Some(_) => {
 			msg.reply(&ctx.http, "You already have a deck with that name!").await?;
 			return Ok(());
 		},
 		None => ()
 	}
-	let deck = Deck::empty(player.discord_id, deck_name.clone());
+	let deck = Deck::empty(player.discord_id, player.economy, deck_name.clone());
 	add_deck(&deck).await;
 	msg.reply(&ctx.http, format!("You created the deck **{}**", deck_name)).await?;
 
@@ -340,14 +454,18 @@ async fn deck_create(ctx: &Context, msg: &Message, args: Args) -> CommandResult
 
 #[command("delete")]
 #[aliases("d")]
+#[description("Deletes one of your decks")]
+#[usage("<name>")]
+#[example("blastoise")]
+#[min_args(1)]
 async fn deck_delete(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
 	let deck_name = args.rest().to_lowercase();
 	if deck_name == String::from("") {
 		msg.reply(&ctx.http, "You didn't provide a deck name.").await?;
 		return Ok(());
 	}
-	let mut player = get_player(msg.author.id.0).await;
-	let deck = get_deck(player.discord_id, deck_name.clone()).await;
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let deck = get_deck(player.discord_id, player.economy, deck_name.clone()).await;
 	match deck {
 		Some(_) => (),
 		None => {
@@ -368,7 +486,7 @@ async fn deck_delete(ctx: &Context, msg: &Message, args: Args) -> CommandResult
 	}
 	// Player said "y" to get here
 	for (crd, amt) in deck.cards.iter() {
-		if vec!["col1-94", "col1-93", "col1-89", "col1-88", "col1-91", "col1-95", "col1-92", "col1-90"].contains(&crd.as_str()) {
+		if is_basic_energy(crd) || deck.draft {
 			continue;
 		}
 		*player.cards.entry(crd.clone()).or_insert(0) += amt;
@@ -380,7 +498,7 @@ async fn deck_delete(ctx: &Context, msg: &Message, args: Args) -> CommandResult
 		player_cards_update.insert(crd, amt);
 	}
 	player_update.insert("cards", player_cards_update);
-	update_player(&player, doc! { "$set": player_update }).await;
+	update_player(&player, doc! { "$set": player_update }).await?;
 	delete_deck(&deck).await;
 	msg.reply(&ctx.http, format!("You deleted **{}**", deck.name)).await?;
 
@@ -389,6 +507,10 @@ async fn deck_delete(ctx: &Context, msg: &Message, args: Args) -> CommandResult
 
 #[command("add")]
 #[aliases("a")]
+#[description("Adds cards to a deck")]
+#[usage("<name> <card id:amount/...>")]
+#[example("blastoise base1-2:2/base1-15")]
+#[min_args(2)]
 async fn deck_add(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
 	let deck_name = args.find::<String>().unwrap_or(String::from(""));
 	if deck_name == String::from("") {
@@ -400,8 +522,8 @@ async fn deck_add(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult
 		msg.reply(&ctx.http, "You didn't provide cards to add.").await?;
 		return Ok(());
 	}
-	let mut player = get_player(msg.author.id.0).await;
-	let deck = get_deck(player.discord_id, deck_name.clone()).await;
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let deck = get_deck(player.discord_id, player.economy, deck_name.clone()).await;
 	match deck {
 		Some(_) => (),
 		None => {
@@ -411,21 +533,29 @@ async fn deck_add(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult
 	}
 	let mut deck = deck.unwrap();
 	let deckcards = DeckCards::from_card_str(card_str);
-	if !deckcards.player_has_all(&player) {
+	if !deck.draft && !deckcards.player_has_all(&player) {
 		msg.reply(&ctx.http, "You don't own all of what you're putting in the deck!").await?;
 		return Ok(());
 	}
-	if !deckcards.is_valid_addition(&deck) {
-		// Maybe update this to list what's not valid
-		msg.reply(&ctx.http, "You have invalid additions to this deck!").await?;
+	let violations = deckcards.addition_violations(ctx, &deck).await;
+	if violations.len() > 0 {
+		let violations = violations
+			.iter()
+			.map(|v| format!("- {}", v))
+			.collect::<Vec<String>>()
+			.join("\n");
+		msg.reply(&ctx.http, format!("You can't add those to **{}**, it would have:\n{}", deck.name, violations)).await?;
 		return Ok(());
 	}
 	for (card_id, amt) in deckcards.cards {
+		*deck.cards.entry(card_id.clone()).or_insert(0) += amt;
+		if deck.draft {
+			continue;
+		}
 		*player.cards.entry(card_id.clone()).or_insert(0) -= amt;
 		if *player.cards.entry(card_id.clone()).or_insert(0) == 0 {
 			player.cards.remove(&card_id);
 		}
-		*deck.cards.entry(card_id.clone()).or_insert(0) += amt;
 	}
 	player.cards.retain(|_, v| *v > 0);
 	// Update the player
@@ -435,7 +565,7 @@ async fn deck_add(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult
 		player_cards_update.insert(crd, amt);
 	}
 	player_update.insert("cards", player_cards_update);
-	update_player(&player, doc! { "$set": player_update }).await;
+	update_player(&player, doc! { "$set": player_update }).await?;
 
 	// Update the deck
 	let mut deck_update = Document::new();
@@ -453,6 +583,10 @@ async fn deck_add(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult
 
 #[command("remove")]
 #[aliases("r")]
+#[description("Removes cards from a deck")]
+#[usage("<name> <card id:amount/...>")]
+#[example("blastoise base1-15")]
+#[min_args(2)]
 async fn deck_remove(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
 	let deck_name = args.find::<String>().unwrap_or(String::from(""));
 	if deck_name == String::from("") {
@@ -464,8 +598,8 @@ async fn deck_remove(ctx: &Context, msg: &Message, mut args: Args) -> CommandRes
 		msg.reply(&ctx.http, "You didn't provide cards to remove.").await?;
 		return Ok(());
 	}
-	let mut player = get_player(msg.author.id.0).await;
-	let deck = get_deck(player.discord_id, deck_name.clone()).await;
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let deck = get_deck(player.discord_id, player.economy, deck_name.clone()).await;
 	match deck {
 		Some(_) => (),
 		None => {
@@ -480,14 +614,16 @@ async fn deck_remove(ctx: &Context, msg: &Message, mut args: Args) -> CommandRes
 		return Ok(());
 	}
 	for (card_id, amt) in deckcards.cards {
-		if vec!["col1-94", "col1-93", "col1-89", "col1-88", "col1-91", "col1-95", "col1-92", "col1-90"].contains(&card_id.as_str()) {
+		if is_basic_energy(&card_id) {
 			continue;
 		}
 		*deck.cards.entry(card_id.clone()).or_insert(0) -= amt;
 		if *deck.cards.entry(card_id.clone()).or_insert(0) == 0 {
 			deck.cards.remove(&card_id);
 		}
-		*player.cards.entry(card_id.clone()).or_insert(0) += amt;
+		if !deck.draft {
+			*player.cards.entry(card_id.clone()).or_insert(0) += amt;
+		}
 	}
 	deck.cards.retain(|_, v| *v > 0);
 	// Update the player
@@ -497,7 +633,7 @@ async fn deck_remove(ctx: &Context, msg: &Message, mut args: Args) -> CommandRes
 		player_cards_update.insert(crd, amt);
 	}
 	player_update.insert("cards", player_cards_update);
-	update_player(&player, doc! { "$set": player_update }).await;
+	update_player(&player, doc! { "$set": player_update }).await?;
 
 	// Update the deck
 	let mut deck_update = Document::new();
@@ -516,6 +652,7 @@ async fn deck_remove(ctx: &Context, msg: &Message, mut args: Args) -> CommandRes
 #[command("energy")]
 #[aliases("e")]
 #[sub_commands(deck_energy_add, deck_energy_remove)]
+#[description("Shows the deck energy commands")]
 async fn deck_energy_main(ctx: &Context, msg: &Message) -> CommandResult {
 	let content = "Here are the available deck energy commands:
 	**.deck energy add <name> <type> [amount - Default: 1]** to add a basic energy to a deck.
@@ -542,6 +679,10 @@ async fn deck_energy_main(ctx: &Context, msg: &Message) -> CommandResult {
 
 #[command("add")]
 #[aliases("a")]
+#[description("Adds basic energy to a deck")]
+#[usage("<name> <type> [amount - Default: 1]")]
+#[example("blastoise water 10")]
+#[min_args(2)]
 async fn deck_energy_add(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
 	let deck_name = args.find::<String>().unwrap_or(String::from(""));
 	if deck_name == String::from("") {
@@ -553,16 +694,9 @@ async fn deck_energy_add(ctx: &Context, msg: &Message, mut args: Args) -> Comman
 		msg.reply(&ctx.http, "You didn't provide an energy type.").await?;
 		return Ok(());
 	}
-	let energy_card = match energy_type.to_lowercase().as_str() {
-		"darkness" | "dark" => "col1-94",
-		"fighting" => "col1-93",
-		"fire" => "col1-89",
-		"grass" => "col1-88",
-		"lightning" | "electric" => "col1-91",
-		"metal" | "steel" => "col1-95",
-		"psychic" => "col1-92",
-		"water" => "col1-90",
-		_ => {
+	let energy_card = match find_basic_energy(&energy_type) {
+		Some(x) => x.card_id,
+		None => {
 			msg.reply(&ctx.http, "You didn't select a valid basic energy type.").await?;
 			return Ok(());
 		},
@@ -571,8 +705,8 @@ async fn deck_energy_add(ctx: &Context, msg: &Message, mut args: Args) -> Comman
 		Ok(x) => x,
 		Err(_) => 1
 	};
-	let player = get_player(msg.author.id.0).await;
-	let deck = get_deck(player.discord_id, deck_name.clone()).await;
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let deck = get_deck(player.discord_id, player.economy, deck_name.clone()).await;
 	match deck {
 		Some(_) => (),
 		None => {
@@ -602,6 +736,10 @@ async fn deck_energy_add(ctx: &Context, msg: &Message, mut args: Args) -> Comman
 
 #[command("remove")]
 #[aliases("r")]
+#[description("Removes basic energy from a deck")]
+#[usage("<name> <type> [amount - Default: 1]")]
+#[example("blastoise water 2")]
+#[min_args(2)]
 async fn deck_energy_remove(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
 	let deck_name = args.find::<String>().unwrap_or(String::from(""));
 	if deck_name == String::from("") {
@@ -613,16 +751,9 @@ async fn deck_energy_remove(ctx: &Context, msg: &Message, mut args: Args) -> Com
 		msg.reply(&ctx.http, "You didn't provide an energy type.").await?;
 		return Ok(());
 	}
-	let energy_card = match energy_type.to_lowercase().as_str() {
-		"darkness" | "dark" => "col1-94",
-		"fighting" => "col1-93",
-		"fire" => "col1-89",
-		"grass" => "col1-88",
-		"lightning" | "electric" => "col1-91",
-		"metal" | "steel" => "col1-95",
-		"psychic" => "col1-92",
-		"water" => "col1-90",
-		_ => {
+	let energy_card = match find_basic_energy(&energy_type) {
+		Some(x) => x.card_id,
+		None => {
 			msg.reply(&ctx.http, "You didn't select a valid basic energy type.").await?;
 			return Ok(());
 		},
@@ -631,8 +762,8 @@ async fn deck_energy_remove(ctx: &Context, msg: &Message, mut args: Args) -> Com
 		Ok(x) => x,
 		Err(_) => 1
 	};
-	let player = get_player(msg.author.id.0).await;
-	let deck = get_deck(player.discord_id, deck_name.clone()).await;
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let deck = get_deck(player.discord_id, player.economy, deck_name.clone()).await;
 	match deck {
 		Some(_) => (),
 		None => {
@@ -641,7 +772,7 @@ async fn deck_energy_remove(ctx: &Context, msg: &Message, mut args: Args) -> Com
 		}
 	}
 	let mut deck = deck.unwrap();
-	let energy_amount = deck.cards.get(energy_card.into()).unwrap_or(&0).clone();
+	let energy_amount = deck.cards.get(energy_card).unwrap_or(&0).clone();
 	if energy_amount < amount {
 		amount = energy_amount;
 	}
@@ -663,6 +794,10 @@ async fn deck_energy_remove(ctx: &Context, msg: &Message, mut args: Args) -> Com
 
 #[command("display")]
 #[aliases("d")]
+#[description("Sets the card shown for a deck")]
+#[usage("<name> <card id>")]
+#[example("blastoise base1-2")]
+#[min_args(2)]
 async fn deck_display(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
 	let deck_name = args.find::<String>().unwrap_or(String::from(""));
 	if deck_name == String::from("") {
@@ -674,8 +809,8 @@ async fn deck_display(ctx: &Context, msg: &Message, mut args: Args) -> CommandRe
 		msg.reply(&ctx.http, "You didn't provide a card.").await?;
 		return Ok(());
 	}
-	let player = get_player(msg.author.id.0).await;
-	let deck = get_deck(player.discord_id, deck_name.clone()).await;
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let deck = get_deck(player.discord_id, player.economy, deck_name.clone()).await;
 	match deck {
 		Some(_) => (),
 		None => {
@@ -735,24 +870,17 @@ impl DeckCards {
 		true
 	}
 
-	pub fn is_valid_addition(&self, deck: &Deck) -> bool {
-		let deckcards_sum = self.cards
-			.iter()
-			.map(|ca| ca.1)
-			.collect::<Vec<i64>>()
-			.iter()
-			.sum::<i64>();
-		let deck_sum = deck.cards.values().sum::<i64>();
-		if deckcards_sum + deck_sum > 60 {
-			return false;
-		}
+	/// What would be wrong with the deck after adding these cards. Copies are
+	/// counted by card name, and basic energy doesn't count towards them.
+	pub async fn addition_violations(&self, ctx: &Context, deck: &Deck) -> Vec<String> {
+		let mut amounts = deck.cards.clone();
 		for (card_id, amt) in &self.cards {
-			if deck.cards.get(card_id).unwrap_or(&0) + amt > 4 {
-				return false;
-			}
+			*amounts.entry(card_id.clone()).or_insert(0) += amt;
 		}
+		let card_ids = amounts.keys().cloned().collect::<Vec<String>>();
+		let cards = legality::with_amounts(get_multiple_cards_by_id(ctx, card_ids).await, &amounts);
 
-		true
+		legality::addition_violations(&cards)
 	}
 
 	pub fn deck_has_all(&self, deck: &Deck) -> bool {
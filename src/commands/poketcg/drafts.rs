@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+use mongodb::bson::{
+	doc,
+	Document
+};
+use serenity::{
+	framework::{
+		standard::{
+			macros::{
+				command
+			},
+			Args,
+			CommandResult
+		},
+	},
+	model::{
+		channel::{
+			Message,
+		},
+	},
+	utils::{
+		Colour
+	},
+	prelude::*
+};
+
+use crate::error::BotResult;
+
+use super::{
+	card::Card,
+	decks::{
+		add_deck,
+		get_deck,
+		is_basic_energy,
+		update_deck,
+		Deck
+	},
+	player::{
+		get_player,
+		update_player,
+		wishlist_doc,
+		Player
+	}
+};
+
+// A card in a draft deck with how many the deck has and how many the player owns
+struct DraftCard {
+	card: Card,
+	needed: i64,
+	owned: i64
+}
+
+impl DraftCard {
+	fn missing(&self) -> i64 {
+		(self.needed - self.owned).max(0)
+	}
+
+	fn missing_cost(&self) -> f64 {
+		self.card.price * self.missing() as f64
+	}
+}
+
+/// The deck's cards next to how many the player owns. Basic energy is free so it's left out.
+async fn draft_cards(ctx: &Context, deck: &Deck, player: &Player) -> Vec<DraftCard> {
+	let cards = deck
+		.get_cards(ctx)
+		.await
+		.into_iter()
+		.map(|c| (c.card_id.clone(), c))
+		.collect::<HashMap<String, Card>>();
+	let mut ret = deck.cards
+		.iter()
+		.filter(|(card_id, _)| !is_basic_energy(card_id))
+		.filter_map(|(card_id, needed)| Some(DraftCard {
+			card: cards.get(card_id)?.clone(),
+			needed: *needed,
+			owned: *player.cards.get(card_id).unwrap_or(&0)
+		}))
+		.collect::<Vec<DraftCard>>();
+	ret.sort_by(|c1, c2| c1.card.name.cmp(&c2.card.name));
+
+	ret
+}
+
+// Lists as many lines as fit in an embed field
+fn field_lines(lines: Vec<String>) -> String {
+	let mut ret = String::new();
+	for (i, line) in lines.iter().enumerate() {
+		if ret.chars().count() + line.chars().count() > 1000 {
+			ret.push_str(&format!("_and {} more_", lines.len() - i));
+			break;
+		}
+		ret.push_str(line);
+		ret.push('\n');
+	}
+	if ret == "" {
+		ret = String::from("None");
+	}
+
+	ret
+}
+
+async fn get_draft(ctx: &Context, msg: &Message, player: &Player, deck_name: String) -> BotResult<Option<Deck>> {
+	let deck = match get_deck(player.discord_id, player.economy, deck_name).await {
+		Some(x) => x,
+		None => {
+			msg.reply(&ctx.http, "You don't have a deck with that name.").await?;
+			return Ok(None);
+		}
+	};
+	if !deck.draft {
+		msg.reply(&ctx.http, format!("**{}** isn't a draft, its cards are already out of your collection.", deck.name)).await?;
+		return Ok(None);
+	}
+
+	Ok(Some(deck))
+}
+
+#[command("draft")]
+#[aliases("df")]
+#[description("Creates a draft deck to plan with. You can add any card to it, owned or not")]
+#[usage("<name>")]
+#[example("charizard")]
+#[min_args(1)]
+async fn deck_draft(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+	let deck_name = args.rest().to_lowercase();
+	if deck_name == String::from("") {
+		msg.reply(&ctx.http, "You didn't provide a deck name.").await?;
+		return Ok(());
+	}
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	if get_deck(player.discord_id, player.economy, deck_name.clone()).await.is_some() {
+		msg.reply(&ctx.http, "You already have a deck with that name!").await?;
+		return Ok(());
+	}
+	let mut deck = Deck::empty(player.discord_id, player.economy, deck_name.clone());
+	deck.draft = true;
+	add_deck(&deck).await;
+	msg.reply(&ctx.http, format!("You created the draft deck **{}**. Cards you add to it stay in your collection until you **.deck commit** it", deck_name)).await?;
+
+	Ok(())
+}
+
+#[command("diff")]
+#[aliases("missing")]
+#[description("Shows which cards of a draft deck you own and what the missing ones cost")]
+#[usage("<name>")]
+#[example("charizard")]
+#[min_args(1)]
+async fn deck_diff(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+	let deck_name = args.rest().to_lowercase();
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let deck = match get_draft(ctx, msg, &player, deck_name).await? {
+		Some(x) => x,
+		None => return Ok(())
+	};
+	let cards = draft_cards(ctx, &deck, &player).await;
+	let needed = cards.iter().map(|c| c.needed).sum::<i64>();
+	let missing = cards.iter().map(|c| c.missing()).sum::<i64>();
+	let cost = cards.iter().map(|c| c.missing_cost()).sum::<f64>();
+	let owned_lines = cards
+		.iter()
+		.filter(|c| c.missing() == 0)
+		.map(|c| format!("{}x **{}** (_{}_)", c.needed, c.card.name, c.card.card_id))
+		.collect::<Vec<String>>();
+	let missing_lines = cards
+		.iter()
+		.filter(|c| c.missing() > 0)
+		.map(|c| format!("**{}** (_{}_) {}/{} - ${:.2}", c.card.name, c.card.card_id, c.owned.min(c.needed), c.needed, c.missing_cost()))
+		.collect::<Vec<String>>();
+	msg
+		.channel_id
+		.send_message(&ctx.http, |m| {
+			m.embed(|e| {
+				e
+					.title(format!("{} - Owned and missing", deck.name))
+					.description(format!("You own **{}/{}** of the cards. The **{}** missing cards cost **${:.2}**", needed - missing, needed, missing, cost))
+					.field("Owned", field_lines(owned_lines), false)
+					.field("Missing", field_lines(missing_lines), false)
+					.colour(Colour::from_rgb(255, 50, 20))
+			})
+		})
+		.await?;
+
+	Ok(())
+}
+
+#[command("commit")]
+#[description("Turns a draft deck into a real deck. The cards you own are moved into it and the missing ones go on your wishlist")]
+#[usage("<name>")]
+#[example("charizard")]
+#[min_args(1)]
+async fn deck_commit(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+	let deck_name = args.rest().to_lowercase();
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let mut deck = match get_draft(ctx, msg, &player, deck_name).await? {
+		Some(x) => x,
+		None => return Ok(())
+	};
+	let cards = draft_cards(ctx, &deck, &player).await;
+	// Only the basic energy stays as it is, everything else is what the player can put in
+	deck.cards.retain(|card_id, _| is_basic_energy(card_id));
+	let mut moved = 0;
+	let mut wished = 0;
+	let mut cost = 0.0;
+	for card in &cards {
+		let card_id = &card.card.card_id;
+		let taken = card.owned.min(card.needed);
+		*player.cards.entry(card_id.clone()).or_insert(0) -= taken;
+		deck.cards.insert(card_id.clone(), taken);
+		moved += taken;
+		if card.missing() > 0 {
+			*player.wishlist.entry(card_id.clone()).or_insert(0) += card.missing();
+			wished += card.missing();
+			cost += card.missing_cost();
+		}
+	}
+	player.cards.retain(|_, v| *v > 0);
+	deck.cards.retain(|_, v| *v > 0);
+	deck.draft = false;
+
+	let mut player_update = Document::new();
+	let mut player_cards_update = Document::new();
+	for (crd, amt) in player.cards.iter() {
+		player_cards_update.insert(crd, amt);
+	}
+	player_update.insert("cards", player_cards_update);
+	player_update.insert("wishlist", wishlist_doc(&player.wishlist));
+	update_player(&player, doc! { "$set": player_update }).await?;
+
+	let mut deck_update = Document::new();
+	let mut deck_card_update = Document::new();
+	for (crd, amt) in deck.cards.iter() {
+		deck_card_update.insert(crd, amt);
+	}
+	deck_update.insert("cards", deck_card_update);
+	deck_update.insert("draft", false);
+	update_deck(&deck, doc! { "$set": deck_update }).await;
+
+	let mut content = format!("You committed **{}** and moved **{}** cards into it from your collection", deck.name, moved);
+	if wished > 0 {
+		content.push_str(&format!("\nThe **{}** cards you're missing (**${:.2}**) were added to your wishlist", wished, cost));
+	}
+	msg.reply(&ctx.http, content).await?;
+
+	Ok(())
+}
@@ -0,0 +1,343 @@
+use std::{
+	borrow::Cow,
+	collections::HashMap
+};
+use chrono::Utc;
+use serde_json::{
+	json,
+	Value
+};
+use serenity::{
+	framework::{
+		standard::{
+			macros::{
+				command
+			},
+			Args,
+			CommandResult
+		},
+	},
+	model::{
+		channel::{
+			AttachmentType,
+			Message,
+		},
+	},
+	prelude::*
+};
+
+use crate::error::BotResult;
+
+use super::{
+	card::{
+		get_cards_by_set,
+		get_multiple_cards_by_id
+	},
+	decks::get_decks_by_player,
+	player::{
+		get_player,
+		Player
+	},
+	player_card::player_cards,
+	sets::get_set,
+	Idable
+};
+
+struct CardRow {
+	id: String,
+	name: String,
+	set_id: String,
+	set_name: String,
+	number: String,
+	rarity: String,
+	amount: i64,
+	price: f64,
+	saved: bool
+}
+
+struct PackRow {
+	set_id: String,
+	set_name: String,
+	amount: i64
+}
+
+struct BinderRow {
+	set_id: String,
+	set_name: String,
+	collected: usize,
+	set_cards: usize,
+	completed: bool
+}
+
+struct DeckRow {
+	name: String,
+	// A draft deck's cards are still in the player's collection
+	draft: bool,
+	// The formats the deck can be played in
+	legal_formats: Vec<&'static str>,
+	// Card id, name and amount
+	cards: Vec<(String, String, i64)>
+}
+
+// Everything in a player's export
+struct PlayerExport {
+	cards: Vec<CardRow>,
+	packs: Vec<PackRow>,
+	binders: Vec<BinderRow>,
+	decks: Vec<DeckRow>
+}
+
+// Sorts by set, then by the card's number in the set like .my cards id
+fn card_number(number: &str) -> (i64, String) {
+	(number.parse::<i64>().unwrap_or(999), String::from(number))
+}
+
+async fn collect(ctx: &Context, player: &Player) -> BotResult<PlayerExport> {
+	let mut cards = player_cards(ctx, player.cards.clone())
+		.await
+		.into_iter()
+		.map(|pc| CardRow {
+			id: pc.card.card_id.clone(),
+			name: pc.card.name.clone(),
+			set_id: pc.card.set.id(),
+			set_name: pc.card.set.name.clone(),
+			number: pc.card.number.clone(),
+			rarity: pc.card.rarity.clone(),
+			amount: pc.amount,
+			price: pc.card.price,
+			saved: player.savelist.contains(&pc.card.card_id)
+		})
+		.collect::<Vec<CardRow>>();
+	cards.sort_by(|c1, c2| (&c1.set_id, card_number(&c1.number)).cmp(&(&c2.set_id, card_number(&c2.number))));
+
+	let mut packs = vec![];
+	for (set_id, amount) in &player.packs {
+		let set = get_set(set_id).await?;
+		packs.push(PackRow {
+			set_id: set.id(),
+			set_name: set.name,
+			amount: *amount
+		});
+	}
+	packs.sort_by(|p1, p2| p1.set_id.cmp(&p2.set_id));
+
+	let mut binders = vec![];
+	let binder_sets = player.completed_binders
+		.iter()
+		.map(|s| (s, true))
+		.chain(Some((&player.current_binder.set, false)).filter(|(s, _)| s.as_str() != ""));
+	for (set_id, completed) in binder_sets {
+		let set = get_set(set_id).await?;
+		let set_cards = get_cards_by_set(ctx, &set).await.len();
+		binders.push(BinderRow {
+			set_id: set.id(),
+			set_name: set.name,
+			collected: match completed {
+				true => set_cards,
+				false => player.current_binder.cards.len()
+			},
+			set_cards,
+			completed
+		});
+	}
+
+	let player_decks = get_decks_by_player(player.discord_id, player.economy).await;
+	let deck_card_ids = player_decks
+		.iter()
+		.flat_map(|d| d.cards.keys().cloned())
+		.collect::<Vec<String>>();
+	let card_names = get_multiple_cards_by_id(ctx, deck_card_ids)
+		.await
+		.into_iter()
+		.map(|c| (c.card_id, c.name))
+		.collect::<HashMap<String, String>>();
+	let mut decks = vec![];
+	for d in &player_decks {
+		let mut cards = d.cards
+			.iter()
+			.map(|(id, amount)| (id.clone(), card_names.get(id).cloned().unwrap_or_default(), *amount))
+			.collect::<Vec<(String, String, i64)>>();
+		cards.sort();
+		let legal_formats = d.legality(ctx)
+			.await
+			.iter()
+			.filter(|(_, violations)| violations.len() == 0)
+			.map(|(format, _)| format.name())
+			.collect();
+		decks.push(DeckRow {
+			name: d.name.clone(),
+			draft: d.draft,
+			legal_formats,
+			cards
+		});
+	}
+	decks.sort_by(|d1, d2| d1.name.cmp(&d2.name));
+
+	Ok(PlayerExport {
+		cards,
+		packs,
+		binders,
+		decks
+	})
+}
+
+// Quotes a field if it would break the row, eg. card names with commas
+fn csv_field(field: &str) -> String {
+	match field.contains(|c| c == ',' || c == '"' || c == '\n') {
+		true => format!("\"{}\"", field.replace('"', "\"\"")),
+		false => String::from(field)
+	}
+}
+
+fn csv(header: &[&str], rows: Vec<Vec<String>>) -> Vec<u8> {
+	let mut ret = header.join(",");
+	ret.push('\n');
+	for row in rows {
+		ret.push_str(&row.iter().map(|f| csv_field(f)).collect::<Vec<String>>().join(","));
+		ret.push('\n');
+	}
+
+	ret.into_bytes()
+}
+
+fn to_csv(collection: &PlayerExport) -> Vec<(String, Vec<u8>)> {
+	let cards = collection.cards
+		.iter()
+		.map(|c| vec![
+			c.id.clone(),
+			c.name.clone(),
+			c.set_id.clone(),
+			c.set_name.clone(),
+			c.number.clone(),
+			c.rarity.clone(),
+			c.amount.to_string(),
+			format!("{:.2}", c.price),
+			c.saved.to_string()
+		])
+		.collect();
+	let packs = collection.packs
+		.iter()
+		.map(|p| vec![p.set_id.clone(), p.set_name.clone(), p.amount.to_string()])
+		.collect();
+	let binders = collection.binders
+		.iter()
+		.map(|b| vec![
+			b.set_id.clone(),
+			b.set_name.clone(),
+			b.collected.to_string(),
+			b.set_cards.to_string(),
+			b.completed.to_string()
+		])
+		.collect();
+	let decks = collection.decks
+		.iter()
+		.flat_map(|d| d.cards
+			.iter()
+			.map(|(id, name, amount)| vec![d.name.clone(), d.draft.to_string(), d.legal_formats.join(";"), id.clone(), name.clone(), amount.to_string()])
+		)
+		.collect();
+
+	vec![
+		(String::from("cards.csv"), csv(&["id", "name", "set_id", "set_name", "number", "rarity", "quantity", "price", "saved"], cards)),
+		(String::from("packs.csv"), csv(&["set_id", "set_name", "quantity"], packs)),
+		(String::from("binders.csv"), csv(&["set_id", "set_name", "collected", "set_cards", "completed"], binders)),
+		(String::from("decks.csv"), csv(&["deck", "draft", "legal_formats", "id", "name", "quantity"], decks)),
+	]
+}
+
+fn to_json(player: &Player, collection: &PlayerExport) -> Vec<(String, Vec<u8>)> {
+	let cards = collection.cards
+		.iter()
+		.map(|c| json!({
+			"id": c.id,
+			"name": c.name,
+			"set_id": c.set_id,
+			"set_name": c.set_name,
+			"number": c.number,
+			"rarity": c.rarity,
+			"quantity": c.amount,
+			"price": c.price,
+			"saved": c.saved
+		}))
+		.collect::<Vec<Value>>();
+	let packs = collection.packs
+		.iter()
+		.map(|p| json!({ "set_id": p.set_id, "set_name": p.set_name, "quantity": p.amount }))
+		.collect::<Vec<Value>>();
+	let binders = collection.binders
+		.iter()
+		.map(|b| json!({
+			"set_id": b.set_id,
+			"set_name": b.set_name,
+			"collected": b.collected,
+			"set_cards": b.set_cards,
+			"completed": b.completed
+		}))
+		.collect::<Vec<Value>>();
+	let decks = collection.decks
+		.iter()
+		.map(|d| json!({
+			"name": d.name,
+			"draft": d.draft,
+			"legal_formats": d.legal_formats,
+			"cards": d.cards
+				.iter()
+				.map(|(id, name, amount)| json!({ "id": id, "name": name, "quantity": amount }))
+				.collect::<Vec<Value>>()
+		}))
+		.collect::<Vec<Value>>();
+	let export = json!({
+		"discord_id": player.discord_id.to_string(),
+		"economy": player.economy,
+		"exported": Utc::now().to_rfc3339(),
+		"cash": player.cash,
+		"tokens": player.tokens,
+		"cards": cards,
+		"packs": packs,
+		"binders": binders,
+		"decks": decks
+	});
+
+	vec![(String::from("collection.json"), serde_json::to_vec_pretty(&export).unwrap())]
+}
+
+#[command("export")]
+#[aliases("e")]
+#[description("DMs you your cards, packs, binders and decks as files")]
+#[usage("[csv | json - Default: csv]")]
+#[example("json")]
+async fn my_export(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+	let format = match args.single::<String>() {
+		Ok(x) => x.to_lowercase(),
+		Err(_) => String::from("csv")
+	};
+	if format != "csv" && format != "json" {
+		msg.reply(&ctx.http, "I can only export to **csv** or **json**").await?;
+		return Ok(());
+	}
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let collection = collect(ctx, &player).await?;
+	let files = match format.as_str() {
+		"json" => to_json(&player, &collection),
+		_ => to_csv(&collection)
+	};
+	let dm = msg.author
+		.direct_message(&ctx.http, |m| {
+			m.content(format!("Here's your collection: **{}** cards, **{}** packs, **{}** binders and **{}** decks", collection.cards.len(), collection.packs.len(), collection.binders.len(), collection.decks.len()));
+			for (filename, data) in &files {
+				m.add_file(AttachmentType::Bytes {
+					data: Cow::from(data.as_slice()),
+					filename: filename.clone()
+				});
+			}
+
+			m
+		})
+		.await;
+	match dm {
+		Ok(_) => msg.reply(&ctx.http, "Sent you your collection!").await?,
+		Err(_) => msg.reply(&ctx.http, "I couldn't DM you. Make sure you can get DMs from this server!").await?
+	};
+
+	Ok(())
+}
@@ -0,0 +1,351 @@
+use std::{
+	borrow::Cow,
+	io::Cursor
+};
+use futures::future::try_join_all;
+use image::{
+	imageops::overlay,
+	DynamicImage,
+	ImageOutputFormat,
+	RgbaImage
+};
+use imageproc::drawing::draw_text_mut;
+use rand::{
+	seq::SliceRandom,
+	thread_rng
+};
+use rusttype::Scale;
+use serenity::{
+	framework::{
+		standard::{
+			macros::{
+				command
+			},
+			Args,
+			CommandResult
+		},
+	},
+	model::{
+		channel::{
+			AttachmentType,
+			Message,
+		},
+	},
+	utils::{
+		Colour
+	},
+	prelude::*
+};
+
+use crate::error::BotResult;
+
+use super::{
+	card::Card,
+	card_image::{
+		get_card_thumbnail_image,
+		THUMBNAIL_HEIGHT,
+		THUMBNAIL_WIDTH
+	},
+	deck_stats::{
+		BACKGROUND,
+		FONT,
+		HAND_SIZE,
+		TEXT
+	},
+	decks::{
+		get_deck,
+		Deck
+	},
+	legality::{
+		is_basic_energy,
+		is_basic_pokemon,
+		with_amounts
+	},
+	player::get_player
+};
+
+static PRIZES: i64 = 6;
+static DEFAULT_SIMULATIONS: i64 = 1000;
+static MAX_SIMULATIONS: i64 = 10000;
+// How many cards the simulation shows prize odds for
+static KEY_CARDS: usize = 15;
+
+const GAP: u32 = 12;
+const LABEL_HEIGHT: u32 = 30;
+
+// An opening hand and prizes, as indexes into the deck's cards
+struct Deal {
+	hand: Vec<usize>,
+	prizes: Vec<usize>,
+	mulligans: i64
+}
+
+/// Every copy of every card in the deck, as indexes into `cards`.
+fn card_pool(cards: &[(Card, i64)]) -> Vec<usize> {
+	cards
+		.iter()
+		.enumerate()
+		.flat_map(|(i, (_, amount))| (0..*amount).map(move |_| i))
+		.collect()
+}
+
+/// Shuffles and draws an opening hand, mulliganing until it has a Basic Pokémon, then sets aside the prizes.
+/// Decks without a Basic Pokémon keep their first hand since they can never get one.
+fn deal(cards: &[(Card, i64)], pool: &[usize]) -> Deal {
+	let mut rng = thread_rng();
+	let mut pool = pool.to_vec();
+	let has_basic = pool.iter().any(|i| is_basic_pokemon(&cards[*i].0));
+	let mut mulligans = 0;
+	loop {
+		pool.shuffle(&mut rng);
+		if !has_basic || pool[..HAND_SIZE as usize].iter().any(|i| is_basic_pokemon(&cards[*i].0)) {
+			break;
+		}
+		mulligans += 1;
+	}
+
+	Deal {
+		hand: pool[..HAND_SIZE as usize].to_vec(),
+		prizes: pool[HAND_SIZE as usize..(HAND_SIZE + PRIZES) as usize].to_vec(),
+		mulligans
+	}
+}
+
+// Finds a deck by name, with an optional number as the last word
+async fn get_deck_and_number(msg: &Message, args: &Args) -> BotResult<(Option<Deck>, Option<i64>)> {
+	let deck_name = args.rest().to_lowercase();
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	if let Some((name, last)) = deck_name.rsplit_once(' ') {
+		if let Ok(number) = last.parse::<i64>() {
+			if let Some(deck) = get_deck(player.discord_id, player.economy, String::from(name)).await {
+				return Ok((Some(deck), Some(number)));
+			}
+		}
+	}
+
+	Ok((get_deck(player.discord_id, player.economy, deck_name).await, None))
+}
+
+async fn render_deal(cards: &[(Card, i64)], deal: &Deal) -> BotResult<Vec<u8>> {
+	let width = HAND_SIZE as u32 * (THUMBNAIL_WIDTH + GAP) + GAP;
+	let height = 2 * (LABEL_HEIGHT + THUMBNAIL_HEIGHT + GAP) + GAP;
+	let mut img = RgbaImage::from_pixel(width, height, BACKGROUND);
+	let rows = [("Hand", &deal.hand), ("Prizes", &deal.prizes)];
+	for (row, (label, idxs)) in rows.iter().enumerate() {
+		let y = GAP + row as u32 * (LABEL_HEIGHT + THUMBNAIL_HEIGHT + GAP);
+		draw_text_mut(&mut img, TEXT, GAP as i32, y as i32, Scale::uniform(22.0), &FONT, label);
+		let thumbnails = try_join_all(idxs.iter().map(|i| get_card_thumbnail_image(&cards[*i].0))).await?;
+		for (col, thumbnail) in thumbnails.iter().enumerate() {
+			let x = GAP + col as u32 * (THUMBNAIL_WIDTH + GAP);
+			overlay(&mut img, &thumbnail.to_rgba8(), x as i64, (y + LABEL_HEIGHT) as i64);
+		}
+	}
+
+	let mut buf: Vec<u8> = vec![];
+	DynamicImage::ImageRgba8(img).write_to(&mut Cursor::new(&mut buf), ImageOutputFormat::Png)?;
+
+	Ok(buf)
+}
+
+fn card_names(cards: &[(Card, i64)], idxs: &[usize]) -> String {
+	idxs
+		.iter()
+		.map(|i| cards[*i].0.name.clone())
+		.collect::<Vec<String>>()
+		.join(", ")
+}
+
+struct SimResult {
+	simulations: i64,
+	// Games that needed at least one mulligan
+	mulligan_games: i64,
+	mulligans: i64,
+	// Card name, copies, games with at least one copy prized and games with every copy prized
+	prized: Vec<(String, i64, i64, i64)>
+}
+
+fn simulate(cards: &[(Card, i64)], simulations: i64) -> SimResult {
+	let pool = card_pool(cards);
+	// Copies are counted by name, so every printing of a card is the same key card
+	let mut names: Vec<String> = vec![];
+	let mut copies: Vec<i64> = vec![];
+	let name_idxs = cards
+		.iter()
+		.map(|(card, amount)| {
+			let idx = match names.iter().position(|n| *n == card.name) {
+				Some(x) => x,
+				None => {
+					names.push(card.name.clone());
+					copies.push(0);
+					names.len() - 1
+				}
+			};
+			copies[idx] += amount;
+
+			idx
+		})
+		.collect::<Vec<usize>>();
+	let mut any_prized = vec![0; names.len()];
+	let mut all_prized = vec![0; names.len()];
+	let mut mulligan_games = 0;
+	let mut mulligans = 0;
+	for _ in 0..simulations {
+		let deal = deal(cards, &pool);
+		if deal.mulligans > 0 {
+			mulligan_games += 1;
+			mulligans += deal.mulligans;
+		}
+		let mut prized = vec![0; names.len()];
+		for i in &deal.prizes {
+			prized[name_idxs[*i]] += 1;
+		}
+		for (idx, amount) in prized.iter().enumerate() {
+			if *amount > 0 {
+				any_prized[idx] += 1;
+			}
+			if *amount > 0 && *amount == copies[idx] {
+				all_prized[idx] += 1;
+			}
+		}
+	}
+	let basic_energy = cards
+		.iter()
+		.filter(|(c, _)| is_basic_energy(c))
+		.map(|(c, _)| c.name.as_str())
+		.collect::<Vec<&str>>();
+	let mut prized = names
+		.iter()
+		.enumerate()
+		.filter(|(_, name)| !basic_energy.contains(&name.as_str()))
+		.map(|(i, name)| (name.clone(), copies[i], any_prized[i], all_prized[i]))
+		.collect::<Vec<(String, i64, i64, i64)>>();
+	prized.sort_by(|a, b| (b.3, b.2).cmp(&(a.3, a.2)));
+	prized.truncate(KEY_CARDS);
+
+	SimResult {
+		simulations,
+		mulligan_games,
+		mulligans,
+		prized
+	}
+}
+
+fn percent(count: i64, total: i64) -> String {
+	format!("{:.1}%", count as f64 / total as f64 * 100.0)
+}
+
+#[command("draw")]
+#[aliases("dr")]
+#[description("Shuffles a deck and shows an opening hand and prize cards")]
+#[usage("<name>")]
+#[example("blastoise")]
+#[min_args(1)]
+async fn deck_draw(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+	let deck_name = args.rest().to_lowercase();
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let deck = match get_deck(player.discord_id, player.economy, deck_name).await {
+		Some(x) => x,
+		None => {
+			msg.reply(&ctx.http, "You don't have a deck with that name.").await?;
+			return Ok(());
+		}
+	};
+	let cards = with_amounts(deck.get_cards(ctx).await, &deck.cards);
+	let pool = card_pool(&cards);
+	if (pool.len() as i64) < HAND_SIZE + PRIZES {
+		msg.reply(&ctx.http, format!("A deck needs at least **{}** cards to draw a hand and prizes.", HAND_SIZE + PRIZES)).await?;
+		return Ok(());
+	}
+	let deal = deal(&cards, &pool);
+	let image = render_deal(&cards, &deal).await?;
+	let mut desc = format!("**Hand:** {}\n**Prizes:** {}", card_names(&cards, &deal.hand), card_names(&cards, &deal.prizes));
+	match deal.mulligans {
+		0 => (),
+		1 => desc.push_str("\n\nMulliganed **1** time"),
+		x => desc.push_str(&format!("\n\nMulliganed **{}** times", x))
+	}
+	if !deal.hand.iter().any(|i| is_basic_pokemon(&cards[*i].0)) {
+		desc.push_str("\n\nThis deck doesn't have any Basic Pokémon, so it can't start a game");
+	}
+	msg
+		.channel_id
+		.send_message(&ctx.http, |m| {
+			m.add_file(AttachmentType::Bytes {
+				data: Cow::from(image.as_slice()),
+				filename: String::from("hand.png")
+			});
+			m.embed(|e| {
+				e
+					.title(format!("{} - Opening hand", deck.name))
+					.description(&desc)
+					.image("attachment://hand.png")
+					.colour(Colour::from_rgb(255, 50, 20))
+			})
+		})
+		.await?;
+
+	Ok(())
+}
+
+#[command("sim")]
+#[aliases("goldfish", "gf")]
+#[description("Deals a deck's opening hand and prizes many times to see how often it mulligans and prizes its key cards")]
+#[usage("<name> [games - Default: 1000]")]
+#[example("blastoise 5000")]
+#[min_args(1)]
+async fn deck_sim(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+	let (deck, simulations) = get_deck_and_number(msg, &args).await?;
+	let deck = match deck {
+		Some(x) => x,
+		None => {
+			msg.reply(&ctx.http, "You don't have a deck with that name.").await?;
+			return Ok(());
+		}
+	};
+	let simulations = simulations.unwrap_or(DEFAULT_SIMULATIONS);
+	if simulations < 1 || simulations > MAX_SIMULATIONS {
+		msg.reply(&ctx.http, format!("You can simulate between **1** and **{}** games.", MAX_SIMULATIONS)).await?;
+		return Ok(());
+	}
+	let cards = with_amounts(deck.get_cards(ctx).await, &deck.cards);
+	if (card_pool(&cards).len() as i64) < HAND_SIZE + PRIZES {
+		msg.reply(&ctx.http, format!("A deck needs at least **{}** cards to draw a hand and prizes.", HAND_SIZE + PRIZES)).await?;
+		return Ok(());
+	}
+	if !cards.iter().any(|(c, _)| is_basic_pokemon(c)) {
+		msg.reply(&ctx.http, "This deck doesn't have any Basic Pokémon, so it would mulligan every game.").await?;
+		return Ok(());
+	}
+	let result = simulate(&cards, simulations);
+	let mulligans = format!(
+		"**Games with a mulligan:** {}\n**Mulligans per game:** {:.2}",
+		percent(result.mulligan_games, result.simulations),
+		result.mulligans as f64 / result.simulations as f64
+	);
+	let mut prized = String::new();
+	for (name, copies, any, all) in &result.prized {
+		let line = match copies {
+			1 => format!("**{}** (1): prized {}\n", name, percent(*any, result.simulations)),
+			_ => format!("**{}** ({}): 1+ prized {}, all prized {}\n", name, copies, percent(*any, result.simulations), percent(*all, result.simulations))
+		};
+		// Discord's limit for an embed field
+		if prized.chars().count() + line.chars().count() > 1024 {
+			break;
+		}
+		prized.push_str(&line);
+	}
+	msg
+		.channel_id
+		.send_message(&ctx.http, |m| {
+			m.embed(|e| {
+				e
+					.title(format!("{} - {} simulated games", deck.name, result.simulations))
+					.field("Mulligans", mulligans, false)
+					.field("Prized cards", prized, false)
+					.colour(Colour::from_rgb(255, 50, 20))
+			})
+		})
+		.await?;
+
+	Ok(())
+}
@@ -0,0 +1,162 @@
+use serde::{Serialize, Deserialize};
+use mongodb::{
+	bson::{
+		doc,
+		oid::ObjectId,
+		Document
+	},
+	Collection
+};
+use serenity::{
+	framework::{
+		standard::{
+			macros::{
+				command
+			},
+			Args,
+			CommandResult
+		},
+	},
+	model::{
+		channel::{
+			Message,
+		},
+		id::GuildId,
+	},
+	prelude::*
+};
+
+use crate::{
+	commands::get_client,
+	error::BotResult
+};
+
+use super::timers;
+
+// The economy id every guild shares unless it opts into running isolated
+pub const GLOBAL_ECONOMY: i64 = 0;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GuildSettings {
+	#[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+	id: Option<ObjectId>,
+	pub guild_id: i64,
+	pub isolated: bool
+}
+
+impl GuildSettings {
+	fn new(guild_id: i64) -> Self {
+		Self {
+			id: None,
+			guild_id,
+			isolated: false
+		}
+	}
+
+	pub fn economy(&self) -> i64 {
+		match self.isolated {
+			true => self.guild_id,
+			false => GLOBAL_ECONOMY
+		}
+	}
+}
+
+async fn get_guild_settings_collection() -> BotResult<Collection<GuildSettings>> {
+	let client = get_client().await?;
+	let collection = client.database("poketcg").collection::<GuildSettings>("guilds");
+
+	Ok(collection)
+}
+
+pub async fn get_guild_settings(guild_id: i64) -> BotResult<GuildSettings> {
+	match find_guild_settings(guild_id).await? {
+		Some(x) => Ok(x),
+		None => add_guild_settings(guild_id).await
+	}
+}
+
+/// The guild's settings, without adding them if the guild has never used the bot.
+pub async fn find_guild_settings(guild_id: i64) -> BotResult<Option<GuildSettings>> {
+	let guild_settings_collection = get_guild_settings_collection().await?;
+	let guild_settings = guild_settings_collection
+		.find_one(doc! { "guild_id": guild_id }, None)
+		.await?;
+
+	Ok(guild_settings)
+}
+
+async fn add_guild_settings(guild_id: i64) -> BotResult<GuildSettings> {
+	let mut ret = GuildSettings::new(guild_id);
+	let guild_settings_collection = get_guild_settings_collection().await?;
+	let result = guild_settings_collection
+		.insert_one(&ret, None)
+		.await?;
+	ret.id = result.inserted_id.as_object_id();
+
+	Ok(ret)
+}
+
+async fn update_guild_settings(guild_settings: &GuildSettings) -> BotResult<()> {
+	let guild_settings_collection = get_guild_settings_collection().await?;
+	guild_settings_collection
+		.update_one(
+			doc! { "guild_id": guild_settings.guild_id },
+			doc! { "$set": { "isolated": guild_settings.isolated } },
+			None)
+		.await?;
+
+	Ok(())
+}
+
+/// Resolves which economy a message belongs to. DMs always use the global economy.
+pub async fn get_economy(guild_id: Option<GuildId>) -> BotResult<i64> {
+	match guild_id {
+		Some(g) => Ok(get_guild_settings(g.0 as i64).await?.economy()),
+		None => Ok(GLOBAL_ECONOMY)
+	}
+}
+
+/// The filter matching documents in an economy. Documents from before economies
+/// existed don't have the field, so they belong to the global one.
+pub fn economy_filter(economy: i64) -> Document {
+	match economy {
+		GLOBAL_ECONOMY => doc! { "economy": { "$in": [GLOBAL_ECONOMY, null] } },
+		_ => doc! { "economy": economy }
+	}
+}
+
+#[command("economy")]
+#[aliases("eco")]
+#[only_in(guilds)]
+#[required_permissions("MANAGE_GUILD")]
+#[description("Shows or changes whether this server uses the global economy or its own")]
+#[usage("[global | isolated]")]
+#[example("isolated")]
+async fn economy_command(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+	let mut guild_settings = get_guild_settings(msg.guild_id.unwrap().0 as i64).await?;
+	let mode = match args.find::<String>() {
+		Ok(x) => x.to_lowercase(),
+		Err(_) => String::from("")
+	};
+	match mode.as_str() {
+		"global" => guild_settings.isolated = false,
+		"isolated" | "local" => guild_settings.isolated = true,
+		_ => {
+			let current = match guild_settings.isolated {
+				true => "isolated",
+				false => "global"
+			};
+			msg.reply(&ctx.http, format!("This server uses the **{}** economy.\nUse **.economy <global | isolated>** to change it.", current)).await?;
+			return Ok(());
+		}
+	}
+	update_guild_settings(&guild_settings).await?;
+	// The economy's timer has to exist for its dailies to be reset
+	timers::get_timer(guild_settings.economy()).await?;
+	match guild_settings.isolated {
+		true => msg.reply(&ctx.http, "This server now runs its own economy. Players, the store, the token shop and timers are separate from other servers.").await?,
+		false => msg.reply(&ctx.http, "This server now shares the global economy.").await?
+	};
+
+	Ok(())
+}
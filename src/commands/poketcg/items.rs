@@ -0,0 +1,319 @@
+use mongodb::bson::{
+	doc,
+	Document
+};
+use serenity::{
+	framework::{
+		standard::{
+			macros::{
+				command
+			},
+			Args,
+			CommandResult
+		},
+	},
+	model::{
+		channel::{
+			Message,
+		},
+	},
+	utils::{
+		Colour
+	},
+	prelude::*
+};
+
+use crate::error::Error;
+
+use super::{
+	config::{
+		self,
+		ItemPrice
+	},
+	player::{
+		Player,
+		get_player,
+		update_player
+	},
+	sets::get_set,
+	Idable
+};
+
+pub enum ItemUse {
+	// Kept in the inventory and used up automatically, like the streak freeze
+	Passive,
+	// Applies as soon as it's used
+	Instant,
+	// Becomes active and applies to the player's next quiz or pack
+	Active,
+}
+
+pub struct ItemDef {
+	pub key: &'static str,
+	pub name: &'static str,
+	pub aliases: &'static [&'static str],
+	pub description: &'static str,
+	pub cash: Option<f64>,
+	pub tokens: Option<i64>,
+	pub usage: ItemUse
+}
+
+impl ItemDef {
+	/// The item's prices, from the economy config if it overrides this item.
+	pub fn price(&self) -> ItemPrice {
+		match config::get().item_price(self.key) {
+			Some(x) => x.clone(),
+			None => ItemPrice {
+				cash: self.cash,
+				tokens: self.tokens
+			}
+		}
+	}
+
+	fn matches(&self, name: &str) -> bool {
+		let name = name.to_lowercase();
+
+		self.key == name || self.name.to_lowercase() == name || self.aliases.contains(&name.as_str())
+	}
+}
+
+pub const ITEMS: &'static [ItemDef] = &[
+	ItemDef {
+		key: "streak_freeze",
+		name: "Streak Freeze",
+		aliases: &["freeze", "sf"],
+		description: "Saves your daily streak once if you miss a daily",
+		cash: Some(500.0),
+		tokens: Some(2500),
+		usage: ItemUse::Passive
+	},
+	ItemDef {
+		key: "quiz_hint",
+		name: "Quiz Hint",
+		aliases: &["hint", "qh"],
+		description: "Your next quiz tells you the first letter and generation",
+		cash: Some(25.0),
+		tokens: Some(150),
+		usage: ItemUse::Active
+	},
+	ItemDef {
+		key: "slot_roll",
+		name: "Extra Slot Roll",
+		aliases: &["roll", "slot", "sr"],
+		description: "One more roll on the slot machine today",
+		cash: Some(50.0),
+		tokens: None,
+		usage: ItemUse::Instant
+	},
+	ItemDef {
+		key: "pack_voucher",
+		name: "Pack Voucher",
+		aliases: &["voucher", "pv"],
+		description: "Trade it in for a pack from any set",
+		cash: Some(150.0),
+		tokens: Some(1500),
+		usage: ItemUse::Instant
+	},
+	ItemDef {
+		key: "lucky_charm",
+		name: "Lucky Charm",
+		aliases: &["charm", "lucky", "lc"],
+		description: "The next pack you open is much more likely to have a rarer card",
+		cash: Some(1000.0),
+		tokens: Some(5000),
+		usage: ItemUse::Active
+	},
+];
+
+pub fn find_item(name: &str) -> Option<&'static ItemDef> {
+	ITEMS.iter().find(|i| i.matches(name))
+}
+
+pub fn item_name(key: &str) -> &'static str {
+	match find_item(key) {
+		Some(x) => x.name,
+		None => "Unknown Item"
+	}
+}
+
+/// Adds items to the player's inventory.
+pub fn give_item(player: &mut Player, item: &ItemDef, amount: i64) {
+	*player.items.entry(String::from(item.key)).or_insert(0) += amount;
+}
+
+fn take_item(player: &mut Player, item: &ItemDef) -> bool {
+	match player.items.get_mut(item.key) {
+		Some(amt) if *amt > 0 => {
+			*amt -= 1;
+			player.items.retain(|_, v| *v > 0);
+			true
+		},
+		_ => false
+	}
+}
+
+#[command("items")]
+#[aliases("item", "inventory", "inv")]
+#[sub_commands(items_buy)]
+#[description("Shows your items and the ones for sale")]
+async fn items_main(ctx: &Context, msg: &Message) -> CommandResult {
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let mut desc = String::from("**Your items:**\n");
+	if player.items.len() == 0 {
+		desc.push_str("None!\n");
+	}
+	for item in ITEMS {
+		if let Some(amt) = player.items.get(item.key) {
+			desc.push_str(&format!("**{}** x{}\n", item.name, amt));
+		}
+	}
+	if player.active_items.len() > 0 {
+		let active = player.active_items
+			.iter()
+			.map(|i| item_name(i))
+			.collect::<Vec<&str>>()
+			.join(", ");
+		desc.push_str(&format!("**Active:** {}\n", active));
+	}
+	desc.push_str(&format!("\nYou have **${:.2}**. To buy an item use **.items buy <name> [amount]**, to use one use **.use <name>**\n\n", player.cash));
+	for item in ITEMS {
+		match item.price().cash {
+			Some(cash) => desc.push_str(&format!("**{}** _({})_: {} - ${:.2}\n", item.name, item.aliases[0], item.description, cash)),
+			None => desc.push_str(&format!("**{}** _({})_: {} - _Not for sale_\n", item.name, item.aliases[0], item.description))
+		}
+	}
+	msg
+		.channel_id
+		.send_message(&ctx.http, |m| {
+			m.embed(|e| {
+				e
+					.title("Items")
+					.description(&desc)
+					.colour(Colour::from_rgb(255, 50, 20))
+			})
+		})
+		.await?;
+
+	Ok(())
+}
+
+#[command("buy")]
+#[aliases("b")]
+#[description("Buys items with cash")]
+#[usage("<item> [amount - Default: 1]")]
+#[example("freeze 2")]
+#[min_args(1)]
+async fn items_buy(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+	let item = match args.single::<String>() {
+		Ok(x) => find_item(&x),
+		Err(_) => None
+	};
+	let item = match item {
+		Some(x) => x,
+		None => {
+			msg.reply(&ctx.http, "That's not an item. Use **.items** to see what's for sale").await?;
+			return Ok(());
+		}
+	};
+	let price = match item.price().cash {
+		Some(x) => x,
+		None => {
+			msg.reply(&ctx.http, format!("**{}** can't be bought with cash", item.name)).await?;
+			return Ok(());
+		}
+	};
+	let amount = match args.single::<i64>() {
+		Ok(x) if x > 0 => x,
+		_ => 1
+	};
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
+	if player.cash < price {
+		return Err(Error::InsufficientFunds(format!("${:.2}", price - player.cash)).into());
+	}
+	// Either the most they can afford or the amount they wanted
+	let amount = amount.min((player.cash / price) as i64);
+	player.cash -= price * amount as f64;
+	give_item(&mut player, item, amount);
+	let mut update = Document::new();
+	update.insert("cash", player.cash);
+	update.insert("items", player.items_doc());
+	update_player(&player, doc! { "$set": update }).await?;
+	msg.reply(&ctx.http, format!("You bought {} **{}**", amount, item.name)).await?;
+
+	Ok(())
+}
+
+#[command("use")]
+#[description("Uses one of your items")]
+#[usage("<item> [set id]")]
+#[example("charm")]
+#[example("voucher swsh1")]
+#[min_args(1)]
+async fn use_command(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+	let item = match args.single::<String>() {
+		Ok(x) => find_item(&x),
+		Err(_) => None
+	};
+	let item = match item {
+		Some(x) => x,
+		None => {
+			msg.reply(&ctx.http, "That's not an item. Use **.items** to see your items").await?;
+			return Ok(());
+		}
+	};
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
+	if *player.items.get(item.key).unwrap_or(&0) <= 0 {
+		msg.reply(&ctx.http, format!("You don't have a **{}**", item.name)).await?;
+		return Ok(());
+	}
+	let mut update = Document::new();
+	let content = match item.usage {
+		ItemUse::Passive => {
+			msg.reply(&ctx.http, format!("**{}** is used automatically when you need it", item.name)).await?;
+			return Ok(());
+		},
+		ItemUse::Active => {
+			if player.active_items.contains(&String::from(item.key)) {
+				msg.reply(&ctx.http, format!("You already have a **{}** active", item.name)).await?;
+				return Ok(());
+			}
+			player.active_items.push(String::from(item.key));
+			update.insert("active_items", player.active_items.clone());
+			format!("Your **{}** is active!", item.name)
+		},
+		ItemUse::Instant => match item.key {
+			"slot_roll" => {
+				player.daily_slots += 1;
+				update.insert("daily_slots", player.daily_slots);
+				format!("You have **{}** slot rolls", player.daily_slots)
+			},
+			"pack_voucher" => {
+				let set_id = match args.single::<String>() {
+					Ok(x) => x,
+					Err(_) => String::from("")
+				};
+				let set = match get_set(&set_id).await {
+					Ok(x) => x,
+					Err(_) => {
+						msg.reply(&ctx.http, "Choose which set you want a pack from with **.use voucher <set id>**").await?;
+						return Ok(());
+					}
+				};
+				*player.packs.entry(set.id()).or_insert(0) += 1;
+				let mut player_packs = Document::new();
+				for (pck, amt) in player.packs.iter() {
+					player_packs.insert(pck, amt);
+				}
+				update.insert("packs", player_packs);
+				format!("You traded your voucher for a **{}** pack", set.name)
+			},
+			_ => String::from("")
+		}
+	};
+	take_item(&mut player, item);
+	update.insert("items", player.items_doc());
+	update_player(&player, doc! { "$set": update }).await?;
+	msg.reply(&ctx.http, content).await?;
+
+	Ok(())
+}
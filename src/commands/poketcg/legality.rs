@@ -0,0 +1,440 @@
+use std::collections::{
+	BTreeSet,
+	HashMap,
+	HashSet
+};
+use serenity::{
+	framework::{
+		standard::{
+			macros::{
+				command
+			},
+			Args,
+			CommandResult
+		},
+	},
+	model::{
+		channel::{
+			Message,
+		},
+	},
+	utils::{
+		Colour
+	},
+	prelude::*
+};
+
+use super::{
+	card::Card,
+	decks::get_deck,
+	player::get_player
+};
+
+// The regulation marks that are in Standard. Update these when the format rotates
+const STANDARD_REGULATION_MARKS: &'static [&str] = &["H", "I", "J"];
+
+// Subtypes of Pokémon with a Rule Box, which aren't allowed in GLC
+const RULE_BOX_SUBTYPES: &'static [&str] = &["EX", "GX", "V", "VMAX", "VSTAR", "V-UNION", "ex", "TAG TEAM", "MEGA", "LEGEND", "Radiant", "Prism Star"];
+
+static DECK_SIZE: i64 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+	Standard,
+	Expanded,
+	Unlimited,
+	Glc
+}
+
+pub const FORMATS: &'static [Format] = &[Format::Standard, Format::Expanded, Format::Unlimited, Format::Glc];
+
+impl Format {
+	pub fn name(&self) -> &'static str {
+		match self {
+			Format::Standard => "Standard",
+			Format::Expanded => "Expanded",
+			Format::Unlimited => "Unlimited",
+			Format::Glc => "GLC"
+		}
+	}
+
+	pub fn from_name(name: &str) -> Option<Self> {
+		match name.to_lowercase().as_str() {
+			"standard" | "std" => Some(Format::Standard),
+			"expanded" | "exp" => Some(Format::Expanded),
+			"unlimited" | "unl" => Some(Format::Unlimited),
+			"glc" | "gymleaderchallenge" => Some(Format::Glc),
+			_ => None
+		}
+	}
+
+	fn max_copies(&self) -> i64 {
+		match self {
+			Format::Glc => 1,
+			_ => 4
+		}
+	}
+
+	pub fn is_legal(&self, card: &Card) -> bool {
+		self.card_legality(card).is_none()
+	}
+
+	/// Why the card can't be played in the format, if it can't.
+	fn card_legality(&self, card: &Card) -> Option<&'static str> {
+		// Any printing of a basic energy can be played anywhere
+		if is_basic_energy(card) {
+			return None;
+		}
+		// GLC uses the Expanded card pool
+		let key = match self {
+			Format::Standard => "standard",
+			Format::Expanded | Format::Glc => "expanded",
+			Format::Unlimited => "unlimited"
+		};
+		// The API's legality wins, the regulation marks only cover new cards it doesn't list yet
+		match card.legalities.get(key).map(|l| l.as_str()) {
+			Some("Banned") => return Some("is banned"),
+			Some("Legal") => (),
+			None if *self == Format::Standard && STANDARD_REGULATION_MARKS.contains(&card.regulation_mark.as_str()) => (),
+			None if *self == Format::Standard && card.regulation_mark != "" => return Some("has rotated out"),
+			_ => return Some("isn't legal")
+		}
+		if *self == Format::Glc && has_rule_box(card) {
+			return Some("has a Rule Box, which isn't allowed");
+		}
+		if *self == Format::Glc && is_ace_spec(card) {
+			return Some("is an ACE SPEC, which isn't allowed");
+		}
+
+		None
+	}
+}
+
+fn has_subtype(card: &Card, subtype: &str) -> bool {
+	card.subtypes.iter().any(|s| s == subtype)
+}
+
+pub fn is_basic_energy(card: &Card) -> bool {
+	card.supertype == "Energy" && has_subtype(card, "Basic")
+}
+
+pub fn is_basic_pokemon(card: &Card) -> bool {
+	card.supertype == "Pokémon" && has_subtype(card, "Basic")
+}
+
+fn is_ace_spec(card: &Card) -> bool {
+	has_subtype(card, "ACE SPEC")
+}
+
+fn is_radiant(card: &Card) -> bool {
+	has_subtype(card, "Radiant") || card.name.starts_with("Radiant ")
+}
+
+fn is_prism_star(card: &Card) -> bool {
+	has_subtype(card, "Prism Star") || card.name.contains('◇')
+}
+
+fn has_rule_box(card: &Card) -> bool {
+	card.supertype == "Pokémon" && RULE_BOX_SUBTYPES.iter().any(|s| has_subtype(card, s))
+}
+
+/// Pairs each card with how many of it the deck has. A card that's in `cards` twice is only counted once.
+pub fn with_amounts(cards: Vec<Card>, amounts: &HashMap<String, i64>) -> Vec<(Card, i64)> {
+	let mut seen = HashSet::new();
+	cards
+		.into_iter()
+		.filter(|c| seen.insert(c.card_id.clone()))
+		.map(|c| {
+			let amount = *amounts.get(&c.card_id).unwrap_or(&0);
+			(c, amount)
+		})
+		.collect()
+}
+
+// Copies are counted by name, so every printing of a card counts towards the same limit
+fn copy_violations(cards: &[(Card, i64)], max_copies: i64) -> Vec<String> {
+	let mut ret = vec![];
+	let mut by_name: HashMap<&str, i64> = HashMap::new();
+	for (card, amount) in cards.iter().filter(|(c, _)| !is_basic_energy(c)) {
+		*by_name.entry(&card.name).or_insert(0) += amount;
+	}
+	let mut names = by_name.keys().collect::<Vec<&&str>>();
+	names.sort();
+	for name in names {
+		let amount = by_name[*name];
+		let is_prism = cards.iter().any(|(c, _)| c.name == **name && is_prism_star(c));
+		if is_prism && amount > 1 {
+			ret.push(format!("**{}** copies of **{}**, a Prism Star card can only have 1", amount, name));
+		} else if amount > max_copies {
+			ret.push(format!("**{}** copies of **{}**, the most you can have is {}", amount, name, max_copies));
+		}
+	}
+	let ace_specs = cards.iter().filter(|(c, _)| is_ace_spec(c)).map(|(_, a)| a).sum::<i64>();
+	if ace_specs > 1 {
+		ret.push(format!("**{}** ACE SPEC cards, a deck can only have 1", ace_specs));
+	}
+	let radiants = cards.iter().filter(|(c, _)| is_radiant(c)).map(|(_, a)| a).sum::<i64>();
+	if radiants > 1 {
+		ret.push(format!("**{}** Radiant Pokémon, a deck can only have 1", radiants));
+	}
+
+	ret
+}
+
+/// What's wrong with a deck's cards while it's being built, so before it has 60 cards.
+/// These rules are the same for every format except GLC's singleton rule.
+pub fn addition_violations(cards: &[(Card, i64)]) -> Vec<String> {
+	let mut ret = vec![];
+	let total = cards.iter().map(|(_, a)| a).sum::<i64>();
+	if total > DECK_SIZE {
+		ret.push(format!("**{}** cards, a deck can only have {}", total, DECK_SIZE));
+	}
+	ret.extend(copy_violations(cards, 4));
+
+	ret
+}
+
+/// Everything that keeps a deck from being played in the format. Empty if it's legal.
+pub fn violations(format: Format, cards: &[(Card, i64)]) -> Vec<String> {
+	let mut ret = vec![];
+	let total = cards.iter().map(|(_, a)| a).sum::<i64>();
+	if total != DECK_SIZE {
+		ret.push(format!("**{}** cards, a deck needs exactly {}", total, DECK_SIZE));
+	}
+	if !cards.iter().any(|(c, a)| *a > 0 && is_basic_pokemon(c)) {
+		ret.push(String::from("No Basic Pokémon, a deck needs at least 1"));
+	}
+	ret.extend(copy_violations(cards, format.max_copies()));
+	for (card, _) in cards {
+		if let Some(why) = format.card_legality(card) {
+			ret.push(format!("**{}** (_{}_) {} in {}", card.name, card.card_id, why, format.name()));
+		}
+	}
+	if format == Format::Glc {
+		let types = cards
+			.iter()
+			.filter(|(c, _)| c.supertype == "Pokémon")
+			.flat_map(|(c, _)| c.types.iter().map(|t| t.as_str()))
+			.collect::<BTreeSet<&str>>();
+		if types.len() > 1 {
+			let types = types.into_iter().collect::<Vec<&str>>().join(", ");
+			ret.push(format!("Pokémon of more than one type ({}), every Pokémon needs to share a type", types));
+		}
+	}
+
+	ret
+}
+
+/// The violations of a deck in every format.
+pub fn legality(cards: &[(Card, i64)]) -> Vec<(Format, Vec<String>)> {
+	FORMATS
+		.iter()
+		.map(|f| (*f, violations(*f, cards)))
+		.collect()
+}
+
+/// A line of badges showing which formats a deck is legal in, eg. "✅ Standard ❌ Expanded".
+pub fn badges(legality: &Vec<(Format, Vec<String>)>) -> String {
+	legality
+		.iter()
+		.map(|(f, v)| match v.len() {
+			0 => format!("✅ {}", f.name()),
+			_ => format!("❌ {}", f.name())
+		})
+		.collect::<Vec<String>>()
+		.join("  ")
+}
+
+#[command("legality")]
+#[aliases("legal", "l")]
+#[description("Shows which formats a deck can be played in and what's keeping it out of the others")]
+#[usage("<name> [standard | expanded | unlimited | glc - Default: all]")]
+#[example("blastoise expanded")]
+#[min_args(1)]
+async fn deck_legality(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+	let mut deck_name = args.rest().to_lowercase();
+	let mut format = None;
+	if let Some((name, last)) = deck_name.rsplit_once(' ') {
+		if let Some(x) = Format::from_name(last) {
+			format = Some(x);
+			deck_name = String::from(name);
+		}
+	}
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let deck = match get_deck(player.discord_id, player.economy, deck_name.clone()).await {
+		Some(x) => x,
+		None => {
+			msg.reply(&ctx.http, "You don't have a deck with that name.").await?;
+			return Ok(());
+		}
+	};
+	let legality = deck
+		.legality(ctx)
+		.await
+		.into_iter()
+		.filter(|(f, _)| format.is_none() || format == Some(*f))
+		.collect::<Vec<(Format, Vec<String>)>>();
+	let mut desc = format!("{}\n\n", badges(&legality));
+	for (format, violations) in &legality {
+		if violations.len() == 0 {
+			desc.push_str(&format!("**{}:** Legal\n\n", format.name()));
+			continue;
+		}
+		desc.push_str(&format!("**{}:**\n", format.name()));
+		for violation in violations {
+			desc.push_str(&format!("- {}\n", violation));
+		}
+		desc.push('\n');
+	}
+	// Discord's limit for an embed description
+	if let Some((i, _)) = desc.char_indices().nth(4096) {
+		desc.truncate(i);
+	}
+	msg
+		.channel_id
+		.send_message(&ctx.http, |m| {
+			m.embed(|e| {
+				e
+					.title(format!("{} - Legality", deck.name))
+					.description(&desc)
+					.colour(Colour::from_rgb(255, 50, 20))
+			})
+		})
+		.await?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json::json;
+
+	use super::*;
+
+	fn card(id: &str, name: &str, supertype: &str, subtypes: &[&str], regulation_mark: &str, legalities: serde_json::Value) -> Card {
+		Card::from_json(&json!({
+			"id": id,
+			"name": name,
+			"number": "1",
+			"supertype": supertype,
+			"subtypes": subtypes,
+			"types": if supertype == "Pokémon" { json!(["Lightning"]) } else { json!([]) },
+			"regulationMark": regulation_mark,
+			"legalities": legalities,
+			"images": { "large": "" },
+			"set": {
+				"id": "sv1",
+				"name": "Scarlet & Violet",
+				"series": "Scarlet & Violet",
+				"printedTotal": 198,
+				"total": 258,
+				"releaseDate": "2023/03/31",
+				"images": { "logo": "", "symbol": "" }
+			}
+		}))
+	}
+
+	fn everywhere() -> serde_json::Value {
+		json!({ "standard": "Legal", "expanded": "Legal", "unlimited": "Legal" })
+	}
+
+	fn pikachu(id: &str) -> Card {
+		card(id, "Pikachu", "Pokémon", &["Basic"], "G", everywhere())
+	}
+
+	fn energy() -> Card {
+		card("sve-4", "Basic Lightning Energy", "Energy", &["Basic"], "", json!({ "unlimited": "Legal" }))
+	}
+
+	fn trainer(id: &str, name: &str, subtypes: &[&str]) -> Card {
+		card(id, name, "Trainer", subtypes, "H", everywhere())
+	}
+
+	#[test]
+	fn a_full_deck_with_a_basic_is_legal() {
+		let cards = vec![(pikachu("sv1-1"), 4), (energy(), 56)];
+		for format in FORMATS.iter().filter(|f| **f != Format::Glc) {
+			assert!(violations(*format, &cards).is_empty(), "{:?}", violations(*format, &cards));
+		}
+	}
+
+	#[test]
+	fn needs_sixty_cards_and_a_basic() {
+		let v = violations(Format::Unlimited, &[(pikachu("sv1-1"), 4), (energy(), 50)]);
+		assert_eq!(v.len(), 1);
+		assert!(v[0].contains("exactly 60"));
+		let v = violations(Format::Unlimited, &[(energy(), 60)]);
+		assert_eq!(v, vec![String::from("No Basic Pokémon, a deck needs at least 1")]);
+	}
+
+	#[test]
+	fn copies_count_every_printing() {
+		let cards = vec![(pikachu("sv1-1"), 3), (pikachu("sv2-1"), 2), (energy(), 55)];
+		let v = violations(Format::Unlimited, &cards);
+		assert_eq!(v.len(), 1);
+		assert!(v[0].contains("**5** copies of **Pikachu**"));
+	}
+
+	#[test]
+	fn only_one_ace_spec_radiant_and_prism_star() {
+		let cards = vec![
+			(pikachu("sv1-1"), 4),
+			(trainer("sv1-2", "Prime Catcher", &["Item", "ACE SPEC"]), 1),
+			(trainer("sv1-3", "Master Ball", &["Item", "ACE SPEC"]), 1),
+			(card("sv1-4", "Radiant Greninja", "Pokémon", &["Basic", "Radiant"], "F", everywhere()), 1),
+			(card("sv1-5", "Radiant Charizard", "Pokémon", &["Basic", "Radiant"], "F", everywhere()), 1),
+			(card("sv1-6", "Cyrus ◇", "Trainer", &["Supporter", "Prism Star"], "", everywhere()), 2),
+			(energy(), 50)
+		];
+		let v = violations(Format::Unlimited, &cards);
+		assert!(v.iter().any(|x| x.contains("ACE SPEC")));
+		assert!(v.iter().any(|x| x.contains("Radiant")));
+		assert!(v.iter().any(|x| x.contains("Prism Star")));
+		assert_eq!(v.len(), 3, "{:?}", v);
+	}
+
+	#[test]
+	fn basic_energy_has_no_copy_limit() {
+		let cards = vec![(pikachu("sv1-1"), 1), (energy(), 59)];
+		assert!(violations(Format::Glc, &cards).is_empty());
+	}
+
+	#[test]
+	fn glc_is_singleton_without_rule_boxes_or_mixed_types() {
+		let mut water = card("sv1-7", "Squirtle", "Pokémon", &["Basic"], "G", everywhere());
+		water.types = vec![String::from("Water")];
+		let cards = vec![
+			(pikachu("sv1-1"), 2),
+			(card("sv1-8", "Pikachu ex", "Pokémon", &["Basic", "ex"], "G", everywhere()), 1),
+			(water, 1),
+			(energy(), 56)
+		];
+		let v = violations(Format::Glc, &cards);
+		assert!(v.iter().any(|x| x.contains("**2** copies of **Pikachu**")));
+		assert!(v.iter().any(|x| x.contains("Rule Box")));
+		assert!(v.iter().any(|x| x.contains("more than one type")));
+	}
+
+	#[test]
+	fn standard_trusts_the_api_before_regulation_marks() {
+		// Listed as Legal by the API even though G isn't a Standard mark
+		assert!(Format::Standard.is_legal(&pikachu("sv1-1")));
+		// Not listed yet, so the regulation mark decides
+		let new = card("sv9-1", "Pikachu", "Pokémon", &["Basic"], "I", json!({}));
+		assert!(Format::Standard.is_legal(&new));
+		let rotated = card("swsh1-1", "Pikachu", "Pokémon", &["Basic"], "D", json!({ "expanded": "Legal" }));
+		assert_eq!(Format::Standard.card_legality(&rotated), Some("has rotated out"));
+		let banned = card("sv1-9", "Pikachu", "Pokémon", &["Basic"], "H", json!({ "standard": "Banned" }));
+		assert_eq!(Format::Standard.card_legality(&banned), Some("is banned"));
+		let old = card("base1-58", "Pikachu", "Pokémon", &["Basic"], "", json!({ "unlimited": "Legal" }));
+		assert_eq!(Format::Standard.card_legality(&old), Some("isn't legal"));
+	}
+
+	#[test]
+	fn with_amounts_counts_each_card_once() {
+		let amounts = HashMap::from([(String::from("sv1-1"), 4)]);
+		let cards = with_amounts(vec![pikachu("sv1-1"), pikachu("sv1-1")], &amounts);
+		assert_eq!(cards.len(), 1);
+		assert_eq!(cards[0].1, 4);
+	}
+}
@@ -2,7 +2,6 @@ use chrono::{
 	Utc,
 	Duration,
 	DateTime,
-	Local,
 };
 use dotenv;
 use mongodb::{
@@ -11,7 +10,7 @@ use mongodb::{
 		Document
 	},
 };
-use std::{time::Duration as StdDuration, sync::Arc, collections::HashMap};
+use std::{time::Duration as StdDuration, sync::Arc};
 pub mod card;
 use card::SEARCH_CARD_COMMAND;
 pub mod sets;
@@ -29,6 +28,24 @@ pub mod quiz;
 pub mod binder;
 pub mod card_image;
 pub mod decks;
+pub mod decklist;
+pub mod deck_stats;
+pub mod goldfish;
+pub mod deck_image;
+pub mod autobuild;
+pub mod drafts;
+pub mod legality;
+pub mod guild;
+pub mod config;
+pub mod prestige;
+pub mod scheduler;
+pub mod settings;
+pub mod reminders;
+pub mod items;
+pub mod calendar;
+pub mod community;
+pub mod changelog;
+pub mod export;
 
 use serenity::{
 	framework::{
@@ -57,9 +74,20 @@ use serde_json;
 use rand::{
 	Rng
 };
-use crate::{BOTTEST_CHECK, Cache, CardCache};
+use crate::{
+	error::{
+		BotResult,
+		Error
+	},
+	logging,
+	BOTTEST_CHECK,
+	Cache,
+	CardCache
+};
+use config::ADMIN_RELOAD_CONFIG_COMMAND;
+use scheduler::ADMIN_JOBS_COMMAND;
 
-async fn api_call(endpoint: &str, params: Option<Vec<(&str, &str)>>) -> Option<serde_json::Value> {
+async fn api_call(endpoint: &str, params: Option<Vec<(&str, &str)>>) -> BotResult<serde_json::Value> {
 	dotenv::dotenv().ok();
 	let poketcg_key = dotenv::var("POKETCGAPIKEY").unwrap();
 	let client = reqwest::Client::new();
@@ -71,15 +99,22 @@ async fn api_call(endpoint: &str, params: Option<Vec<(&str, &str)>>) -> Option<s
 		Some(x) => req.query(&x),
 		None => req
 	};
-	let data: serde_json::Value = req
-		.send().await.unwrap()
-		.json().await.unwrap();
-	
-
-	if data.is_null() {
-		None
-	} else {
-		Some(data)
+	let data = match req.send().await {
+		Ok(resp) => resp.json::<serde_json::Value>().await,
+		Err(why) => Err(why)
+	};
+	#[cfg(feature = "metrics")]
+	crate::metrics::record_api_call(endpoint, matches!(&data, Ok(x) if !x.is_null()));
+	match data {
+		Ok(x) if !x.is_null() => Ok(x),
+		Ok(_) => {
+			error!(target: logging::CATALOG, "PokeTCG API returned nothing for {}", endpoint);
+			Err(Error::Api(format!("{} returned nothing", endpoint)))
+		},
+		Err(why) => {
+			error!(target: logging::CATALOG, "PokeTCG API request for {} failed: {}", endpoint, why);
+			Err(why.into())
+		}
 	}
 }
 
@@ -110,32 +145,9 @@ const RARITY_ORDER: &'static [&str] = &[
 	"Unknown",
 ];
 
-lazy_static! {
-	static ref RARITY_MAPPING: HashMap<&'static str, i64> = {
-		let mut m = HashMap::new();
-		m.insert("Rare", 75);
-		m.insert("Rare ACE", 10);
-		m.insert("Rare BREAK", 10);
-		m.insert("Rare Holo", 40);
-		m.insert("Rare Holo EX", 12);
-		m.insert("Rare Holo GX", 12);
-		m.insert("Rare Holo LV.X", 12);
-		m.insert("Rare Holo Star", 8);
-		m.insert("Rare Holo V", 15);
-		m.insert("Rare Holo VMAX", 10);
-		m.insert("Rare Prime", 10);
-		m.insert("Rare Prism Star", 10);
-		m.insert("Rare Rainbow", 5);
-		m.insert("Rare Secret", 1);
-		m.insert("Rare Shining", 20);
-		m.insert("Rare Shiny", 5);
-		m.insert("Rare Shiny GX", 2);
-		m.insert("Rare Ultra", 5);
-		m.insert("Amazing Rare", 15);
-		m.insert("LEGEND", 3);
-		
-		m
-	};
+/// A Discord timestamp, which each user sees in their own timezone. Doesn't render in embed footers.
+pub fn discord_timestamp(time: &DateTime<Utc>, style: char) -> String {
+	format!("<t:{}:{}>", time.timestamp(), style)
 }
 
 pub trait PaginateEmbed {
@@ -159,7 +171,7 @@ pub trait HasSet {
 
 #[async_trait]
 pub trait Scrollable {
-	async fn scroll_through(&self, ctx: &Context, msg: &Message) -> Result<(), String>;
+	async fn scroll_through(&self, ctx: &Context, msg: &Message) -> BotResult<()>;
 }
 
 enum SellMode {
@@ -169,10 +181,10 @@ enum SellMode {
 	BySet(String),
 }
 
-async fn binder_paginated_embeds(ctx: &Context, msg: &Message, player: player::Player, missing_only: bool) -> Result<(), String> {
+async fn binder_paginated_embeds(ctx: &Context, msg: &Message, player: player::Player, missing_only: bool) -> BotResult<()> {
 	let left_arrow = ReactionType::try_from("⬅️").expect("No left arrow");
 	let right_arrow = ReactionType::try_from("➡️").expect("No right arrow");
-	let set = sets::get_set(&player.current_binder.set).await.unwrap();
+	let set = sets::get_set(&player.current_binder.set).await?;
 	let mut set_cards = card::get_cards_by_set(ctx, &set).await;
 	set_cards.sort_by(|c1, c2| {
 		if c1.set().id() == c2.set().id() {
@@ -197,8 +209,8 @@ async fn binder_paginated_embeds(ctx: &Context, msg: &Message, player: player::P
 	let embeds = cards.iter().map(|e| e.embed()).collect::<Vec<_>>();
 	let mut idx: i16 = 0;
 	if !player.current_binder.cards.contains(&cards[idx as usize].card_id()) {
-		let card_img = card_image::get_card_image(&cards[idx as usize]).await;
-		let img = card_img.to_dyn_image();
+		let card_img = card_image::get_card_image(&cards[idx as usize]).await?;
+		let img = card_img.to_dyn_image()?;
 		img.save("gs.png").unwrap();
 	}
 	let mut message = msg
@@ -243,8 +255,8 @@ async fn binder_paginated_embeds(ctx: &Context, msg: &Message, player: player::P
 		}
 		let in_binder = player.current_binder.cards.contains(&cards[idx as usize].card_id());
 		if !in_binder {
-			let card_img = card_image::get_card_image(&cards[idx as usize]).await;
-			let img = card_img.to_dyn_image();
+			let card_img = card_image::get_card_image(&cards[idx as usize]).await?;
+			let img = card_img.to_dyn_image()?;
 			img.save("gs.png").unwrap();
 		}
 		message.edit(&ctx, |m| {
@@ -304,8 +316,9 @@ async fn get_set_average_price(ctx: &Context, set: &sets::Set) -> f64 {
 	if commons.len() > 0 && uncommons.len() > 0 && rares.len() > 0 {
 		let common_price: f64 = (commons.iter().map(|c| c.price).sum::<f64>() / commons.len() as f64) * 6.0;
 		let uncommon_price: f64 = (uncommons.iter().map(|c| c.price).sum::<f64>() / uncommons.len() as f64) * 3.0;
+		let economy_config = config::get();
 		let mut rare_prices = vec![];
-		for (rarity, weight) in RARITY_MAPPING.iter() {
+		for (rarity, weight) in economy_config.rarity_weights.iter() {
 			let current_rarity_cards = rares
 				.iter()
 				.filter(|c| &c.rarity.as_str() == rarity)
@@ -327,6 +340,7 @@ async fn get_set_average_price(ctx: &Context, set: &sets::Set) -> f64 {
 
 #[command("sell")]
 #[sub_commands(sell_card, sell_under, sell_dups, sell_all, sell_packs, sell_set)]
+#[description("Shows the sell commands")]
 async fn sell_main(ctx: &Context, msg: &Message) -> CommandResult {
 	let content = "Here are the available selling commands:
 	**.sell card <card id> [amount - Default: _1_]** to sell a specific card.
@@ -389,6 +403,7 @@ async fn sell_cards_helper(ctx: &Context, mut player: player::Player, mode: Sell
 		sold_cards.push(card_to_sell.clone());
 	}
 	player.cards.retain(|_, v| *v > 0);
+	let total_cash = total_cash * player.sell_mult();
 	player.cash += total_cash;
 	player.total_cash += total_cash;
 	player.cards_sold += total_sold;
@@ -407,6 +422,10 @@ async fn sell_cards_helper(ctx: &Context, mut player: player::Player, mode: Sell
 
 #[command("card")]
 #[aliases("c")]
+#[description("Sells a specific card")]
+#[usage("<card id> [amount - Default: 1]")]
+#[example("swsh1-25 2")]
+#[min_args(1)]
 async fn sell_card(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
 	let card_id = match args.find::<String>() {
 		Ok(x) => x,
@@ -421,7 +440,7 @@ async fn sell_card(ctx: &Context, msg: &Message, mut args: Args) -> CommandResul
 		Err(_) => 1
 	};
 	
-	let mut player = player::get_player(msg.author.id.0).await;
+	let mut player = player::get_player(msg.author.id.0, msg.guild_id).await?;
 	if player.cards.contains_key(&card_id) {
 		let amounts = vec![amount, *player.cards.get(&card_id).unwrap()];
 		let amount = *amounts.iter().min().unwrap();
@@ -432,8 +451,9 @@ async fn sell_card(ctx: &Context, msg: &Message, mut args: Args) -> CommandResul
 			player.cards.remove(&card_id);
 		}
 		player.cards_sold += amount;
-		player.cash += card.price * amount as f64;
-		player.total_cash += card.price * amount as f64;
+		let cash = card.price * amount as f64 * player.sell_mult();
+		player.cash += cash;
+		player.total_cash += cash;
 		update.insert("cards_sold", player.cards_sold);
 		update.insert("cash", player.cash);
 		update.insert("total_cash", player.total_cash);
@@ -442,8 +462,8 @@ async fn sell_card(ctx: &Context, msg: &Message, mut args: Args) -> CommandResul
 			player_cards.insert(crd, amt);
 		}
 		update.insert("cards", player_cards);
-		player::update_player(&player, doc! { "$set": update }).await;
-		msg.reply(&ctx.http, format!("You sold {} **{}** for ${:.2}", amount, card.name, card.price * amount as f64)).await?;
+		player::update_player(&player, doc! { "$set": update }).await?;
+		msg.reply(&ctx.http, format!("You sold {} **{}** for ${:.2}", amount, card.name, cash)).await?;
 	} else {
 		msg.reply(&ctx.http, "You don't have that card").await?;
 	}
@@ -453,6 +473,10 @@ async fn sell_card(ctx: &Context, msg: &Message, mut args: Args) -> CommandResul
 
 #[command("under")]
 #[aliases("u")]
+#[description("Sells all cards worth less than the value")]
+#[usage("[value - Default: 1.00] [rares - Default: false]")]
+#[example("0.50")]
+#[example("2 true")]
 async fn sell_under(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
 	let value = match args.find::<f64>() {
 		Ok(x) => x,
@@ -462,9 +486,9 @@ async fn sell_under(ctx: &Context, msg: &Message, mut args: Args) -> CommandResu
 		Ok(x) => x,
 		Err(_) => false
 	};
-	let player = player::get_player(msg.author.id.0).await;
+	let player = player::get_player(msg.author.id.0, msg.guild_id).await?;
 	let (_, total_sold, total_cash, player_update) = sell_cards_helper(ctx, player.clone(), SellMode::Under(value), rares).await;
-	player::update_player(&player, doc! { "$set": player_update }).await;
+	player::update_player(&player, doc! { "$set": player_update }).await?;
 	msg.reply(&ctx.http, format!("You sold **{}** cards for **${:.2}**", total_sold, total_cash)).await?;
 
 	Ok(())
@@ -472,14 +496,17 @@ async fn sell_under(ctx: &Context, msg: &Message, mut args: Args) -> CommandResu
 
 #[command("dups")]
 #[aliases("dup", "d")]
+#[description("Sells duplicate cards until 1 of each remains")]
+#[usage("[rares - Default: false]")]
+#[example("true")]
 async fn sell_dups(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
 	let rares = match args.find::<bool>() {
 		Ok(x) => x,
 		Err(_) => false
 	};
-	let player = player::get_player(msg.author.id.0).await;
+	let player = player::get_player(msg.author.id.0, msg.guild_id).await?;
 	let (_, total_sold, total_cash, player_update) = sell_cards_helper(ctx, player.clone(), SellMode::Duplicates, rares).await;
-	player::update_player(&player, doc! { "$set": player_update }).await;
+	player::update_player(&player, doc! { "$set": player_update }).await?;
 	msg.reply(&ctx.http, format!("You sold **{}** cards for **${:.2}**", total_sold, total_cash)).await?;
 
 	Ok(())
@@ -487,14 +514,17 @@ async fn sell_dups(ctx: &Context, msg: &Message, mut args: Args) -> CommandResul
 
 #[command("all")]
 #[aliases("a")]
+#[description("Sells all your cards")]
+#[usage("[rares - Default: false]")]
+#[example("true")]
 async fn sell_all(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
 	let rares = match args.find::<bool>() {
 		Ok(x) => x,
 		Err(_) => false
 	};
-	let player = player::get_player(msg.author.id.0).await;
+	let player = player::get_player(msg.author.id.0, msg.guild_id).await?;
 	let (_, total_sold, total_cash, player_update) = sell_cards_helper(ctx, player.clone(), SellMode::All, rares).await;
-	player::update_player(&player, doc! { "$set": player_update }).await;
+	player::update_player(&player, doc! { "$set": player_update }).await?;
 	msg.reply(&ctx.http, format!("You sold **{}** cards for **${:.2}**", total_sold, total_cash)).await?;
 
 	Ok(())
@@ -502,26 +532,23 @@ async fn sell_all(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult
 
 #[command("set")]
 #[aliases("s")]
+#[description("Sells all your cards from a set")]
+#[usage("<set id> [rares - Default: false]")]
+#[example("swsh1 true")]
+#[min_args(1)]
 async fn sell_set(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
 	let set_id = match args.find::<String>() {
 		Ok(x) => x,
 		Err(_) => String::from("")
 	};
-	let set = sets::get_set(&set_id).await;
-	match set {
-		Some(_) => (),
-		None => {
-			msg.reply(&ctx.http, "No set found with that id.").await?;
-		}
-	}
-	let set = set.unwrap();
+	let set = sets::get_set(&set_id).await?;
 	let rares = match args.find::<bool>() {
 		Ok(x) => x,
 		Err(_) => false
 	};
-	let player = player::get_player(msg.author.id.0).await;
+	let player = player::get_player(msg.author.id.0, msg.guild_id).await?;
 	let (_, total_sold, total_cash, player_update) = sell_cards_helper(ctx, player.clone(), SellMode::BySet(set.id()), rares).await;
-	player::update_player(&player, doc! { "$set": player_update }).await;
+	player::update_player(&player, doc! { "$set": player_update }).await?;
 	msg.reply(&ctx.http, format!("You sold **{}** cards for **${:.2}**", total_sold, total_cash)).await?;
 
 	Ok(())
@@ -529,6 +556,10 @@ async fn sell_set(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult
 
 #[command("packs")]
 #[aliases("pack", "p")]
+#[description("Sells packs from a set")]
+#[usage("<set id> [amount - Default: 1]")]
+#[example("swsh1 3")]
+#[min_args(1)]
 async fn sell_packs(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
 	let pack_id = match args.find::<String>() {
 		Ok(x) => x,
@@ -543,23 +574,24 @@ async fn sell_packs(ctx: &Context, msg: &Message, mut args: Args) -> CommandResu
 		Err(_) => 1
 	};
 	
-	let mut player = player::get_player(msg.author.id.0).await;
+	let mut player = player::get_player(msg.author.id.0, msg.guild_id).await?;
 	if player.packs.contains_key(&pack_id) {
 		let amounts = vec![amount, *player.packs.get(&pack_id).unwrap()];
 		let amount = *amounts.iter().min().unwrap();
-		let set = sets::get_set(&pack_id).await.unwrap();
+		let set = sets::get_set(&pack_id).await?;
 		let mut update = Document::new();
 		*player.packs.entry(pack_id.clone()).or_insert(0) -= amount;
 		player.packs.retain(|_, v| *v > 0);
-		player.cash += set.pack_price() * amount as f64;
+		let cash = set.pack_price() * amount as f64 * player.sell_mult();
+		player.cash += cash;
 		update.insert("cash", player.cash);
 		let mut player_packs = Document::new();
 		for (pck, amt) in player.packs.iter() {
 			player_packs.insert(pck, amt);
 		}
 		update.insert("packs", player_packs);
-		player::update_player(&player, doc! { "$set": update }).await;
-		msg.reply(&ctx.http, format!("You sold {} **{}** packs for ${:.2}", amount, set.name, set.pack_price() * amount as f64)).await?;
+		player::update_player(&player, doc! { "$set": update }).await?;
+		msg.reply(&ctx.http, format!("You sold {} **{}** packs for ${:.2}", amount, set.name, cash)).await?;
 	} else {
 		msg.reply(&ctx.http, "You don't have that card").await?;
 	}
@@ -569,6 +601,7 @@ async fn sell_packs(ctx: &Context, msg: &Message, mut args: Args) -> CommandResu
 
 #[command("search")]
 #[sub_commands(search_card, search_set)]
+#[description("Shows the search commands")]
 async fn search_main(ctx: &Context, msg: &Message) -> CommandResult {
 	let search_help_str = "Here are the available **search** commands:
 	**.search card:** Searches for a card with a matching name
@@ -584,8 +617,12 @@ async fn search_main(ctx: &Context, msg: &Message) -> CommandResult {
 
 #[command("openpack")]
 #[aliases("op")]
+#[description("Opens packs from a set")]
+#[usage("<set id> [amount - Default: 1]")]
+#[example("swsh1 5")]
+#[min_args(1)]
 async fn open_pack_command(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-	let mut player = player::get_player(msg.author.id.0).await;
+	let mut player = player::get_player(msg.author.id.0, msg.guild_id).await?;
 	if player.daily_packs <= 0 {
 		msg.reply(&ctx.http, "You're out of packs for today!").await?;
 		return Ok(());
@@ -605,8 +642,13 @@ async fn open_pack_command(ctx: &Context, msg: &Message, mut args: Args) -> Comm
 	if player.packs.contains_key(&set_id) {
 		let amounts = vec![player.daily_packs, amount, *player.packs.get(&set_id).unwrap()]; 
 		let amount = *amounts.iter().min().unwrap();
-		let pack = packs::Pack::from_set_id(ctx, &set_id, amount as usize).await?;
+		let lucky_packs = match player.take_active_item("lucky_charm") {
+			true => 1,
+			false => 0
+		};
+		let pack = packs::Pack::from_set_id_with_luck(ctx, &set_id, amount as usize, lucky_packs).await?;
 		let mut update = Document::new();
+		update.insert("active_items", player.active_items.clone());
 		player.total_cards += pack.cards.len() as i64;
 		player.packs_opened += amount;
 		player.daily_packs -= amount;
@@ -630,7 +672,9 @@ async fn open_pack_command(ctx: &Context, msg: &Message, mut args: Args) -> Comm
 			player_cards.insert(card_id, amt);
 		}
 		update.insert("cards", player_cards);
-		player::update_player(&player, doc! { "$set": update }).await;
+		player::update_player(&player, doc! { "$set": update }).await?;
+		#[cfg(feature = "metrics")]
+		crate::metrics::record_packs_opened(amount as u64);
 		pack.cards.scroll_through(ctx, msg).await?;
 	} else {
 		msg.reply(&ctx.http, "You don't have that pack").await?;
@@ -641,48 +685,59 @@ async fn open_pack_command(ctx: &Context, msg: &Message, mut args: Args) -> Comm
 
 #[command("daily")]
 #[aliases("d")]
+#[description("Claims your daily reward")]
 async fn daily_command(ctx: &Context, msg: &Message) -> CommandResult {
-	let mut player = player::get_player(msg.author.id.0).await;
+	let mut player = player::get_player(msg.author.id.0, msg.guild_id).await?;
 	let now = Utc::now();
 	if player.daily_reset >= now {
 		msg
 			.channel_id
 			.send_message(
-				&ctx.http, |m| {
-					let local_timer: DateTime<Local> = DateTime::from(player.daily_reset);
-				
-					m.content(format!("Your daily resets **{}**", local_timer.format("%h %d %H:%M")))
-				})
+				&ctx.http, |m| m.content(format!("Your daily resets **{}** ({})", discord_timestamp(&player.daily_reset, 'f'), discord_timestamp(&player.daily_reset, 'R'))))
 			.await
 			.unwrap();
 		return Ok(());
 	}
+	let economy_config = config::get();
 	let mut update = Document::new();
+	let reward;
 	let r: i64 = rand::thread_rng().gen_range(0..100);
-	let pack_reset = std::cmp::min(10, 2 + player.daily_streak);
+	let pack_reset = std::cmp::min(economy_config.daily.pack_chance_cap, economy_config.daily.pack_chance + player.daily_streak);
 	if r <= pack_reset {
-		let player_daily_packs = 50 + (player.upgrades.daily_pack_amount * 10);
+		let player_daily_packs = economy_config.packs.daily_packs + (player.upgrades.level("daily_pack_amount") * economy_config.packs.daily_packs_per_level);
 		player.daily_packs += player_daily_packs;
 		update.insert("daily_packs", player.daily_packs);
-		msg.reply(&ctx.http, format!("***WOAH!*** You got **{}** daily packs!", player_daily_packs)).await?;
+		reward = format!("***WOAH!*** You got **{}** daily packs!", player_daily_packs);
 	} else {
-		let player_mult = 1.0 + player.upgrades.daily_reward_mult as f64 * 0.1;
-		let min_cash = std::cmp::min(5 + player.daily_streak, 15) as i32;
-		let max_cash = std::cmp::min(20 + player.daily_streak, 40) as i32;
+		let player_mult = 1.0 + player.upgrades.level("daily_reward_mult") as f64 * economy_config.daily.reward_mult_per_level;
+		let min_cash = std::cmp::min(economy_config.daily.min_cash + player.daily_streak, economy_config.daily.min_cash_cap) as i32;
+		let max_cash = std::cmp::min(economy_config.daily.max_cash + player.daily_streak, economy_config.daily.max_cash_cap) as i32;
 		let cash: f64 = rand::thread_rng().gen_range(min_cash..=max_cash) as f64 * player_mult;
 		player.cash += cash;
 		player.total_cash += cash;
 		update.insert("cash", player.cash);
 		update.insert("total_cash", player.total_cash);
-		msg.reply(&ctx.http, format!("You got **${:.2}**", cash as f64)).await?;
+		reward = format!("You got **${:.2}**", cash as f64);
 	}
-	let hours_til_update = 24 - player.upgrades.daily_time_reset;
+	let hours_til_update = economy_config.daily.reset_hours - player.upgrades.level("daily_time_reset");
 	player.daily_reset = Utc::now() + Duration::hours(hours_til_update);
 	update.insert("daily_reset", player.daily_reset);
 	player.daily_streak += 1;
 	update.insert("daily_streak", player.daily_streak);
-	player::update_player(&player, doc!{ "$set": update }).await;
+	// The daily is still given if the calendar's reward can't be, that day's reward is skipped
+	let calendar_reward = match calendar::claim_calendar_day(ctx, &mut player, &mut update).await {
+		Ok(x) => x,
+		Err(why) => {
+			error!(target: logging::ECONOMY, "Couldn't give {} their calendar reward: {}", player.discord_id, why);
+			None
+		}
+	};
+	player::update_player(&player, doc!{ "$set": update }).await?;
+	msg.reply(&ctx.http, reward).await?;
 	msg.channel_id.say(&ctx.http, format!("Your daily streak is now **{}**", player.daily_streak)).await?;
+	if let Some(calendar_reward) = calendar_reward {
+		msg.channel_id.say(&ctx.http, calendar_reward).await?;
+	}
 
 	Ok(())
 }
@@ -690,6 +745,7 @@ async fn daily_command(ctx: &Context, msg: &Message) -> CommandResult {
 #[command("trade")]
 #[aliases("tr")]
 #[sub_commands(trade_with)]
+#[description("Shows how trading works")]
 async fn trade_main(ctx: &Context, msg: &Message) -> CommandResult {
 	let content = "Here are the available trading commands:
 		**.trade with <@player> <trade offer>** to trade with another player
@@ -715,22 +771,27 @@ async fn trade_main(ctx: &Context, msg: &Message) -> CommandResult {
 
 // ADMIN COMMANDS (FOR TESTING)
 #[command("admin")]
-#[sub_commands(admin_show_pack, admin_add_cash, admin_mock_slot, admin_add_tokens, admin_set_cards, admin_cache)]
+#[sub_commands(admin_show_pack, admin_add_cash, admin_mock_slot, admin_add_tokens, admin_set_cards, admin_cache, admin_reload_config, admin_jobs)]
 #[checks(BotTest)]
+#[description("Admin commands")]
 async fn admin_main() -> CommandResult {
 	Ok(())
 }
 
 #[command("pack")]
 #[checks(BotTest)]
+#[description("Opens a pack without using it up")]
+#[usage("<set id> [amount]")]
+#[example("swsh1")]
+#[min_args(1)]
 async fn admin_show_pack(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-	let _player = player::get_player(msg.author.id.0).await;
+	let _player = player::get_player(msg.author.id.0, msg.guild_id).await?;
 	let set_id = args.find::<String>().unwrap();
 	let amount = match args.find::<i32>() {
 		Ok(x) => x as usize,
 		Err(_) => 1usize
 	};
-	let pack = packs::Pack::from_set_id(ctx, set_id.as_str(), amount).await.unwrap();
+	let pack = packs::Pack::from_set_id(ctx, set_id.as_str(), amount).await?;
 	pack.cards.scroll_through(ctx, msg).await?;
 
 	Ok(())
@@ -738,8 +799,12 @@ async fn admin_show_pack(ctx: &Context, msg: &Message, mut args: Args) -> Comman
 
 #[command("cash")]
 #[checks(BotTest)]
+#[description("Gives you cash")]
+#[usage("<amount>")]
+#[example("100")]
+#[min_args(1)]
 async fn admin_add_cash(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-	let mut player_ = player::get_player(msg.author.id.0).await;
+	let mut player_ = player::get_player(msg.author.id.0, msg.guild_id).await?;
 	let amount = args.find::<f64>().expect("No amount to add");
 	let og_cash = player_.cash;
 	player_.cash += amount;
@@ -755,15 +820,19 @@ async fn admin_add_cash(ctx: &Context, msg: &Message, mut args: Args) -> Command
 			}
 		}
 	)
-		.await;
+		.await?;
 
 	Ok(())
 }
 
 #[command("tokens")]
 #[checks(BotTest)]
+#[description("Gives you tokens")]
+#[usage("<amount>")]
+#[example("1000")]
+#[min_args(1)]
 async fn admin_add_tokens(_ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-	let mut player = player::get_player(msg.author.id.0).await;
+	let mut player = player::get_player(msg.author.id.0, msg.guild_id).await?;
 	let amount = args.find::<i64>().expect("No amount to add");
 	player.tokens += amount;
 	player.total_tokens += amount;
@@ -776,15 +845,18 @@ async fn admin_add_tokens(_ctx: &Context, msg: &Message, mut args: Args) -> Comm
 			}
 		}
 	)
-		.await;
+		.await?;
 
 	Ok(())
 }
 
 #[command("slots")]
 #[checks(BotTest)]
+#[description("Rolls the slots without paying out")]
+#[usage("[amount]")]
+#[example("10")]
 async fn admin_mock_slot(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-	let player = player::get_player(msg.author.id.0).await;
+	let player = player::get_player(msg.author.id.0, msg.guild_id).await?;
 	if player.daily_slots <= 0 {
 		msg.reply(&ctx.http, "You're out of slot rolls for today!").await?;
 		return Ok(());
@@ -793,10 +865,10 @@ async fn admin_mock_slot(ctx: &Context, msg: &Message, mut args: Args) -> Comman
 		Ok(x) => x,
 		Err(_) => 1
 	};
-	let slots = slot::Slot::new(amount);
+	let slots = slot::Slot::new(amount, player.upgrades.level("rare_slot_luck"));
 	let mut roll_displays = vec![];
 	for roll in slots.rolls {
-		roll_displays.push(roll.reward_display(player.upgrades.slot_reward_mult));
+		roll_displays.push(roll.reward_display(player.upgrades.level("slot_reward_mult")));
 	}
 	let content = roll_displays.join("\n");
 
@@ -807,20 +879,17 @@ async fn admin_mock_slot(ctx: &Context, msg: &Message, mut args: Args) -> Comman
 
 #[command("set")]
 #[checks(BotTest)]
+#[description("Gives you every card from a set")]
+#[usage("<set id>")]
+#[example("swsh1")]
+#[min_args(1)]
 async fn admin_set_cards(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-	let mut player = player::get_player(msg.author.id.0).await;
+	let mut player = player::get_player(msg.author.id.0, msg.guild_id).await?;
 	let set_id = match args.find::<String>() {
 		Ok(x) => x,
 		Err(_) => String::from("")
 	};
-	let set = sets::get_set(&set_id).await;
-	match set {
-		Some(_) => (),
-		None => {
-			msg.reply(&ctx.http, "No set found with that id.").await?;
-		}
-	}
-	let set = set.unwrap();
+	let set = sets::get_set(&set_id).await?;
 	let cards = card::get_cards_by_set(ctx, &set).await;
 	for card in cards {
 		*player.cards.entry(card.card_id()).or_insert(0) += 1;
@@ -834,7 +903,7 @@ async fn admin_set_cards(ctx: &Context, msg: &Message, mut args: Args) -> Comman
 		player_cards.insert(crd, amt);
 	}
 	player_update.insert("cards", player_cards);
-	player::update_player(&player, doc! { "$set": player_update }).await;
+	player::update_player(&player, doc! { "$set": player_update }).await?;
 	msg.reply(&ctx.http, format!("Added all the cards for **{}**", set.name)).await?;
 
 	Ok(())
@@ -842,13 +911,14 @@ async fn admin_set_cards(ctx: &Context, msg: &Message, mut args: Args) -> Comman
 
 #[command("cache")]
 #[checks(BotTest)]
+#[description("Shows the card cache")]
 async fn admin_cache(ctx: &Context) -> CommandResult {
 	{
 		let cache_read = ctx.data.read().await;
 		let cache_lock = cache_read.get::<Cache>().expect("Expected Cache in TypeMap").clone();
 		let cache = cache_lock.read().await;
 
-		println!("{:?}", cache.iter());
+		info!(target: logging::CATALOG, "Card cache has {} cards: {:?}", cache.len(), cache.keys());
 
 	}
 
@@ -857,31 +927,34 @@ async fn admin_cache(ctx: &Context) -> CommandResult {
 
 // TASKS
 pub async fn refresh_dailys(_ctx: Arc<Context>) {
-	let timer = timers::get_timer().await;
+	if let Err(why) = refresh_all_dailys().await {
+		error!(target: logging::ECONOMY, "Couldn't reset dailies: {}", why);
+	}
+}
+
+async fn refresh_all_dailys() -> BotResult<()> {
+	// Makes sure the global timer exists before there's a command that uses it
+	timers::get_timer(guild::GLOBAL_ECONOMY).await?;
+	for timer in timers::get_timers().await? {
+		refresh_economy_dailys(&timer).await?;
+	}
+
+	Ok(())
+}
+
+async fn refresh_economy_dailys(timer: &timers::Timer) -> BotResult<()> {
 	if Utc::now() >= timer.pack_reset {
-		println!("Reseting dailys");
-		let players = player::get_players().await;
-		for mut player in players {
-			let mut update = Document::new();
-			let player_daily_packs = 50 + (player.upgrades.daily_pack_amount * 10);
-			let player_pack_limit = 500 + (player.upgrades.pack_limit * 100);
-			let player_daily_slots = 10 + player.upgrades.daily_slot_amount;
-			player.daily_packs += player_daily_packs;
-			if player.daily_packs > player_pack_limit {
-				player.daily_packs = player_pack_limit;
-			}
-			update.insert("daily_packs", player.daily_packs);
-			player.daily_slots = player_daily_slots;
-			update.insert("daily_slots", player.daily_slots);
-			player::update_player(&player, doc!{"$set": update }).await;
-		}
-		timers::update_timer(&timer).await;
+		let updated = player::reset_dailies_in(timer.economy).await?;
+		info!(target: logging::ECONOMY, "Reset dailies for {} players in economy {}", updated, timer.economy);
+		timers::update_timer(timer).await?;
 	}
+
+	Ok(())
 }
 
 pub async fn refresh_card_prices(ctx: Arc<Context>) {
 	let cached_cards = card::get_outdated_cards(&ctx).await;
-	println!("Updating {} outdated cards... ", &cached_cards.len());
+	info!(target: logging::CATALOG, "Updating {} outdated cards", &cached_cards.len());
 	let card_ids = cached_cards
 		.iter()
 		.map(|c| c.card.id())
@@ -895,35 +968,11 @@ pub async fn refresh_card_prices(ctx: Arc<Context>) {
 		updated_cards.push(cached_card);
 	}
 	card::update_cached_cards(&ctx, updated_cards).await;
-	// println!("Updated cached cards!");
 }
 
-pub async fn check_daily_streaks(ctx: Arc<Context>) {
-	println!("Checking daily streaks");
-	let players = player::get_players().await;
-	let now = Utc::now();
-	for mut player in players {
-		let hours_til_update = 24 - player.upgrades.daily_time_reset; // for the next reset
-		if player.daily_reset + Duration::hours(hours_til_update) < now {
-			player.daily_streak = 0;
-			let mut update = Document::new();
-			update.insert("daily_streak", player.daily_streak);
-			player::update_player(&player, doc!{"$set": update }).await;
-		}
+pub async fn check_daily_streaks(_ctx: Arc<Context>) {
+	match player::reset_expired_streaks().await {
+		Ok((reset, frozen)) => info!(target: logging::ECONOMY, "Checked daily streaks, {} were reset and {} were frozen", reset, frozen),
+		Err(why) => error!(target: logging::ECONOMY, "Couldn't check daily streaks: {}", why)
 	}
 }
-
-/* Tasks
- * v1.6.0
- * 	Add global cash sinks (buys for all players) (aka expensive)
- * 		daily reset
- * 		token shop refresh
- * 		store refresh
- * 		pack reset
- * 		slot reset
- * Misc
- * 	Add a help command (Okay shits not working so I don't care right now.)
- *	 	Add usage macro to all the commands and sub commands
- * 	Add a changelog command that DMs the user the patch note they want
- * 		Add the current version to the activity
-*/
\ No newline at end of file
@@ -11,7 +11,8 @@ use crate::card::{
 	Card,
 	get_cards_by_set
 };
-use crate::commands::poketcg::RARITY_MAPPING;
+use crate::commands::poketcg::config;
+use crate::error::BotResult;
 
 #[derive(Debug)]
 pub struct Pack {
@@ -20,10 +21,14 @@ pub struct Pack {
 }
 
 impl Pack {
-	pub async fn from_set_id(ctx: &Context, set_id: &str, amount: usize) -> Result<Self, String> {
-		let set = get_set(set_id)
-			.await
-			.unwrap();
+	pub async fn from_set_id(ctx: &Context, set_id: &str, amount: usize) -> BotResult<Self> {
+		Self::from_set_id_with_luck(ctx, set_id, amount, 0).await
+	}
+
+	/// Opens packs where the first `lucky_packs` of them have their rare picked
+	/// with flattened weights, so the rarer cards come up much more often.
+	pub async fn from_set_id_with_luck(ctx: &Context, set_id: &str, amount: usize, lucky_packs: usize) -> BotResult<Self> {
+		let set = get_set(set_id).await?;
 		let all_cards = get_cards_by_set(ctx, &set)
 			.await;
 		let rares = all_cards
@@ -60,13 +65,27 @@ impl Pack {
 					cards.push(c);
 				}
 			}
+			let economy_config = config::get();
 			let mut rares_with_weights = vec![];
 			for rare in rares {
-				let weight = RARITY_MAPPING.get(rare.rarity.as_str()).unwrap_or(&0).to_owned();
+				let weight = economy_config.rarity_weight(rare.rarity.as_str());
 				rares_with_weights.push((rare, weight));
 			}
-			for _ in 0..amount {
-				let c = rares_with_weights
+			let max_weight = rares_with_weights
+				.iter()
+				.map(|rw| rw.1)
+				.max()
+				.unwrap_or(0);
+			let lucky_rares_with_weights = rares_with_weights
+				.iter()
+				.map(|rw| (rw.0.clone(), rw.1 + (max_weight - rw.1) / 2))
+				.collect::<Vec<(Card, i64)>>();
+			for i in 0..amount {
+				let weights = match i < lucky_packs {
+					true => &lucky_rares_with_weights,
+					false => &rares_with_weights
+				};
+				let c = weights
 					.choose_weighted(&mut thread_rng(), |rw| rw.1 as i32)
 					.unwrap()
 					.clone()
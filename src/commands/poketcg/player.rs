@@ -14,10 +14,9 @@ use mongodb::{
 };
 use chrono::{
 	DateTime, 
-	Utc, 
-	Local,
-	Duration
+	Utc
 };
+use chrono_tz::Tz;
 use futures::stream::{TryStreamExt};
 use serenity::{
 	framework::{
@@ -36,6 +35,7 @@ use serenity::{
 		channel::{
 			Message,
 		},
+		id::GuildId,
 	},
 	utils::{
 		Colour
@@ -44,23 +44,41 @@ use serenity::{
 };
 
 use crate::{
-	commands::get_client
+	commands::get_client,
+	error::{
+		BotResult,
+		Error
+	}
 };
 
 use super::{
 	PaginateEmbed,
-	upgrade::Upgrade,
+	upgrade::{
+		Upgrade,
+		UPGRADES,
+		find_upgrade
+	},
 	binder::Binder,
+	reminders::Reminders,
+	calendar::LoginCalendar,
+	changelog,
+	export::MY_EXPORT_COMMAND,
 	RARITY_ORDER,
 	player_card,
 	timers,
+	config,
+	guild::{
+		get_economy,
+		economy_filter
+	},
 	HasSet,
 	Idable,
 	card::{
 		get_multiple_cards_by_id,
 		get_card
 	},
-	Scrollable
+	Scrollable,
+	discord_timestamp
 };
 
 fn def_10() -> i64 {
@@ -87,6 +105,10 @@ fn def_empty_vec_str() -> Vec<String> {
 	vec![]
 }
 
+fn def_utc() -> String {
+	String::from("UTC")
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Player {
 	#[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
@@ -131,11 +153,36 @@ pub struct Player {
 	#[serde(default = "def_empty_vec_str")]
 	pub completed_binders: Vec<String>,
 	#[serde(default = "def_0")]
-	pub daily_streak: i64
+	pub daily_streak: i64,
+	#[serde(default = "def_0")]
+	pub economy: i64,
+	#[serde(default = "def_0")]
+	pub prestige: i64,
+	#[serde(default = "def_0")]
+	pub prestige_points: i64,
+	#[serde(default = "def_utc")]
+	pub timezone: String,
+	#[serde(default)]
+	pub reminders: Reminders,
+	#[serde(default)]
+	pub items: HashMap<String, i64>,
+	// Items that were used and apply to the next quiz or pack
+	#[serde(default = "def_empty_vec_str")]
+	pub active_items: Vec<String>,
+	#[serde(default)]
+	pub calendar: LoginCalendar,
+	// The bot version the player last used, so they're told once about what's new
+	#[serde(default)]
+	pub last_seen_version: String,
+	// Cards the player wants and how many, eg. what's missing from a draft deck
+	#[serde(default)]
+	pub wishlist: HashMap<String, i64>
 }
 
 impl Player {
-	fn new_from_discord_id(discord_id: i64) -> Self {
+	fn new_from_discord_id(discord_id: i64, economy: i64) -> Self {
+		let economy_config = config::get();
+
 		Self {
 			id: None,
 			discord_id,
@@ -148,14 +195,14 @@ impl Player {
 			cards: HashMap::new(),
 			total_cards: 0,
 			cards_sold: 0,
-			daily_packs: 50,
-			quiz_questions: 5,
+			daily_packs: economy_config.packs.daily_packs,
+			quiz_questions: economy_config.quiz.questions,
 			current_multiplier: 1,
 			quiz_correct: 0,
 			quiz_reset: Utc::now(),
 			savelist: vec![],
 			perm_multiplier: 50,
-			daily_slots: 10,
+			daily_slots: economy_config.packs.daily_slots,
 			slots_rolled: 0,
 			jackpots: 0,
 			boofs: 0,
@@ -166,15 +213,77 @@ impl Player {
 			current_binder: Binder::empty(),
 			completed_binders: vec![],
 			daily_streak: 0,
+			economy,
+			prestige: 0,
+			prestige_points: 0,
+			timezone: def_utc(),
+			reminders: Reminders::default(),
+			items: HashMap::new(),
+			active_items: vec![],
+			calendar: LoginCalendar::default(),
+			last_seen_version: String::from(changelog::VERSION),
+			wishlist: HashMap::new(),
+		}
+	}
+
+	pub fn tz(&self) -> Tz {
+		self.timezone.parse().unwrap_or(Tz::UTC)
+	}
+
+	/// Formats a time in the player's timezone, for places Discord timestamps don't render like footers.
+	pub fn format_time(&self, time: DateTime<Utc>) -> String {
+		time.with_timezone(&self.tz()).format("%h %d %H:%M %Z").to_string()
+	}
+
+	pub fn prestige_badge(&self) -> String {
+		match self.prestige {
+			0 => String::from(""),
+			1..=5 => format!(" {}", "⭐".repeat(self.prestige as usize)),
+			_ => format!(" ⭐x{}", self.prestige)
+		}
+	}
+
+	/// Removes an active item, returning whether it was active.
+	pub fn take_active_item(&mut self, item: &str) -> bool {
+		match self.active_items.iter().position(|i| i == item) {
+			Some(idx) => {
+				self.active_items.remove(idx);
+				true
+			},
+			None => false
+		}
+	}
+
+	pub fn items_doc(&self) -> Document {
+		let mut ret = Document::new();
+		for (item, amt) in self.items.iter() {
+			ret.insert(item, amt);
 		}
+
+		ret
+	}
+
+	/// Multiplier applied to cash from selling cards and packs.
+	pub fn sell_mult(&self) -> f64 {
+		1.0 + self.upgrades.level("sell_price_bonus") as f64 * config::get().effects.sell_bonus_per_level
+	}
+
+	/// Fraction of the cash this player sends in a trade that's lost as a fee.
+	pub fn trade_fee(&self) -> f64 {
+		let effects = &config::get().effects;
+		let fee = effects.trade_fee - self.upgrades.level("trade_fee_reduction") as f64 * effects.trade_fee_reduction_per_level;
+
+		fee.max(0.0)
 	}
 }
 
 impl PaginateEmbed for Player {
 	fn embed(&self) -> CreateEmbed {
-		let quiz_reset_local: DateTime<Local> = DateTime::from(self.quiz_reset);
-		let daily_reset_local: DateTime<Local> = DateTime::from(self.daily_reset);
-		let mut desc = format!("**Wallet:** ${:.2} | **Total Earned:** ${:.2}\n\n", &self.cash, &self.total_cash);
+		let mut desc = String::from("");
+		if self.prestige > 0 {
+			desc.push_str(&format!("**Prestige:** {} | **Prestige Points:** {}\n\n", &self.prestige, &self.prestige_points));
+		}
+		desc.push_str(&format!("**Wallet:** ${:.2} | **Total Earned:** ${:.2}\n\n", &self.cash, &self.total_cash));
 		desc.push_str(&format!("**Current Packs:** {}\n", self.packs.values().map(|v| v.clone() as i32).sum::<i32>()));
 		desc.push_str(&format!("**Opened Packs:** {} | **Bought Packs:** {}\n\n", &self.packs_opened, &self.packs_bought));
 		desc.push_str(&format!("**Total Cards:** {} | **Cards Sold:** {}\n\n", &self.total_cards, &self.cards_sold));
@@ -187,8 +296,8 @@ impl PaginateEmbed for Player {
 			"" => desc.push_str(&format!("**Current Binder:** None! | **Completed Binders:** {}\n\n", &self.completed_binders.len())),
 			_ => desc.push_str(&format!("**Current Binder:** {} | **Completed Binders:** {}\n\n", &self.current_binder.set, &self.completed_binders.len()))
 		}
-		desc.push_str(&format!("Quiz resets at **{}**\n", quiz_reset_local.format("%m/%d %H:%M")));
-		desc.push_str(&format!("Daily reset at **{}** | Current Streak: **{}**", daily_reset_local.format("%m/%d %H:%M"), &self.daily_streak));
+		desc.push_str(&format!("Quiz resets **{}**\n", discord_timestamp(&self.quiz_reset, 'f')));
+		desc.push_str(&format!("Daily resets **{}** | Current Streak: **{}**", discord_timestamp(&self.daily_reset, 'f'), &self.daily_streak));
 		let mut ret = CreateEmbed::default();
 		ret
 			.description(desc)
@@ -198,72 +307,240 @@ impl PaginateEmbed for Player {
 	}
 }
 
-async fn get_player_collection() -> Collection<Player> {
-	let client = get_client().await.unwrap();
+async fn get_player_collection() -> BotResult<Collection<Player>> {
+	let client = get_client().await?;
 	let collection = client.database("poketcg").collection::<Player>("players");
 
-	collection
+	Ok(collection)
 }
 
 // Database functions
-pub async fn get_players() -> Vec<Player> { // Will change to Player
-	let player_collection = get_player_collection().await;
+pub async fn get_players_with_reminders() -> BotResult<Vec<Player>> {
+	let player_collection = get_player_collection().await?;
 	let players = player_collection
-		.find(None, None)
-		.await
-		.unwrap()
+		.find(doc! { "$or": [
+			{ "reminders.daily": true },
+			{ "reminders.streak": true },
+			{ "reminders.quiz": true },
+			{ "reminders.slots": true },
+			{ "reminders.store": true }
+		]}, None)
+		.await?
 		.try_collect::<Vec<Player>>()
-		.await
-		.unwrap();
+		.await?;
 
-	players
+	Ok(players)
 }
 
-pub async fn get_player(discord_id: u64) -> Player { // Will change to Player
+pub async fn get_player(discord_id: u64, guild_id: Option<GuildId>) -> BotResult<Player> {
 	let discord_id = discord_id as i64;
-	let player_collection = get_player_collection().await;
-	let player = player_collection
-		.find_one(doc! { "discord_id": discord_id }, None)
-		.await
-		.unwrap();
-	match player {
-		Some(x) => return x,
-		None => return add_player(discord_id).await
+	let economy = get_economy(guild_id).await?;
+	match find_player(discord_id, economy).await? {
+		Some(x) => Ok(x),
+		None => add_player(discord_id, economy).await
 	}
 }
 
-async fn add_player(discord_id: i64) -> Player {
-	let ret = Player::new_from_discord_id(discord_id);
-	let player_collection = get_player_collection().await;
-	player_collection
+/// The player in the economy, without making them one if they've never played.
+pub async fn find_player(discord_id: i64, economy: i64) -> BotResult<Option<Player>> {
+	let player_collection = get_player_collection().await?;
+	let mut filter = doc! { "discord_id": discord_id };
+	filter.extend(economy_filter(economy));
+	let player = player_collection
+		.find_one(filter, None)
+		.await?;
+
+	Ok(player)
+}
+
+async fn add_player(discord_id: i64, economy: i64) -> BotResult<Player> {
+	let mut ret = Player::new_from_discord_id(discord_id, economy);
+	let player_collection = get_player_collection().await?;
+	let result = player_collection
 		.insert_one(&ret, None)
-		.await
-		.unwrap();
-	
-	ret
+		.await?;
+	// Later updates are done by id, so the new player needs it
+	ret.id = result.inserted_id.as_object_id();
+
+	Ok(ret)
 }
 
-pub async fn update_player(player: &Player, update: Document) {
-	let player_collection = get_player_collection().await;
+pub async fn update_player(player: &Player, update: Document) -> BotResult<()> {
+	let id = match player.id {
+		Some(x) => x,
+		None => return Err(Error::NotFound(String::from("player")))
+	};
+	let player_collection = get_player_collection().await?;
 	player_collection
 		.update_one(
-			doc! {"_id": &player.id.unwrap() }, 
-			update, 
+			doc! {"_id": id },
+			update,
 			None)
-		.await
-		.unwrap();
+		.await?;
+
+	Ok(())
+}
+
+// An upgrade's level inside an update pipeline. Older players may not have every upgrade key yet
+fn upgrade_level_expr(upgrade: &str) -> Document {
+	doc! { "$ifNull": [format!("$upgrades.{}", upgrade), 0] }
+}
+
+// A player's daily packs after a reset, capped by their pack limit
+fn daily_packs_expr() -> Document {
+	let packs_config = config::get().packs.clone();
+	let daily_packs = doc! { "$add": [
+		packs_config.daily_packs,
+		{ "$multiply": [upgrade_level_expr("daily_pack_amount"), packs_config.daily_packs_per_level] }
+	]};
+	let pack_limit = doc! { "$add": [
+		packs_config.pack_limit,
+		{ "$multiply": [upgrade_level_expr("pack_limit"), packs_config.pack_limit_per_level] }
+	]};
+
+	doc! { "$min": [{ "$add": ["$daily_packs", daily_packs] }, pack_limit] }
+}
+
+// A player's slot rolls after a reset
+fn daily_slots_expr() -> Document {
+	let packs_config = config::get().packs.clone();
+
+	doc! { "$add": [
+		packs_config.daily_slots,
+		{ "$multiply": [upgrade_level_expr("daily_slot_amount"), packs_config.daily_slots_per_level] }
+	]}
+}
+
+async fn update_players_in(economy: i64, pipeline: Vec<Document>) -> BotResult<u64> {
+	let player_collection = get_player_collection().await?;
+	let result = player_collection
+		.update_many(economy_filter(economy), pipeline, None)
+		.await?;
+
+	Ok(result.modified_count)
+}
+
+/// Gives every player in the economy their daily packs and slot rolls in a single
+/// update. Returns how many players were updated.
+pub async fn reset_dailies_in(economy: i64) -> BotResult<u64> {
+	let pipeline = vec![
+		doc! { "$set": {
+			"daily_packs": daily_packs_expr(),
+			"daily_slots": daily_slots_expr()
+		}}
+	];
+
+	update_players_in(economy, pipeline).await
+}
+
+/// Gives every player in the economy their daily packs, without touching slot rolls.
+pub async fn reset_packs_in(economy: i64) -> BotResult<u64> {
+	update_players_in(economy, vec![doc! { "$set": { "daily_packs": daily_packs_expr() } }]).await
+}
+
+/// Refills the slot rolls of every player in the economy.
+pub async fn reset_slots_in(economy: i64) -> BotResult<u64> {
+	update_players_in(economy, vec![doc! { "$set": { "daily_slots": daily_slots_expr() } }]).await
+}
+
+/// Makes the daily ready right away for every player in the economy that's waiting on it.
+pub async fn reset_daily_timers_in(economy: i64) -> BotResult<u64> {
+	let now = Utc::now();
+	let pipeline = vec![doc! { "$set": { "daily_reset": { "$min": ["$daily_reset", now] } } }];
+
+	update_players_in(economy, pipeline).await
+}
+
+/// Resets the streak of every player that missed their last daily. Players with a
+/// streak freeze use one instead and get a fresh daily. Returns how many streaks
+/// were reset and how many were frozen.
+pub async fn reset_expired_streaks() -> BotResult<(u64, u64)> {
+	let reset_hours = config::get().daily.reset_hours;
+	let now = Utc::now();
+	// When the player's next daily expires, in milliseconds after their daily reset
+	let expiry = doc! { "$add": [
+		"$daily_reset",
+		{ "$multiply": [{ "$subtract": [reset_hours, upgrade_level_expr("daily_time_reset")] }, 3600000] }
+	]};
+	let player_collection = get_player_collection().await?;
+	let frozen = player_collection
+		.update_many(
+			doc! {
+				"daily_streak": { "$gt": 0 },
+				"items.streak_freeze": { "$gt": 0 },
+				"$expr": { "$lt": [expiry.clone(), now] }
+			},
+			doc! {
+				"$inc": { "items.streak_freeze": -1 },
+				"$set": { "daily_reset": now }
+			},
+			None)
+		.await?;
+	// A used up freeze shouldn't stay behind as a 0 in the player's items
+	player_collection
+		.update_many(
+			doc! { "items.streak_freeze": { "$lte": 0 } },
+			doc! { "$unset": { "items.streak_freeze": "" } },
+			None)
+		.await?;
+	let filter = doc! {
+		"daily_streak": { "$gt": 0 },
+		"$expr": { "$lt": [expiry, now] }
+	};
+	let pipeline = vec![doc! { "$set": { "daily_streak": 0 } }];
+	let reset = player_collection
+		.update_many(filter, pipeline, None)
+		.await?;
+
+	Ok((reset.modified_count, frozen.modified_count))
+}
+
+#[cfg(feature = "metrics")]
+#[derive(Debug, Deserialize)]
+pub struct MoneySupply {
+	#[serde(rename = "_id")]
+	pub economy: i64,
+	pub cash: f64,
+	pub tokens: i64
+}
+
+/// The cash and tokens held by every player, summed up per economy.
+#[cfg(feature = "metrics")]
+pub async fn get_money_supply() -> BotResult<Vec<MoneySupply>> {
+	let pipeline = vec![
+		doc! { "$group": {
+			"_id": { "$ifNull": ["$economy", 0] },
+			"cash": { "$sum": "$cash" },
+			"tokens": { "$sum": "$tokens" }
+		}},
+		doc! { "$sort": { "_id": 1 } }
+	];
+	let player_collection = get_player_collection().await?;
+	let docs = player_collection
+		.aggregate(pipeline, None)
+		.await?
+		.try_collect::<Vec<Document>>()
+		.await?;
+	let mut ret = vec![];
+	for doc in docs {
+		ret.push(mongodb::bson::from_document::<MoneySupply>(doc).map_err(|why| Error::Parse(why.to_string()))?);
+	}
+
+	Ok(ret)
 }
 
 // COMMANDS
 #[command("my")]
-#[sub_commands(my_cards, my_packs, my_stats, my_upgrades)]
+#[sub_commands(my_cards, my_packs, my_stats, my_upgrades, my_export)]
+#[description("Shows the commands for your own profile")]
 async fn my_main(ctx: &Context, msg: &Message) -> CommandResult {
 	let content = "Here are the available my commands:
 	**.my cards [sort_by - Default: name]** to view your cards.
 	**.my packs** to view your packs.
 	**.my stats** to view your stats.
-	**.my upgrades** to view your upgrades.";
-	let _ = get_player(msg.author.id.0).await;
+	**.my upgrades** to view your upgrades.
+	**.my export [csv | json]** to get your collection as files.";
+	let _ = get_player(msg.author.id.0, msg.guild_id).await?;
 	msg.reply(&ctx.http, content).await?;
 
 	Ok(())
@@ -271,12 +548,15 @@ async fn my_main(ctx: &Context, msg: &Message) -> CommandResult {
 
 #[command("cards")]
 #[aliases("c")]
+#[description("Shows your cards")]
+#[usage("[sort_by - Default: name]")]
+#[example("price")]
 async fn my_cards(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
 	let sorting = match args.find::<String>() {
 		Ok(x) => x.to_lowercase(),
 		Err(_) => String::from("name")
 	};
-	let player = get_player(msg.author.id.0).await;
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
 	let mut cards = player_card::player_cards(ctx, player.cards.clone()).await;
 	if cards.len() == 0 {
 		msg.reply(&ctx.http, "You have no cards!").await?;
@@ -322,9 +602,10 @@ async fn my_cards(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult
 
 #[command("packs")]
 #[aliases("p")]
+#[description("Shows your packs")]
 async fn my_packs(ctx: &Context, msg: &Message) -> CommandResult {
-	let player = get_player(msg.author.id.0).await;
-	let timer = timers::get_timer().await;
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let timer = timers::get_timer(player.economy).await?;
 	let mut desc = format!("You have **{}** packs left to open today\n", player.daily_packs);
 	desc.push_str("Use **.(op)enpack <set_id> [amount]** to open packs\n");
 	for (set_id, amount) in player.packs.iter() {
@@ -338,11 +619,7 @@ async fn my_packs(ctx: &Context, msg: &Message) -> CommandResult {
 					.title("Your packs")
 					.description(&desc)
 					.colour(Colour::from_rgb(255, 50, 20))
-					.footer(|f| {
-						let local_timer: DateTime<Local> = DateTime::from(timer.pack_reset);
-
-						f.text(&format!("Resets {}", local_timer.format("%h %d %H:%M")))
-					})
+					.footer(|f| f.text(&format!("Resets {}", player.format_time(timer.pack_reset))))
 			})
 		})
 		.await?;
@@ -352,8 +629,9 @@ async fn my_packs(ctx: &Context, msg: &Message) -> CommandResult {
 
 #[command("stats")]
 #[aliases("s")]
+#[description("Shows your stats")]
 async fn my_stats(ctx: &Context, msg: &Message) -> CommandResult {
-	let player = get_player(msg.author.id.0).await;
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
 	let nickname = match msg.author_nick(ctx).await {
 		Some(x) => x,
 		None => msg.author.name.clone()
@@ -365,7 +643,7 @@ async fn my_stats(ctx: &Context, msg: &Message) -> CommandResult {
 			let mut e = player.embed();
 			
 			e
-				.title(nickname)
+				.title(format!("{}{}", nickname, player.prestige_badge()))
 				.thumbnail(avatar_url);
 			m.set_embed(e);
 
@@ -378,8 +656,9 @@ async fn my_stats(ctx: &Context, msg: &Message) -> CommandResult {
 
 #[command("upgrades")]
 #[aliases("ups")]
+#[description("Shows your upgrades")]
 async fn my_upgrades(ctx: &Context, msg: &Message) -> CommandResult {
-	let player = get_player(msg.author.id.0).await;
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
 	let nickname = match msg.author_nick(ctx).await {
 		Some(x) => x,
 		None => msg.author.name.clone()
@@ -390,7 +669,7 @@ async fn my_upgrades(ctx: &Context, msg: &Message) -> CommandResult {
 		.send_message(&ctx.http, |m| {
 			m.embed(|e| {
 				e
-					.title(nickname)
+					.title(format!("{}{}", nickname, player.prestige_badge()))
 					.thumbnail(avatar_url)
 					.description(player.upgrades.desc())
 					.colour(Colour::from_rgb(255, 50, 20))
@@ -406,13 +685,14 @@ async fn my_upgrades(ctx: &Context, msg: &Message) -> CommandResult {
 #[command("player")]
 #[aliases("pl")]
 #[sub_commands(player_cards, player_packs, player_stats, player_upgrades)]
+#[description("Shows the commands for another player's profile")]
 async fn player_main(ctx: &Context, msg: &Message) -> CommandResult {
 	let content = "Here are the available player commands:
 	**.player cards [sort_by - Default: name]** to view a player's cards.
 	**.player packs** to view a player's packs.
 	**.player stats** to view a player's stats.
 	**.player upgrades** to view a player's upgrades";
-	let _ = get_player(msg.author.id.0).await;
+	let _ = get_player(msg.author.id.0, msg.guild_id).await?;
 	msg.reply(&ctx.http, content).await?;
 
 	Ok(())
@@ -420,6 +700,10 @@ async fn player_main(ctx: &Context, msg: &Message) -> CommandResult {
 
 #[command("cards")]
 #[aliases("c")]
+#[description("Shows a player's cards")]
+#[usage("<@player> [sort_by - Default: name]")]
+#[example("@player2 price")]
+#[min_args(1)]
 async fn player_cards(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
 	let player_mention = msg.mentions.iter().nth(0);
 	match player_mention {
@@ -430,7 +714,7 @@ async fn player_cards(ctx: &Context, msg: &Message, mut args: Args) -> CommandRe
 		}
 	}
 	let player_mention = player_mention.unwrap();
-	let player = get_player(player_mention.id.0).await;
+	let player = get_player(player_mention.id.0, msg.guild_id).await?;
 	args.advance();
 	let sorting = match args.single::<String>() {
 		Ok(x) => x.to_lowercase(),
@@ -480,6 +764,10 @@ async fn player_cards(ctx: &Context, msg: &Message, mut args: Args) -> CommandRe
 
 #[command("packs")]
 #[aliases("p")]
+#[description("Shows a player's packs")]
+#[usage("<@player>")]
+#[example("@player2")]
+#[min_args(1)]
 async fn player_packs(ctx: &Context, msg: &Message) -> CommandResult {
 	let player_mention = msg.mentions.iter().nth(0);
 	match player_mention {
@@ -490,12 +778,13 @@ async fn player_packs(ctx: &Context, msg: &Message) -> CommandResult {
 		}
 	}
 	let player_mention = player_mention.unwrap();
-	let player = get_player(player_mention.id.0).await;
+	let player = get_player(player_mention.id.0, msg.guild_id).await?;
 	let nickname = match player_mention.nick_in(&ctx.http, msg.guild_id.unwrap()).await {
 		Some(x) => x,
 		None => player_mention.name.clone()
 	};
-	let timer = timers::get_timer().await;
+	let timer = timers::get_timer(player.economy).await?;
+	let viewer = get_player(msg.author.id.0, msg.guild_id).await?;
 	let mut desc = format!("{} has **{}** packs left to open today\n", nickname, player.daily_packs);
 	for (set_id, amount) in player.packs.iter() {
 		desc.push_str(&format!("**{}** - {}\n", set_id, amount));
@@ -508,11 +797,7 @@ async fn player_packs(ctx: &Context, msg: &Message) -> CommandResult {
 					.title(format!("{}'s packs", nickname))
 					.description(&desc)
 					.colour(Colour::from_rgb(255, 50, 20))
-					.footer(|f| {
-						let local_timer: DateTime<Local> = DateTime::from(timer.pack_reset);
-
-						f.text(&format!("Resets {}", local_timer.format("%h %d %H:%M")))
-					})
+					.footer(|f| f.text(&format!("Resets {}", viewer.format_time(timer.pack_reset))))
 			})
 		})
 		.await?;
@@ -522,6 +807,10 @@ async fn player_packs(ctx: &Context, msg: &Message) -> CommandResult {
 
 #[command("stats")]
 #[aliases("s")]
+#[description("Shows a player's stats")]
+#[usage("<@player>")]
+#[example("@player2")]
+#[min_args(1)]
 async fn player_stats(ctx: &Context, msg: &Message) -> CommandResult {
 	let player_mention = msg.mentions.iter().nth(0);
 	match player_mention {
@@ -532,7 +821,7 @@ async fn player_stats(ctx: &Context, msg: &Message) -> CommandResult {
 		}
 	}
 	let player_mention = player_mention.unwrap();
-	let player = get_player(player_mention.id.0).await;
+	let player = get_player(player_mention.id.0, msg.guild_id).await?;
 	let nickname = match player_mention.nick_in(&ctx.http, msg.guild_id.unwrap()).await {
 		Some(x) => x,
 		None => player_mention.name.clone()
@@ -544,7 +833,7 @@ async fn player_stats(ctx: &Context, msg: &Message) -> CommandResult {
 			let mut e = player.embed();
 			
 			e
-				.title(nickname)
+				.title(format!("{}{}", nickname, player.prestige_badge()))
 				.thumbnail(avatar_url);
 			m.set_embed(e);
 
@@ -557,6 +846,10 @@ async fn player_stats(ctx: &Context, msg: &Message) -> CommandResult {
 
 #[command("upgrades")]
 #[aliases("ups")]
+#[description("Shows a player's upgrades")]
+#[usage("<@player>")]
+#[example("@player2")]
+#[min_args(1)]
 async fn player_upgrades(ctx: &Context, msg: &Message) -> CommandResult {
 	let player_mention = msg.mentions.iter().nth(0);
 	match player_mention {
@@ -567,7 +860,7 @@ async fn player_upgrades(ctx: &Context, msg: &Message) -> CommandResult {
 		}
 	}
 	let player_mention = player_mention.unwrap();
-	let player = get_player(player_mention.id.0).await;
+	let player = get_player(player_mention.id.0, msg.guild_id).await?;
 	let nickname = match player_mention.nick_in(&ctx.http, msg.guild_id.unwrap()).await {
 		Some(x) => x,
 		None => player_mention.name.clone()
@@ -578,7 +871,7 @@ async fn player_upgrades(ctx: &Context, msg: &Message) -> CommandResult {
 		.send_message(&ctx.http, |m| {
 			m.embed(|e| {
 				e
-					.title(nickname)
+					.title(format!("{}{}", nickname, player.prestige_badge()))
 					.thumbnail(avatar_url)
 					.description(player.upgrades.desc())
 					.colour(Colour::from_rgb(255, 50, 20))
@@ -594,8 +887,9 @@ async fn player_upgrades(ctx: &Context, msg: &Message) -> CommandResult {
 #[command("upgrades")]
 #[aliases("up")]
 #[sub_commands(upgrades_buy)]
+#[description("Shows the upgrades you can buy")]
 async fn upgrades_main(ctx: &Context, msg: &Message) -> CommandResult {
-	let player = get_player(msg.author.id.0).await;
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
 	let embed = player.upgrades.clone().embed_with_player(player).await;
 	let _ = msg
 		.channel_id
@@ -611,20 +905,13 @@ async fn upgrades_main(ctx: &Context, msg: &Message) -> CommandResult {
 
 #[command("buy")]
 #[aliases("b")]
+#[description("Buys an upgrade")]
+#[usage("<upgrade no. | upgrade name>")]
+#[example("1")]
+#[example("slot_luck")]
+#[min_args(1)]
 async fn upgrades_buy(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-	let mut m = HashMap::new();
-	m.insert(1, "daily_time_reset");
-	m.insert(2, "daily_reward_mult");
-	m.insert(3, "daily_pack_amount");
-	m.insert(4, "store_discount");
-	m.insert(5, "tokenshop_discount");
-	m.insert(6, "slot_reward_mult");
-	m.insert(7, "daily_slot_amount");
-	m.insert(8, "quiz_time_reset");
-	m.insert(9, "quiz_question_amount");
-	m.insert(10, "quiz_mult_limit");
-	m.insert(11, "pack_limit");
-	let mut selection = match args.single::<i32>() {
+	let selection = match args.single::<usize>() {
 		Ok(x) => x,
 		Err(_) => 0
 	};
@@ -632,74 +919,43 @@ async fn upgrades_buy(ctx: &Context, msg: &Message, mut args: Args) -> CommandRe
 		Ok(x) => x,
 		Err(_) => String::from("")
 	};
-	let upgrades = vec!["dailytime", "dailyreward", "dailypacks", "storediscount", "tokenshopdiscount", "slotreward", "dailyslots", "quizreset", "quizattempts", "quizmultiplier", "packlimit"];
-	if selection_str != "" && selection == 0 {
-		selection = (upgrades.iter().position(|r| r == &selection_str).unwrap_or(upgrades.len()) + 1) as i32;
-	}
-	if !(1..=upgrades.len() as i32).contains(&selection) {
-		msg.channel_id.send_message(&ctx.http, |m| m.content("A selection was not made.")).await?;
+	let upgrade_def = match selection {
+		0 => find_upgrade(&selection_str),
+		_ => UPGRADES.get(selection - 1)
+	};
+	let upgrade_def = match upgrade_def {
+		Some(x) => x,
+		None => {
+			msg.channel_id.send_message(&ctx.http, |m| m.content("A selection was not made.")).await?;
+			return Ok(());
+		}
+	};
+	if !upgrade_def.is_available() {
+		msg.reply(&ctx.http, "That upgrade isn't available right now").await?;
 		return Ok(());
 	}
-	let upgrade_selection = m.get(&selection).unwrap().clone();
 	let amount = match args.find::<i32>() {
 		Ok(x) => x,
 		Err(_) => 1
 	};
 	let mut update = Document::new();
-	let mut player = get_player(msg.author.id.0).await;
-	if player.cash < player.upgrades.upgrade_cost(upgrade_selection) {
-		msg.reply(&ctx.http, &format!("You don't have enough... You need **${}** more", player.upgrades.upgrade_cost(upgrade_selection) - player.cash)).await?;
-		return Ok(());
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
+	if player.cash < player.upgrades.upgrade_cost(upgrade_def.key) {
+		return Err(Error::InsufficientFunds(format!("${:.2}", player.upgrades.upgrade_cost(upgrade_def.key) - player.cash)).into());
 	}
-	if player.upgrades.is_max_upgrade(upgrade_selection) {
+	if player.upgrades.is_max_upgrade(upgrade_def.key) {
 		msg.reply(&ctx.http, "That upgrade is already at it's highest level").await?;
 		return Ok(());
 	}
 	let mut count = 0;
-	while player.cash >= player.upgrades.upgrade_cost(upgrade_selection) && count < amount {
-		if player.upgrades.is_max_upgrade(upgrade_selection) {
+	while player.cash >= player.upgrades.upgrade_cost(upgrade_def.key) && count < amount {
+		if player.upgrades.is_max_upgrade(upgrade_def.key) {
 			break;
 		}
-		let cost = player.upgrades.upgrade_cost(upgrade_selection);
-		player.cash -= cost;
-		match upgrade_selection {
-			"daily_time_reset" => {
-				player.upgrades.daily_time_reset += 1;
-				let now = Utc::now();
-				if player.daily_reset >= now {
-					player.daily_reset = player.daily_reset - Duration::hours(1);
-				}
-			},
-			"daily_reward_mult" => player.upgrades.daily_reward_mult += 1,
-			"daily_pack_amount" => {
-				player.upgrades.daily_pack_amount += 1;
-				player.daily_packs += 10;
-			},
-			"store_discount" => player.upgrades.store_discount += 1,
-			"tokenshop_discount" => player.upgrades.tokenshop_discount += 1,
-			"slot_reward_mult" => player.upgrades.slot_reward_mult += 1,
-			"daily_slot_amount" => {
-				player.upgrades.daily_slot_amount += 1;
-				player.daily_slots += 1;
-			},
-			"quiz_time_reset" => {
-				player.upgrades.quiz_time_reset += 1;
-				let now = Utc::now();
-				if player.quiz_reset >= now {
-					player.quiz_reset = player.quiz_reset - Duration::minutes(10);
-				}
-			},
-			"quiz_question_amount" => {
-				player.upgrades.quiz_question_amount += 1;
-				player.quiz_questions += 1;
-			},
-			"quiz_mult_limit" => player.upgrades.quiz_mult_limit += 1,
-			"pack_limit" => player.upgrades.pack_limit += 1,
-			_ => ()
-		}
+		Upgrade::buy(&mut player, upgrade_def);
 		count += 1;
 	}
-	msg.reply(&ctx.http, format!("You bought {} **{}**", count, upgrades[(selection - 1) as usize])).await?;
+	msg.reply(&ctx.http, format!("You bought {} **{}**", count, upgrade_def.name)).await?;
 	update.insert("cash", player.cash);
 	update.insert("upgrades", player.upgrades.to_doc());
 	update.insert("daily_reset", player.daily_reset);
@@ -707,18 +963,19 @@ async fn upgrades_buy(ctx: &Context, msg: &Message, mut args: Args) -> CommandRe
 	update.insert("daily_slots", player.daily_slots);
 	update.insert("quiz_reset", player.quiz_reset);
 	update.insert("quiz_questions", player.quiz_questions);
-	update_player(&player, doc! { "$set": update }).await;
+	update_player(&player, doc! { "$set": update }).await?;
 
 	Ok(())
 }
 
 #[command("lightmode")]
 #[aliases("lm")]
+#[description("Toggles light mode for card images")]
 async fn lightmode_command(ctx: &Context, msg: &Message) -> CommandResult {
-	let mut player = get_player(msg.author.id.0).await;
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
 	player.light_mode = !player.light_mode;
 	msg.reply(&ctx.http, format!("Set light mode to **{}**", player.light_mode)).await?;
-	update_player(&player, doc! { "$set": {"light_mode": player.light_mode}}).await;
+	update_player(&player, doc! { "$set": {"light_mode": player.light_mode}}).await?;
 
 	Ok(())
 }
@@ -726,8 +983,9 @@ async fn lightmode_command(ctx: &Context, msg: &Message) -> CommandResult {
 #[command("savelist")]
 #[aliases("sl", "favourite", "favorite", "fv")]
 #[sub_commands(savelist_add, savelist_clear, savelist_remove)]
+#[description("Shows your savelist. Cards in it aren't sold by the sell commands")]
 async fn savelist_main(ctx: &Context, msg: &Message) -> CommandResult {
-	let player = get_player(msg.author.id.0).await;
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
 	let mut cards = get_multiple_cards_by_id(ctx, player.savelist.clone()).await;
 	if cards.len() == 0 {
 		msg.reply(&ctx.http, "You have no cards in your savelist! Use **.savelist add <card id>** to add a card\nOr use the :floppy_disk: emoji when scrolling through cards!").await?;
@@ -741,6 +999,10 @@ async fn savelist_main(ctx: &Context, msg: &Message) -> CommandResult {
 
 #[command("add")]
 #[aliases("a", "+")]
+#[description("Adds a card to your savelist")]
+#[usage("<card id>")]
+#[example("swsh1-25")]
+#[min_args(1)]
 async fn savelist_add(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
 	let card_id = match args.find::<String>() {
 		Ok(x) => x,
@@ -750,7 +1012,7 @@ async fn savelist_add(ctx: &Context, msg: &Message, mut args: Args) -> CommandRe
 		msg.reply(&ctx.http, "No card provided").await?;
 		return Ok(());
 	}
-	let mut player = get_player(msg.author.id.0).await;
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
 	let card = get_card(ctx, &card_id).await;
 	if player.savelist.contains(&card_id) {
 		msg.reply(&ctx.http, format!("**{}** is already in your savelist", card.name)).await?;
@@ -758,13 +1020,17 @@ async fn savelist_add(ctx: &Context, msg: &Message, mut args: Args) -> CommandRe
 	}
 	msg.reply(&ctx.http, format!("**{}** added to your savelist", card.name)).await?;
 	player.savelist.push(card_id);
-	update_player(&player, doc! { "$set": { "savelist": player.savelist.clone()}}).await;
+	update_player(&player, doc! { "$set": { "savelist": player.savelist.clone()}}).await?;
 
 	Ok(())
 }
 
 #[command("remove")]
 #[aliases("r", "-")]
+#[description("Removes a card from your savelist")]
+#[usage("<card id>")]
+#[example("swsh1-25")]
+#[min_args(1)]
 async fn savelist_remove(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
 	let card_id = match args.find::<String>() {
 		Ok(x) => x,
@@ -774,7 +1040,7 @@ async fn savelist_remove(ctx: &Context, msg: &Message, mut args: Args) -> Comman
 		msg.reply(&ctx.http, "No card provided").await?;
 		return Ok(());
 	}
-	let mut player = get_player(msg.author.id.0).await;
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
 	let card = get_card(ctx, &card_id).await;
 	if !player.savelist.contains(&card_id) {
 		msg.reply(&ctx.http, format!("**{}** is not in your savelist", card.name)).await?;
@@ -783,17 +1049,139 @@ async fn savelist_remove(ctx: &Context, msg: &Message, mut args: Args) -> Comman
 	msg.reply(&ctx.http, format!("**{}** removed from your savelist", card.name)).await?;
 	let index = player.savelist.clone().iter().position(|c| c == &card_id).unwrap();
 	player.savelist.remove(index);
-	update_player(&player, doc! { "$set": { "savelist": player.savelist.clone()}}).await;
+	update_player(&player, doc! { "$set": { "savelist": player.savelist.clone()}}).await?;
 
 	Ok(())
 }
 
 #[command("clear")]
+#[description("Clears your savelist")]
 async fn savelist_clear(ctx: &Context, msg: &Message) -> CommandResult {
-	let mut player = get_player(msg.author.id.0).await;
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
 	player.savelist = vec![];
-	update_player(&player, doc! { "$set": { "savelist": player.savelist.clone()}}).await;
+	update_player(&player, doc! { "$set": { "savelist": player.savelist.clone()}}).await?;
 	msg.reply(&ctx.http, "Your savelist has been cleared").await?;
 
 	Ok(())
-}
\ No newline at end of file
+}
+
+#[command("wishlist")]
+#[aliases("wl", "wants")]
+#[sub_commands(wishlist_add, wishlist_remove, wishlist_clear)]
+#[description("Shows the cards you want and what they'd cost")]
+async fn wishlist_main(ctx: &Context, msg: &Message) -> CommandResult {
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let mut cards = get_multiple_cards_by_id(ctx, player.wishlist.keys().cloned().collect()).await;
+	if cards.len() == 0 {
+		msg.reply(&ctx.http, "You have no cards in your wishlist! Use **.wishlist add <card id> [amount]** to add a card\nOr **.deck commit** a draft deck to add the cards you're missing").await?;
+		return Ok(());
+	}
+	cards.sort_by(|c1, c2| c1.name.cmp(&c2.name));
+	let mut desc = String::new();
+	let mut total = 0.0;
+	for card in &cards {
+		let amount = *player.wishlist.get(&card.card_id).unwrap_or(&0);
+		total += card.price * amount as f64;
+		let mut line = format!("**{}** (_{}_) x{} - ${:.2}", card.name, card.card_id, amount, card.price * amount as f64);
+		if let Some(owned) = player.cards.get(&card.card_id) {
+			line.push_str(&format!(" - you have {}", owned));
+		}
+		line.push('\n');
+		// Discord's limit for an embed description
+		if desc.chars().count() + line.chars().count() > 4000 {
+			desc.push_str("...");
+			break;
+		}
+		desc.push_str(&line);
+	}
+	msg
+		.channel_id
+		.send_message(&ctx.http, |m| {
+			m.embed(|e| {
+				e
+					.title("Wishlist")
+					.description(&desc)
+					.footer(|f| f.text(format!("{} cards - ${:.2} total", player.wishlist.values().sum::<i64>(), total)))
+					.colour(Colour::from_rgb(255, 50, 20))
+			})
+		})
+		.await?;
+
+	Ok(())
+}
+
+#[command("add")]
+#[aliases("a", "+")]
+#[description("Adds a card to your wishlist")]
+#[usage("<card id> [amount - Default: 1]")]
+#[example("swsh1-25 2")]
+#[min_args(1)]
+async fn wishlist_add(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+	let card_id = match args.single::<String>() {
+		Ok(x) => x,
+		Err(_) => String::from("")
+	};
+	if card_id == "" {
+		msg.reply(&ctx.http, "No card provided").await?;
+		return Ok(());
+	}
+	let amount = args.single::<i64>().unwrap_or(1);
+	if amount < 1 {
+		msg.reply(&ctx.http, "You have to want at least 1").await?;
+		return Ok(());
+	}
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let card = get_card(ctx, &card_id).await;
+	*player.wishlist.entry(card.card_id.clone()).or_insert(0) += amount;
+	update_player(&player, doc! { "$set": { "wishlist": wishlist_doc(&player.wishlist) }}).await?;
+	msg.reply(&ctx.http, format!("**{}** x{} added to your wishlist", card.name, amount)).await?;
+
+	Ok(())
+}
+
+#[command("remove")]
+#[aliases("r", "-")]
+#[description("Removes a card from your wishlist")]
+#[usage("<card id>")]
+#[example("swsh1-25")]
+#[min_args(1)]
+async fn wishlist_remove(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+	let card_id = match args.find::<String>() {
+		Ok(x) => x,
+		Err(_) => String::from("")
+	};
+	if card_id == "" {
+		msg.reply(&ctx.http, "No card provided").await?;
+		return Ok(());
+	}
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let card = get_card(ctx, &card_id).await;
+	if player.wishlist.remove(&card.card_id).is_none() {
+		msg.reply(&ctx.http, format!("**{}** is not in your wishlist", card.name)).await?;
+		return Ok(());
+	}
+	update_player(&player, doc! { "$set": { "wishlist": wishlist_doc(&player.wishlist) }}).await?;
+	msg.reply(&ctx.http, format!("**{}** removed from your wishlist", card.name)).await?;
+
+	Ok(())
+}
+
+#[command("clear")]
+#[description("Clears your wishlist")]
+async fn wishlist_clear(ctx: &Context, msg: &Message) -> CommandResult {
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
+	player.wishlist = HashMap::new();
+	update_player(&player, doc! { "$set": { "wishlist": wishlist_doc(&player.wishlist) }}).await?;
+	msg.reply(&ctx.http, "Your wishlist has been cleared").await?;
+
+	Ok(())
+}
+
+pub fn wishlist_doc(wishlist: &HashMap<String, i64>) -> Document {
+	let mut ret = Document::new();
+	for (card_id, amount) in wishlist {
+		ret.insert(card_id, amount);
+	}
+
+	ret
+}
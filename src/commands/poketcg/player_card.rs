@@ -15,6 +15,7 @@ use super::{
 use std::collections::HashMap;
 use async_trait::async_trait;
 
+use crate::logging;
 use crate::commands::poketcg::card::{
 	Card,
 	get_multiple_cards_by_id
@@ -70,12 +71,12 @@ impl HasSet for PlayerCard {
 
 #[async_trait]
 impl Scrollable for Vec<PlayerCard> {
-	async fn scroll_through(&self, ctx: &Context, msg: &Message) -> Result<(), String> {
+	async fn scroll_through(&self, ctx: &Context, msg: &Message) -> BotResult<()> {
 		let left_arrow = ReactionType::try_from("⬅️").expect("No left arrow");
 		let right_arrow = ReactionType::try_from("➡️").expect("No right arrow");
 		let save_icon = ReactionType::try_from("💾").expect("No floppy disk");
 		let binder_icon = ReactionType::try_from(":pokeball:972277627077423124").expect("No pokeball");
-		let mut player = player::get_player(msg.author.id.0).await;
+		let mut player = player::get_player(msg.author.id.0, msg.guild_id).await?;
 		let embeds = self.iter().map(|e| e.embed()).collect::<Vec<_>>();
 		let mut idx: i16 = 0;
 		let mut content = String::from("");
@@ -133,7 +134,7 @@ impl Scrollable for Vec<PlayerCard> {
 							player.savelist.push(card_id.clone());
 							content = format!("**{}** added to your savelist!", &self[idx as usize].card_name());
 						}
-						player::update_player(&player, doc! { "$set": { "savelist": player.savelist.clone()}}).await;
+						player::update_player(&player, doc! { "$set": { "savelist": player.savelist.clone()}}).await?;
 					},
 					"pokeball:972277627077423124" => {
 						let card_id = self[idx as usize].card_id().clone();
@@ -142,7 +143,7 @@ impl Scrollable for Vec<PlayerCard> {
 						} else if &self[idx as usize].set().id() != &player.current_binder.set {
 							content = String::from("This card doesn't go in your binder!");
 						} else {
-							let current_binder_set = sets::get_set(&player.current_binder.set).await.unwrap();
+							let current_binder_set = sets::get_set(&player.current_binder.set).await?;
 							let mut player_update = Document::new();
 							*player.cards.entry(self[idx as usize].card_id()).or_insert(0) -= 1;
 							if *player.cards.entry(self[idx as usize].card_id()).or_insert(0) == 0 {
@@ -154,11 +155,9 @@ impl Scrollable for Vec<PlayerCard> {
 							}
 							player_update.insert("cards", player_cards);
 							player.current_binder.cards.push(self[idx as usize].card_id().clone());
-							if player.current_binder.is_complete(ctx).await {
-								player.completed_binders.push(player.current_binder.set);
-								player.current_binder = binder::Binder::empty();
-								player_update.insert("completed_binders", player.completed_binders.clone());
-								content = format!("You completed the **{}** binder!", current_binder_set.name);
+							if player.current_binder.is_complete(ctx).await? {
+								let bonus = binder::complete_binder(&mut player, &mut player_update);
+								content = binder::completed_binder_message(&current_binder_set.name, bonus);
 							} else {
 								content = format!("You added **{}** to your binder!", &self[idx as usize].card_name());
 							}
@@ -168,11 +167,11 @@ impl Scrollable for Vec<PlayerCard> {
 								player_cards.insert(crd, amt);
 							}
 							player_update.insert("cards", player_cards);
-							player::update_player(&player, doc! { "$set": player_update }).await;
+							player::update_player(&player, doc! { "$set": player_update }).await?;
 						}
 					}
 					_ => {
-						println!("{}", &emoji.as_data().as_str());
+						debug!(target: logging::CATALOG, "Unknown reaction {}", &emoji.as_data().as_str());
 						continue
 					}
 				};
@@ -0,0 +1,266 @@
+use chrono::Utc;
+use mongodb::bson::{
+	doc,
+	Document
+};
+use serenity::{
+	framework::{
+		standard::{
+			macros::{
+				command
+			},
+			Args,
+			CommandResult
+		},
+	},
+	model::{
+		channel::{
+			Message,
+		},
+	},
+	utils::{
+		Colour
+	},
+	prelude::*
+};
+use std::time::Duration;
+
+use crate::error::Error;
+
+use super::{
+	card::get_card,
+	config,
+	player::{
+		Player,
+		get_player,
+		update_player
+	},
+	slot::{
+		TokenShop,
+		get_token_shop
+	}
+};
+
+pub struct PrestigeItem {
+	pub name: &'static str,
+	pub description: &'static str,
+	pub cost: i64,
+	pub effect: fn(&mut Player, &TokenShop)
+}
+
+fn multiplier_boost(player: &mut Player, _: &TokenShop) {
+	player.perm_multiplier += 5;
+}
+
+fn daily_refresh(player: &mut Player, _: &TokenShop) {
+	player.daily_reset = Utc::now();
+}
+
+fn slot_refill(player: &mut Player, _: &TokenShop) {
+	player.daily_slots += config::get().packs.daily_slots;
+}
+
+fn quiz_refill(player: &mut Player, _: &TokenShop) {
+	player.quiz_questions += config::get().quiz.questions;
+}
+
+fn token_bag(player: &mut Player, _: &TokenShop) {
+	player.tokens += 1000;
+	player.total_tokens += 1000;
+}
+
+fn shop_rainbow(player: &mut Player, token_shop: &TokenShop) {
+	*player.cards.entry(token_shop.rainbow_card.clone()).or_insert(0) += 1;
+	player.total_cards += 1;
+}
+
+// Only buyable with prestige points
+pub const PRESTIGE_ITEMS: &'static [PrestigeItem] = &[
+	PrestigeItem {
+		name: "Multiplier Boost",
+		description: "Permanently raises your quiz multiplier by 5",
+		cost: 3,
+		effect: multiplier_boost
+	},
+	PrestigeItem {
+		name: "Daily Refresh",
+		description: "Lets you claim your daily right away",
+		cost: 1,
+		effect: daily_refresh
+	},
+	PrestigeItem {
+		name: "Slot Refill",
+		description: "Gives you another day's worth of slot rolls",
+		cost: 1,
+		effect: slot_refill
+	},
+	PrestigeItem {
+		name: "Quiz Refill",
+		description: "Gives you another set of quiz questions",
+		cost: 1,
+		effect: quiz_refill
+	},
+	PrestigeItem {
+		name: "Token Bag",
+		description: "1000 tokens",
+		cost: 2,
+		effect: token_bag
+	},
+	PrestigeItem {
+		name: "Shop Rainbow",
+		description: "A copy of today's rainbow card from the token shop",
+		cost: 5,
+		effect: shop_rainbow
+	},
+];
+
+fn prestige_requirements(player: &Player) -> Vec<String> {
+	// Returns the requirements the player is still missing
+	let prestige_config = &config::get().prestige;
+	let cash_required = prestige_config.cash_required(player.prestige);
+	let levels_required = prestige_config.upgrade_levels_required(player.prestige);
+	let mut missing = vec![];
+	if player.cash < cash_required {
+		missing.push(format!("**${:.2}** cash _(you have ${:.2})_", cash_required, player.cash));
+	}
+	if player.upgrades.total_levels() < levels_required {
+		missing.push(format!("**{}** total upgrade levels _(you have {})_", levels_required, player.upgrades.total_levels()));
+	}
+
+	missing
+}
+
+#[command("prestige")]
+#[aliases("rebirth")]
+#[description("Starts over for a permanent bonus and prestige points")]
+#[usage("[cards]")]
+#[example("cards")]
+async fn prestige_command(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+	let reset_cards = match args.find::<String>() {
+		Ok(x) => vec!["cards", "full", "c"].contains(&x.to_lowercase().as_str()),
+		Err(_) => false
+	};
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let prestige_config = config::get().prestige.clone();
+	let missing = prestige_requirements(&player);
+	if missing.len() > 0 {
+		let mut content = format!("To reach prestige **{}** you still need:\n", player.prestige + 1);
+		content.push_str(&missing.join("\n"));
+		msg.reply(&ctx.http, content).await?;
+		return Ok(());
+	}
+	let mut points = prestige_config.points;
+	let mut content = String::from("Prestiging resets your **cash** and **upgrades**");
+	if reset_cards {
+		points += prestige_config.card_reset_points;
+		content.push_str(", as well as every **pack** and every **card** that isn't in a binder, except one copy of the cards on your savelist");
+	}
+	content.push_str(&format!(".\nIn exchange your multiplier goes up by **{}** and you get **{}** prestige points.\nDo you want to prestige? (y/n)", prestige_config.perm_multiplier_bonus, points));
+	let _ = msg.reply(&ctx.http, content).await?;
+	if let Some(confirmation_reply) = &msg.author.await_reply(&ctx).timeout(Duration::from_secs(30)).await {
+		if confirmation_reply.content.to_lowercase() != "y" {
+			msg.reply(&ctx.http, "You did not prestige.").await?;
+			return Ok(());
+		}
+	} else {
+		msg.reply(&ctx.http, "You did not prestige.").await?;
+		return Ok(());
+	}
+	// Player said "y" to get here
+	player.cash = 25.0;
+	player.upgrades.reset();
+	player.perm_multiplier += prestige_config.perm_multiplier_bonus;
+	player.prestige += 1;
+	player.prestige_points += points;
+	let mut player_update = Document::new();
+	if reset_cards {
+		let savelist = player.savelist.clone();
+		player.cards.retain(|c, _| savelist.contains(c));
+		for amt in player.cards.values_mut() {
+			*amt = 1;
+		}
+		player.packs.clear();
+		let mut player_cards = Document::new();
+		for (crd, amt) in player.cards.iter() {
+			player_cards.insert(crd, amt);
+		}
+		player_update.insert("cards", player_cards);
+		player_update.insert("packs", Document::new());
+	}
+	player_update.insert("cash", player.cash);
+	player_update.insert("upgrades", player.upgrades.to_doc());
+	player_update.insert("perm_multiplier", player.perm_multiplier);
+	player_update.insert("prestige", player.prestige);
+	player_update.insert("prestige_points", player.prestige_points);
+	update_player(&player, doc! { "$set": player_update }).await?;
+	msg.reply(&ctx.http, format!("You are now prestige **{}**!{}", player.prestige, player.prestige_badge())).await?;
+
+	Ok(())
+}
+
+#[command("prestige")]
+#[aliases("p")]
+#[description("Spends prestige points on exclusive items")]
+#[usage("[slot no.]")]
+#[example("1")]
+async fn game_corner_tokens_prestige(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let selection = match args.single::<usize>() {
+		Ok(x) => x,
+		Err(_) => 0
+	};
+	if selection == 0 {
+		let mut desc = format!("You have **{}** prestige points\n", player.prestige_points);
+		desc.push_str("To purchase one, use **.gamecorner tokenshop prestige <slot no>**\n\n");
+		for (i, item) in PRESTIGE_ITEMS.iter().enumerate() {
+			desc.push_str(&format!("**{}:** {} - {} _({} points)_\n", i + 1, item.name, item.description, item.cost));
+		}
+		msg
+			.channel_id
+			.send_message(&ctx.http, |m| {
+				m.embed(|e| {
+					e
+						.title("Prestige Shop")
+						.description(&desc)
+						.colour(Colour::from_rgb(255, 50, 20))
+				})
+			})
+			.await?;
+		return Ok(());
+	}
+	let item = match PRESTIGE_ITEMS.get(selection - 1) {
+		Some(x) => x,
+		None => {
+			msg.channel_id.send_message(&ctx.http, |m| m.content("A selection was not made.")).await?;
+			return Ok(());
+		}
+	};
+	if player.prestige_points < item.cost {
+		return Err(Error::InsufficientFunds(format!("{} prestige points", item.cost - player.prestige_points)).into());
+	}
+	let token_shop = get_token_shop(ctx, player.economy).await?;
+	player.prestige_points -= item.cost;
+	(item.effect)(&mut player, &token_shop);
+	let content = match item.name {
+		"Shop Rainbow" => format!("You bought a **{}**!", get_card(ctx, &token_shop.rainbow_card).await.name),
+		_ => format!("You bought a **{}**!", item.name)
+	};
+	let mut player_cards = Document::new();
+	for (crd, amt) in player.cards.iter() {
+		player_cards.insert(crd, amt);
+	}
+	let mut player_update = Document::new();
+	player_update.insert("prestige_points", player.prestige_points);
+	player_update.insert("perm_multiplier", player.perm_multiplier);
+	player_update.insert("daily_reset", player.daily_reset);
+	player_update.insert("daily_slots", player.daily_slots);
+	player_update.insert("quiz_questions", player.quiz_questions);
+	player_update.insert("tokens", player.tokens);
+	player_update.insert("total_tokens", player.total_tokens);
+	player_update.insert("cards", player_cards);
+	player_update.insert("total_cards", player.total_cards);
+	update_player(&player, doc! { "$set": player_update }).await?;
+	msg.reply(&ctx.http, content).await?;
+
+	Ok(())
+}
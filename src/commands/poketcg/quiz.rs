@@ -25,12 +25,13 @@ use serenity::{
 use chrono::{
 	Utc,
 	Duration,
-	DateTime,
-	Local,
 };
 use std::time::Duration as StdDuration;
 use crate::{
 	player,
+	config,
+	discord_timestamp,
+	error::BotResult,
 };
 use mongodb::bson::{Document, doc};
 use convert_case::{Case, Casing};
@@ -84,16 +85,15 @@ impl Quiz {
 		}
 	}
 
-	pub async fn generate_silhouette(&self, light_mode: bool) {
+	pub async fn generate_silhouette(&self, light_mode: bool) -> BotResult<()> {
 		let resp = reqwest::Client::new()
 			.get(format!("https://img.pokemondb.net/sprites/home/normal/{}.png", self.name))
-			.send().await.unwrap()
-			.bytes().await.unwrap();
+			.send().await?
+			.bytes().await?;
 		let reader = Reader::new(Cursor::new(resp))
-			.with_guessed_format()
-			.expect("Can't get image");
-		let mut image = reader.decode().unwrap().into_rgba8();
-		image.save("quizresult.PNG").unwrap();
+			.with_guessed_format()?;
+		let mut image = reader.decode()?.into_rgba8();
+		image.save("quizresult.PNG")?;
 		let (width, height) = image.dimensions();
 		for i in 0..width {
 			for j in 0..height {
@@ -107,7 +107,9 @@ impl Quiz {
 				}
 			}
 		}
-		image.save("quizsilhouette.PNG").unwrap();
+		image.save("quizsilhouette.PNG")?;
+
+		Ok(())
 	}
 
 	pub fn guess_name(&self) -> String {
@@ -128,25 +130,30 @@ impl Quiz {
 
 #[command("quiz")]
 #[aliases("q")]
+#[description("Starts a Pokemon quiz")]
 async fn quiz_command(ctx: &Context, msg: &Message) -> CommandResult {
-	let mut player = player::get_player(msg.author.id.0).await;
+	let mut player = player::get_player(msg.author.id.0, msg.guild_id).await?;
+	let quiz_config = config::get().quiz.clone();
 	if player.quiz_reset < Utc::now() {
-		player.quiz_questions = 5 + player.upgrades.quiz_question_amount;
-		let minutes_til_reset = 120 - 10 * player.upgrades.quiz_time_reset;
+		player.quiz_questions = quiz_config.questions + player.upgrades.level("quiz_question_amount");
+		let minutes_til_reset = quiz_config.reset_minutes - quiz_config.reset_minutes_per_level * player.upgrades.level("quiz_time_reset");
 		player.quiz_reset = Utc::now() + Duration::minutes(minutes_til_reset);
 	}
 	if player.quiz_questions <= 0 {
-		let local_timer: DateTime<Local> = DateTime::from(player.quiz_reset);
-		msg.reply(&ctx.http, format!("Your quiz attempts reset **{}**", local_timer.format("%h %d %H:%M"))).await?;
+		msg.reply(&ctx.http, format!("Your quiz attempts reset **{}** ({})", discord_timestamp(&player.quiz_reset, 'f'), discord_timestamp(&player.quiz_reset, 'R'))).await?;
 		return Ok(());
 	}
 	let quiz = Quiz::random_quiz().await;
-	quiz.generate_silhouette(player.light_mode).await;
+	quiz.generate_silhouette(player.light_mode).await?;
+	let content = match player.take_active_item("quiz_hint") {
+		true => format!("Who's that Pokemon?! _Hint: it starts with **{}** and it's from **Gen {}**_", quiz.name.chars().next().unwrap_or(' ').to_uppercase(), quiz.generation),
+		false => String::from("Who's that Pokemon?!")
+	};
 	let mut quiz_msg = msg
 		.channel_id
 		.send_message(&ctx.http, |m| {
 			m
-				.content("Who's that Pokemon?!")
+				.content(content)
 				.add_file("./quizsilhouette.PNG")
 		})
 		.await?;
@@ -158,9 +165,9 @@ async fn quiz_command(ctx: &Context, msg: &Message) -> CommandResult {
 			Err(_) => 0
 		};
 		if guess == &quiz.guess_name() {
-			let reward = 0.1 * player.current_multiplier as f64;
+			let reward = quiz_config.correct_reward * player.current_multiplier as f64;
 			player.quiz_correct += 1;
-			if player.current_multiplier < player.perm_multiplier + (player.upgrades.quiz_mult_limit * 10) {
+			if player.current_multiplier < player.perm_multiplier + (player.upgrades.level("quiz_mult_limit") * quiz_config.mult_limit_per_level) {
 				player.current_multiplier += 1;
 			}
 			player.cash += reward;
@@ -174,7 +181,7 @@ async fn quiz_command(ctx: &Context, msg: &Message) -> CommandResult {
 			.await?;
 
 		} else if gen_guess == quiz.generation {
-			let reward = 0.1 * player.current_multiplier as f64;
+			let reward = quiz_config.generation_reward * player.current_multiplier as f64;
 			player.quiz_correct += 1;
 			player.cash += reward;
 			player.total_cash += reward;
@@ -186,7 +193,7 @@ async fn quiz_command(ctx: &Context, msg: &Message) -> CommandResult {
 			})
 			.await?;
 		} else if guess == "pikachu" && &quiz.guess_name() == "clefairy" {
-			let reward = 1.0 * player.current_multiplier as f64;
+			let reward = quiz_config.clefairy_reward * player.current_multiplier as f64;
 			player.quiz_correct += 1;
 			player.cash += reward;
 			player.total_cash += reward;
@@ -223,7 +230,8 @@ async fn quiz_command(ctx: &Context, msg: &Message) -> CommandResult {
 	player_update.insert("total_cash", player.total_cash);
 	player_update.insert("quiz_questions", player.quiz_questions);
 	player_update.insert("quiz_reset", player.quiz_reset);
-	player::update_player(&player, doc! { "$set": player_update}).await;
+	player_update.insert("active_items", player.active_items.clone());
+	player::update_player(&player, doc! { "$set": player_update}).await?;
 
 	Ok(())
 }
\ No newline at end of file
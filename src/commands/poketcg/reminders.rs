@@ -0,0 +1,308 @@
+use std::{
+	collections::HashMap,
+	sync::Arc
+};
+use chrono::{
+	DateTime,
+	Duration,
+	Timelike,
+	Utc
+};
+use mongodb::bson::{
+	doc,
+	Document,
+	to_document
+};
+use serde::{Serialize, Deserialize};
+use serenity::{
+	framework::{
+		standard::{
+			macros::{
+				command
+			},
+			Args,
+			CommandResult
+		},
+	},
+	model::{
+		channel::{
+			Message,
+		},
+		id::UserId,
+	},
+	prelude::*
+};
+
+use crate::{
+	error::BotResult,
+	logging
+};
+
+use super::{
+	config,
+	discord_timestamp,
+	player::{
+		Player,
+		get_player,
+		get_players_with_reminders,
+		update_player
+	},
+	store,
+	timers
+};
+
+// The reminder types, in the order they're shown
+const REMINDER_TYPES: &'static [&str] = &["daily", "streak", "quiz", "slots", "store"];
+
+// How long before a streak expires to warn about it
+static STREAK_WARNING_HOURS: i64 = 2;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Reminders {
+	pub daily: bool,
+	pub streak: bool,
+	pub quiz: bool,
+	pub slots: bool,
+	pub store: bool,
+	// Hours in the player's timezone when nothing is sent. Equal hours means no quiet hours
+	pub quiet_start: u32,
+	pub quiet_end: u32,
+	// The reset each reminder was last sent for, so a reset is only ever reminded once
+	#[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime_optional")]
+	pub daily_sent: Option<DateTime<Utc>>,
+	#[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime_optional")]
+	pub streak_sent: Option<DateTime<Utc>>,
+	#[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime_optional")]
+	pub quiz_sent: Option<DateTime<Utc>>,
+	#[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime_optional")]
+	pub slots_sent: Option<DateTime<Utc>>,
+	#[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime_optional")]
+	pub store_sent: Option<DateTime<Utc>>
+}
+
+impl Reminders {
+	pub fn is_enabled(&self, reminder: &str) -> bool {
+		match reminder {
+			"daily" => self.daily,
+			"streak" => self.streak,
+			"quiz" => self.quiz,
+			"slots" => self.slots,
+			"store" => self.store,
+			_ => false
+		}
+	}
+
+	fn set_enabled(&mut self, reminder: &str, enabled: bool) {
+		match reminder {
+			"daily" => self.daily = enabled,
+			"streak" => self.streak = enabled,
+			"quiz" => self.quiz = enabled,
+			"slots" => self.slots = enabled,
+			"store" => self.store = enabled,
+			_ => ()
+		}
+	}
+
+	fn sent_marker(&mut self, reminder: &str) -> &mut Option<DateTime<Utc>> {
+		match reminder {
+			"daily" => &mut self.daily_sent,
+			"streak" => &mut self.streak_sent,
+			"quiz" => &mut self.quiz_sent,
+			"slots" => &mut self.slots_sent,
+			_ => &mut self.store_sent
+		}
+	}
+
+	fn is_quiet_hour(&self, hour: u32) -> bool {
+		if self.quiet_start == self.quiet_end {
+			false
+		} else if self.quiet_start < self.quiet_end {
+			self.quiet_start <= hour && hour < self.quiet_end
+		} else {
+			hour >= self.quiet_start || hour < self.quiet_end
+		}
+	}
+
+	pub fn to_doc(&self) -> Document {
+		to_document(self).unwrap()
+	}
+}
+
+struct EconomyResets {
+	slots: DateTime<Utc>,
+	store: DateTime<Utc>
+}
+
+async fn get_economy_resets(economy: i64) -> BotResult<EconomyResets> {
+	// Timers and stores hold the next reset, the one that already happened was a day earlier
+	let timer = timers::get_timer(economy).await?;
+	let store = store::get_store(economy).await?;
+
+	Ok(EconomyResets {
+		slots: timer.slot_reset - Duration::days(1),
+		store: store.reset - Duration::days(1)
+	})
+}
+
+fn due_reminder(player: &Player, reminder: &str, resets: &EconomyResets, now: DateTime<Utc>) -> Option<(DateTime<Utc>, String)> {
+	// Returns the reset this reminder is for and the message, if it should be sent now
+	match reminder {
+		"daily" if player.daily_reset <= now => Some((player.daily_reset, String::from("Your daily is ready! Use **.daily** to claim it"))),
+		"streak" if player.daily_reset <= now && player.daily_streak > 0 => {
+			let hours_til_expiry = config::get().daily.reset_hours - player.upgrades.level("daily_time_reset");
+			let expires = player.daily_reset + Duration::hours(hours_til_expiry);
+			match expires - now <= Duration::hours(STREAK_WARNING_HOURS) && expires > now {
+				true => Some((player.daily_reset, format!("Your **{}** day daily streak expires {}! Use **.daily** to keep it", player.daily_streak, discord_timestamp(&expires, 'R')))),
+				false => None
+			}
+		},
+		"quiz" if player.quiz_reset <= now => Some((player.quiz_reset, String::from("Your quiz attempts have reset! Use **.quiz** to play"))),
+		"slots" if resets.slots <= now => Some((resets.slots, String::from("Your slot rolls have been refilled! Use **.gamecorner slots** to play"))),
+		"store" if resets.store <= now => Some((resets.store, String::from("The store has new packs! Use **.store** to see them"))),
+		_ => None
+	}
+}
+
+pub async fn send_reminders(ctx: Arc<Context>) {
+	let players = match get_players_with_reminders().await {
+		Ok(x) => x,
+		Err(why) => {
+			error!(target: logging::SCHEDULER, "Couldn't get the players with reminders: {}", why);
+			return;
+		}
+	};
+	let now = Utc::now();
+	let mut economy_resets = HashMap::new();
+	let mut sent = 0;
+	for mut player in players {
+		if !economy_resets.contains_key(&player.economy) {
+			match get_economy_resets(player.economy).await {
+				Ok(x) => economy_resets.insert(player.economy, x),
+				Err(why) => {
+					error!(target: logging::SCHEDULER, "Couldn't get the resets of economy {}: {}", player.economy, why);
+					continue;
+				}
+			};
+		}
+		let resets = economy_resets.get(&player.economy).unwrap();
+		let quiet = player.reminders.is_quiet_hour(now.with_timezone(&player.tz()).hour());
+		let mut messages = vec![];
+		let mut changed = false;
+		for reminder in REMINDER_TYPES {
+			if !player.reminders.is_enabled(reminder) {
+				continue;
+			}
+			let (reset, message) = match due_reminder(&player, reminder, resets, now) {
+				Some(x) => x,
+				None => continue
+			};
+			let marker = player.reminders.sent_marker(reminder);
+			match *marker {
+				// Already reminded about this reset
+				Some(x) if x == reset => continue,
+				// A reminder that was just turned on doesn't fire for a reset that already happened
+				None => {
+					*marker = Some(reset);
+					changed = true;
+					continue;
+				},
+				_ => ()
+			}
+			// Held until the quiet hours are over
+			if quiet {
+				continue;
+			}
+			*marker = Some(reset);
+			changed = true;
+			messages.push(message);
+		}
+		if messages.len() > 0 {
+			let dm = UserId(player.discord_id as u64).create_dm_channel(&ctx.http).await;
+			if let Ok(channel) = dm {
+				let _ = channel.say(&ctx.http, messages.join("\n")).await;
+				sent += 1;
+			}
+		}
+		if changed {
+			if let Err(why) = update_player(&player, doc! { "$set": { "reminders": player.reminders.to_doc() } }).await {
+				warn!(target: logging::SCHEDULER, "Couldn't save reminders for {}: {}", player.discord_id, why);
+			}
+		}
+	}
+	if sent > 0 {
+		info!(target: logging::SCHEDULER, "Sent reminders to {} players", sent);
+	}
+}
+
+#[command("reminders")]
+#[aliases("reminder", "r")]
+#[description("Toggles the reminders you get as DMs")]
+#[usage("[daily | streak | quiz | slots | store] [on | off]")]
+#[example("daily on")]
+async fn settings_reminders(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let reminder = match args.single::<String>() {
+		Ok(x) => x.to_lowercase(),
+		Err(_) => String::from("")
+	};
+	if !REMINDER_TYPES.contains(&reminder.as_str()) {
+		let mut content = String::from("Here are your reminders, they're sent as DMs:\n");
+		for reminder in REMINDER_TYPES {
+			let state = match player.reminders.is_enabled(reminder) {
+				true => "on",
+				false => "off"
+			};
+			content.push_str(&format!("**{}:** {}\n", reminder, state));
+		}
+		match player.reminders.quiet_start == player.reminders.quiet_end {
+			true => content.push_str("**Quiet hours:** none\n\n"),
+			false => content.push_str(&format!("**Quiet hours:** {:02}:00 - {:02}:00 ({})\n\n", player.reminders.quiet_start, player.reminders.quiet_end, player.timezone))
+		}
+		content.push_str("**.settings reminders <daily | streak | quiz | slots | store> [on | off]** to toggle a reminder\n");
+		content.push_str("**.settings quiet <start hour> <end hour>** to set your quiet hours, or **.settings quiet off**");
+		msg.reply(&ctx.http, content).await?;
+		return Ok(());
+	}
+	let enabled = match args.single::<String>() {
+		Ok(x) => x.to_lowercase() == "on",
+		Err(_) => !player.reminders.is_enabled(&reminder)
+	};
+	player.reminders.set_enabled(&reminder, enabled);
+	update_player(&player, doc! { "$set": { "reminders": player.reminders.to_doc() } }).await?;
+	match enabled {
+		true => msg.reply(&ctx.http, format!("Turned on **{}** reminders. Make sure you can get DMs from this server!", reminder)).await?,
+		false => msg.reply(&ctx.http, format!("Turned off **{}** reminders", reminder)).await?
+	};
+
+	Ok(())
+}
+
+#[command("quiet")]
+#[aliases("q")]
+#[description("Sets the hours when no reminders are sent")]
+#[usage("<start hour> <end hour> | off")]
+#[example("22 8")]
+#[example("off")]
+async fn settings_quiet(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+	let (start, end) = match args.current().map(|a| a.to_lowercase()) {
+		Some(x) if x == "off" => (0, 0),
+		_ => match (args.single::<u32>(), args.single::<u32>()) {
+			(Ok(s), Ok(e)) if s < 24 && e < 24 => (s, e),
+			_ => {
+				msg.reply(&ctx.http, "Quiet hours need a start and end hour from **0** to **23**, or **off**").await?;
+				return Ok(());
+			}
+		}
+	};
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
+	player.reminders.quiet_start = start;
+	player.reminders.quiet_end = end;
+	update_player(&player, doc! { "$set": { "reminders": player.reminders.to_doc() } }).await?;
+	match start == end {
+		true => msg.reply(&ctx.http, "You don't have quiet hours").await?,
+		false => msg.reply(&ctx.http, format!("Reminders won't be sent from **{:02}:00** to **{:02}:00** ({})", start, end, player.timezone)).await?
+	};
+
+	Ok(())
+}
@@ -0,0 +1,337 @@
+use std::{
+	collections::HashSet,
+	sync::{
+		Arc,
+		Mutex
+	},
+	time::Duration as StdDuration
+};
+use chrono::{
+	DateTime,
+	Duration,
+	Local,
+	Utc
+};
+use futures::future::BoxFuture;
+use mongodb::{
+	bson::{
+		doc,
+		oid::ObjectId
+	},
+	Collection
+};
+use serde::{Serialize, Deserialize};
+use serenity::{
+	framework::{
+		standard::{
+			macros::{
+				command
+			},
+			CommandResult
+		},
+	},
+	model::{
+		channel::{
+			Message,
+		},
+	},
+	utils::{
+		Colour
+	},
+	prelude::*
+};
+
+use crate::{
+	commands::get_client,
+	error::BotResult,
+	logging,
+	BOTTEST_CHECK
+};
+
+use super::{
+	refresh_dailys,
+	refresh_card_prices,
+	check_daily_streaks,
+	store,
+	slot,
+	timers,
+	reminders::send_reminders
+};
+
+// How often the scheduler checks for jobs that are due
+static TICK_SECONDS: u64 = 30;
+
+lazy_static! {
+	// Jobs currently running in this process, so a slow job is never started twice
+	static ref RUNNING: Mutex<HashSet<&'static str>> = Mutex::new(HashSet::new());
+}
+
+pub enum Schedule {
+	Every(Duration),
+	// Hour and minute, in UTC
+	DailyAt(u32, u32),
+}
+
+impl Schedule {
+	/// When the job is next due. A job that has never run is due right away.
+	pub fn next_run(&self, last_run: Option<DateTime<Utc>>) -> DateTime<Utc> {
+		let last_run = match last_run {
+			Some(x) => x,
+			None => return Utc::now()
+		};
+		match self {
+			Schedule::Every(interval) => last_run + *interval,
+			Schedule::DailyAt(hour, minute) => {
+				let at = last_run.date_naive().and_hms_opt(*hour, *minute, 0).unwrap().and_utc();
+				if at > last_run {
+					at
+				} else {
+					at + Duration::days(1)
+				}
+			}
+		}
+	}
+
+	fn display(&self) -> String {
+		match self {
+			Schedule::Every(interval) => match interval.num_seconds() {
+				s if s % 3600 == 0 => format!("every {}h", s / 3600),
+				s if s % 60 == 0 => format!("every {}m", s / 60),
+				s => format!("every {}s", s)
+			},
+			Schedule::DailyAt(hour, minute) => format!("daily at {:02}:{:02} UTC", hour, minute)
+		}
+	}
+}
+
+pub struct Job {
+	pub name: &'static str,
+	pub schedule: Schedule,
+	pub run: fn(Arc<Context>) -> BoxFuture<'static, ()>
+}
+
+async fn reset_stores(ctx: Arc<Context>) {
+	// Stores reset lazily when they're viewed, this makes sure they're fresh for every economy at midnight
+	let timers = match timers::get_timers().await {
+		Ok(x) => x,
+		Err(why) => {
+			error!(target: logging::SCHEDULER, "Couldn't get the timers to reset stores: {}", why);
+			return;
+		}
+	};
+	for timer in timers {
+		if let Err(why) = store::get_store(timer.economy).await {
+			error!(target: logging::SCHEDULER, "Couldn't reset the store in economy {}: {}", timer.economy, why);
+		}
+		if let Err(why) = slot::get_token_shop(&ctx, timer.economy).await {
+			error!(target: logging::SCHEDULER, "Couldn't reset the token shop in economy {}: {}", timer.economy, why);
+		}
+	}
+}
+
+fn jobs() -> Vec<Job> {
+	vec![
+		Job {
+			name: "dailies",
+			schedule: Schedule::Every(Duration::minutes(1)),
+			run: |ctx| Box::pin(refresh_dailys(ctx))
+		},
+		Job {
+			name: "card_prices",
+			schedule: Schedule::Every(Duration::hours(1)),
+			run: |ctx| Box::pin(refresh_card_prices(ctx))
+		},
+		Job {
+			name: "daily_streaks",
+			schedule: Schedule::Every(Duration::minutes(30)),
+			run: |ctx| Box::pin(check_daily_streaks(ctx))
+		},
+		Job {
+			name: "store_reset",
+			schedule: Schedule::DailyAt(0, 1),
+			run: |ctx| Box::pin(reset_stores(ctx))
+		},
+		Job {
+			name: "reminders",
+			schedule: Schedule::Every(Duration::minutes(5)),
+			run: |ctx| Box::pin(send_reminders(ctx))
+		},
+	]
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JobRecord {
+	#[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+	id: Option<ObjectId>,
+	pub name: String,
+	#[serde(default, with = "bson::serde_helpers::chrono_datetime_as_bson_datetime_optional")]
+	pub last_run: Option<DateTime<Utc>>,
+	#[serde(default)]
+	pub last_duration_ms: i64,
+	#[serde(default)]
+	pub runs: i64
+}
+
+impl JobRecord {
+	fn new(name: &str) -> Self {
+		Self {
+			id: None,
+			name: String::from(name),
+			last_run: None,
+			last_duration_ms: 0,
+			runs: 0
+		}
+	}
+}
+
+async fn get_job_record_collection() -> BotResult<Collection<JobRecord>> {
+	let client = get_client().await?;
+	let collection = client.database("poketcg").collection::<JobRecord>("jobs");
+
+	Ok(collection)
+}
+
+pub async fn get_job_record(name: &str) -> BotResult<JobRecord> {
+	let job_record_collection = get_job_record_collection().await?;
+	let job_record = job_record_collection
+		.find_one(doc! { "name": name }, None)
+		.await?;
+	match job_record {
+		Some(x) => Ok(x),
+		None => add_job_record(name).await
+	}
+}
+
+async fn add_job_record(name: &str) -> BotResult<JobRecord> {
+	let mut ret = JobRecord::new(name);
+	let job_record_collection = get_job_record_collection().await?;
+	let result = job_record_collection
+		.insert_one(&ret, None)
+		.await?;
+	ret.id = result.inserted_id.as_object_id();
+
+	Ok(ret)
+}
+
+async fn update_job_record(job_record: &JobRecord) -> BotResult<()> {
+	let job_record_collection = get_job_record_collection().await?;
+	job_record_collection
+		.update_one(
+			doc! { "name": &job_record.name },
+			doc! { "$set": {
+				"last_run": job_record.last_run,
+				"last_duration_ms": job_record.last_duration_ms,
+				"runs": job_record.runs
+			}},
+			None)
+		.await?;
+
+	Ok(())
+}
+
+fn is_running(name: &str) -> bool {
+	RUNNING.lock().unwrap().contains(name)
+}
+
+// Marks a job as running until it's dropped, so the job can run again even if recording it fails
+struct RunningGuard(&'static str);
+
+impl RunningGuard {
+	fn new(name: &'static str) -> Self {
+		RUNNING.lock().unwrap().insert(name);
+
+		Self(name)
+	}
+}
+
+impl Drop for RunningGuard {
+	fn drop(&mut self) {
+		RUNNING.lock().unwrap().remove(self.0);
+	}
+}
+
+async fn record_run(name: &str, started: DateTime<Utc>) -> BotResult<()> {
+	let mut job_record = get_job_record(name).await?;
+	job_record.last_run = Some(started);
+	job_record.last_duration_ms = (Utc::now() - started).num_milliseconds();
+	job_record.runs += 1;
+	update_job_record(&job_record).await
+}
+
+async fn run_job(ctx: Arc<Context>, job: Job, _running: RunningGuard) {
+	let started = Utc::now();
+	// Run on its own task so a job that panics is logged instead of taking the scheduler with it
+	let result = tokio::spawn((job.run)(ctx)).await;
+	#[cfg(feature = "metrics")]
+	crate::metrics::record_job(job.name, (Utc::now() - started).to_std().unwrap_or_default(), result.is_ok());
+	match result {
+		Ok(()) => debug!(target: logging::SCHEDULER, "Job {} finished in {}ms", job.name, (Utc::now() - started).num_milliseconds()),
+		Err(why) => error!(target: logging::SCHEDULER, "Job {} failed: {}", job.name, why)
+	}
+	if let Err(why) = record_run(job.name, started).await {
+		error!(target: logging::SCHEDULER, "Couldn't record the run of job {}: {}", job.name, why);
+	}
+}
+
+/// Runs the scheduler loop forever. Jobs that were due while the bot was down
+/// run once on the first tick, then keep to their schedule from there.
+pub async fn run_scheduler(ctx: Arc<Context>) {
+	loop {
+		let now = Utc::now();
+		for job in jobs() {
+			if is_running(job.name) {
+				continue;
+			}
+			let job_record = match get_job_record(job.name).await {
+				Ok(x) => x,
+				Err(why) => {
+					error!(target: logging::SCHEDULER, "Couldn't get the record of job {}: {}", job.name, why);
+					continue;
+				}
+			};
+			if job.schedule.next_run(job_record.last_run) > now {
+				continue;
+			}
+			let running = RunningGuard::new(job.name);
+			tokio::spawn(run_job(Arc::clone(&ctx), job, running));
+		}
+		tokio::time::sleep(StdDuration::from_secs(TICK_SECONDS)).await;
+	}
+}
+
+#[command("jobs")]
+#[checks(BotTest)]
+#[description("Shows the scheduled jobs")]
+async fn admin_jobs(ctx: &Context, msg: &Message) -> CommandResult {
+	let mut desc = String::from("");
+	for job in jobs() {
+		let job_record = get_job_record(job.name).await?;
+		let last_run = match job_record.last_run {
+			Some(x) => {
+				let local: DateTime<Local> = DateTime::from(x);
+				local.format("%m/%d %H:%M:%S").to_string()
+			},
+			None => String::from("never")
+		};
+		let next_run: DateTime<Local> = DateTime::from(job.schedule.next_run(job_record.last_run));
+		let status = match is_running(job.name) {
+			true => " ***RUNNING***",
+			false => ""
+		};
+		desc.push_str(&format!("**{}**{} _({})_\n", job.name, status, job.schedule.display()));
+		desc.push_str(&format!("Last run: {} ({}ms) | Runs: {} | Next run: {}\n\n", last_run, job_record.last_duration_ms, job_record.runs, next_run.format("%m/%d %H:%M:%S")));
+	}
+	msg
+		.channel_id
+		.send_message(&ctx.http, |m| {
+			m.embed(|e| {
+				e
+					.title("Scheduled Jobs")
+					.description(&desc)
+					.colour(Colour::from_rgb(255, 50, 20))
+			})
+		})
+		.await?;
+
+	Ok(())
+}
@@ -53,7 +53,10 @@ pub struct Set {
 	pub logo: String,
 	pub symbol: String,
 	#[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
-	pub release_date: DateTime<Utc>
+	pub release_date: DateTime<Utc>,
+	// The set code used in PTCGL/PTCGO decklists, eg. "SVI". Older sets don't have one
+	#[serde(default)]
+	pub ptcgo_code: String
 }
 
 impl Set {
@@ -72,7 +75,8 @@ impl Set {
 					.unwrap()
 					.and_hms(0, 0, 0),
 				Utc
-			)
+			),
+			ptcgo_code: String::from(obj["ptcgoCode"].as_str().unwrap_or(""))
 		}
 	}
 	
@@ -120,7 +124,7 @@ impl PartialEq for Set {
 
 #[async_trait]
 impl Scrollable for Vec<Set> {
-	async fn scroll_through(&self, ctx: &Context, msg: &Message) -> Result<(), String> {
+	async fn scroll_through(&self, ctx: &Context, msg: &Message) -> BotResult<()> {
 		let left_arrow = ReactionType::try_from("⬅️").expect("No left arrow");
 		let right_arrow = ReactionType::try_from("➡️").expect("No right arrow");
 		let pokemon_card = ReactionType::try_from("<:poketcg:965802882433703936>").expect("No TCG Back");
@@ -216,27 +220,23 @@ pub async fn get_sets() -> Vec<Set> {
 	ret
 }
 
-pub async fn get_set(id: &str) -> Option<Set> {
-	let cached_set = get_set_from_db(id).await;
-	match cached_set {
-		Some(s) => Some(s),
-		None => {
-			let data = api_call(&format!("sets/{}", id), None)
-				.await
-				.unwrap();
-			let set_data = data.get("data");
-			let set = match set_data {
-				Some(x) => {
-					let s = Set::from_json(x);
-					add_set(&s).await;
-					
-					Some(s)
-				},
-				None => None
-			};
-		
-			set
-		}
+pub async fn get_set(id: &str) -> BotResult<Set> {
+	if let Some(set) = get_set_from_db(id).await? {
+		return Ok(set);
+	}
+	// An empty id would ask the API for every set
+	if id.trim() == "" {
+		return Err(Error::NotFound(String::from("set with that id")));
+	}
+	let data = api_call(&format!("sets/{}", id), None).await?;
+	match data.get("data") {
+		Some(x) if x.is_object() => {
+			let set = Set::from_json(x);
+			add_set(&set).await;
+
+			Ok(set)
+		},
+		_ => Err(Error::NotFound(format!("set with the id **{}**", id)))
 	}
 }
 
@@ -278,14 +278,13 @@ async fn add_sets(sets: &Vec<Set>) {
 		.unwrap();
 }
 
-async fn get_set_from_db(id: &str) -> Option<Set> {
+async fn get_set_from_db(id: &str) -> BotResult<Option<Set>> {
 	let set_collection = get_set_collection().await;
 	let set = set_collection
 		.find_one(doc! { "set_id": id }, None)
-		.await
-		.unwrap();
+		.await?;
 
-	set
+	Ok(set)
 }
 
 async fn get_sets_from_db() -> Vec<Set> {
@@ -302,6 +301,10 @@ async fn get_sets_from_db() -> Vec<Set> {
 }
 
 #[command("set")]
+#[description("Searches for a set with a matching name")]
+#[usage("<name>")]
+#[example("evolving skies")]
+#[min_args(1)]
 async fn search_set(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
 	let search_str = args.rest();
 	let sets = get_sets_with_query(&format!("{}", search_str))
@@ -317,6 +320,7 @@ async fn search_set(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
 }
 
 #[command("sets")]
+#[description("Shows all the sets")]
 async fn sets_command(ctx: &Context, msg: &Message) -> CommandResult {
 	let sets = get_sets().await;
 	// set_paginated_embeds(ctx, msg, sets).await?;
@@ -326,16 +330,14 @@ async fn sets_command(ctx: &Context, msg: &Message) -> CommandResult {
 }
 
 #[command("set")]
+#[description("Shows a set")]
+#[usage("<set id>")]
+#[example("swsh1")]
+#[min_args(1)]
 async fn set_command(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
 	let set_id = args.rest();
-	let set = get_set(set_id).await;
-	match set {
-		// Some(x) => set_paginated_embeds(ctx, msg, vec![x]).await?,
-		Some(x) => vec![x].scroll_through(ctx, msg).await?,
-		None => {
-			msg.reply(&ctx.http, "No set found with that id.").await?;
-		}
-	}
+	let set = get_set(set_id).await?;
+	vec![set].scroll_through(ctx, msg).await?;
 
 	Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,81 @@
+use chrono::Utc;
+use chrono_tz::Tz;
+use mongodb::bson::{
+	doc,
+	Document
+};
+use serenity::{
+	framework::{
+		standard::{
+			macros::{
+				command
+			},
+			Args,
+			CommandResult
+		},
+	},
+	model::{
+		channel::{
+			Message,
+		},
+	},
+	prelude::*
+};
+
+use super::{
+	player::{
+		get_player,
+		update_player
+	},
+	reminders::{
+		SETTINGS_REMINDERS_COMMAND,
+		SETTINGS_QUIET_COMMAND
+	}
+};
+
+#[command("settings")]
+#[sub_commands(settings_timezone, settings_reminders, settings_quiet)]
+#[description("Shows your settings")]
+async fn settings_main(ctx: &Context, msg: &Message) -> CommandResult {
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let mut content = String::from("Here are your settings:\n");
+	content.push_str(&format!("**Timezone:** {} _(it's {} for you)_\n", player.timezone, player.format_time(Utc::now())));
+	content.push_str(&format!("**Light mode:** {}\n\n", player.light_mode));
+	content.push_str("**.settings timezone <timezone>** to change your timezone, eg. America/Toronto\n");
+	content.push_str("**.settings reminders** to choose which reminders you get as DMs\n");
+	content.push_str("**.lightmode** to toggle light mode");
+	msg.reply(&ctx.http, content).await?;
+
+	Ok(())
+}
+
+#[command("timezone")]
+#[aliases("tz")]
+#[description("Sets your timezone, used for reset times")]
+#[usage("<timezone>")]
+#[example("America/Toronto")]
+#[min_args(1)]
+async fn settings_timezone(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+	let timezone = match args.single::<String>() {
+		Ok(x) => x,
+		Err(_) => {
+			msg.reply(&ctx.http, "No timezone provided. Use a name from the tz database, eg. **America/Toronto** or **Europe/London**").await?;
+			return Ok(());
+		}
+	};
+	let tz = match timezone.parse::<Tz>() {
+		Ok(x) => x,
+		Err(_) => {
+			msg.reply(&ctx.http, format!("**{}** isn't a timezone I know. Use a name from the tz database, eg. **America/Toronto** or **Europe/London**", timezone)).await?;
+			return Ok(());
+		}
+	};
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
+	player.timezone = tz.name().to_string();
+	let mut update = Document::new();
+	update.insert("timezone", &player.timezone);
+	update_player(&player, doc! { "$set": update }).await?;
+	msg.reply(&ctx.http, format!("Your timezone is now **{}**, it's {} for you", player.timezone, player.format_time(Utc::now()))).await?;
+
+	Ok(())
+}
@@ -1,4 +1,7 @@
-use std::collections::HashMap;
+use std::{
+	collections::HashMap,
+	cmp::min
+};
 use serenity::{
 	framework::{
 		standard::{
@@ -37,7 +40,6 @@ use chrono::{
 	Utc,
 	Datelike,
 	Duration,
-	Local,
 };
 use rand::{
 	seq::{
@@ -46,6 +48,11 @@ use rand::{
 	prelude::*
 };
 use crate::{
+	error::{
+		BotResult,
+		Error
+	},
+	logging,
 	sets::{
 		Set,
 		get_sets,
@@ -62,10 +69,29 @@ use crate::{
 		update_player
 	},
 	commands::get_client,
-	timers
+	timers,
+	config
+};
+
+use super::{
+	Idable,
+	guild::economy_filter,
+	upgrade::find_upgrade,
+	prestige::GAME_CORNER_TOKENS_PRESTIGE_COMMAND,
+	items::{
+		ITEMS,
+		find_item,
+		give_item
+	}
 };
 
-use super::Idable;
+fn def_0() -> i64 {
+	0
+}
+
+fn def_item() -> String {
+	String::from("streak_freeze")
+}
 
 const SLOT_OPTIONS: &'static [&str] = &[
 	"7",
@@ -100,10 +126,10 @@ pub struct Slot {
 }
 
 impl Slot {
-	pub fn new(amount: i64) -> Self {
+	pub fn new(amount: i64, luck_level: i64) -> Self {
 		let mut rolls = vec![];
 		for _ in 0..amount {
-			let roll = SlotRoll::new();
+			let roll = SlotRoll::new(luck_level);
 			rolls.push(roll);
 		}
 
@@ -119,16 +145,32 @@ pub struct SlotRoll {
 	pub slot3: String
 }
 
+// The 7 and R get extra weight from the rare_slot_luck upgrade
+fn slot_weight(slot_option: &str, weight: i32, luck: i32) -> i32 {
+	match slot_option {
+		"7" | "R" => weight + luck,
+		_ => weight
+	}
+}
+
 impl SlotRoll {
-	pub fn new() -> Self {
-		let slot1 = SLOT_OPTIONS
-			.choose(&mut thread_rng()).unwrap().to_string();
+	pub fn new(luck_level: i64) -> Self {
+		let luck = (luck_level * config::get().effects.slot_luck_per_level) as i32;
+		let base_weights: Vec<(&&str, i32)> = SLOT_OPTIONS
+			.iter()
+			.map(|so| (so, slot_weight(so, DEFAULT_WEIGHT, luck)))
+			.collect();
+		let slot1 = base_weights
+			.choose_weighted(&mut thread_rng(), |sw| sw.1)
+			.unwrap()
+			.0
+			.to_string();
 		let mut slot_weights = vec![];
 		for slot_option in SLOT_OPTIONS {
 			if slot_option.to_string() == slot1 {
 				slot_weights.push((slot_option, FAVOURED_WEIGHT));
 			} else {
-				slot_weights.push((slot_option, DEFAULT_WEIGHT));
+				slot_weights.push((slot_option, slot_weight(slot_option, DEFAULT_WEIGHT, luck)));
 			}
 		}
 		let slot2 = slot_weights
@@ -143,7 +185,11 @@ impl SlotRoll {
 				.0
 				.to_string()
 		} else {
-			SLOT_OPTIONS.choose(&mut thread_rng()).unwrap().to_string()
+			base_weights
+				.choose_weighted(&mut thread_rng(), |sw| sw.1)
+				.unwrap()
+				.0
+				.to_string()
 		};
 
 		Self {
@@ -154,16 +200,12 @@ impl SlotRoll {
 	}
 
 	pub fn reward(&self, upgrade_level: i64) -> i64 {
-		let reward_mult = 1.0 + upgrade_level as f64 * 0.1;
+		let economy_config = config::get();
+		let slot_config = &economy_config.slots;
+		let reward_mult = 1.0 + upgrade_level as f64 * slot_config.reward_mult_per_level;
 		match (self.slot1.as_str(), self.slot2.as_str(), self.slot3.as_str()) {
-			("7", "7", "7") => (500.0 * reward_mult) as i64,
-			("R", "R", "R") => (200.0 * reward_mult) as i64,
-			("Pikachu", "Pikachu", "Pikachu") => (120.0 * reward_mult) as i64,
-			("Slowpoke", "Slowpoke", "Slowpoke") => (80.0 * reward_mult) as i64,
-			("Magnemite", "Magnemite", "Magnemite") => (50.0 * reward_mult) as i64,
-			("Shellder", "Shellder", "Shellder") => (30.0 * reward_mult) as i64,
-			("Cherry", "Cherry", "Cherry") => (15.0 * reward_mult) as i64,
-			("Cherry", "Cherry", _) | ("Cherry", _, "Cherry") | (_, "Cherry", "Cherry") => (5.0 * reward_mult) as i64,
+			(a, b, c) if a == b && b == c => (slot_config.payout(a) * reward_mult) as i64,
+			("Cherry", "Cherry", _) | ("Cherry", _, "Cherry") | (_, "Cherry", "Cherry") => (slot_config.payout("CherryPair") * reward_mult) as i64,
 			_ => 0
 		}
 	}
@@ -190,24 +232,30 @@ pub struct TokenShop {
 	pub rare_card: String,
 	pub rainbow_card: String,
 	#[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
-	pub reset: DateTime<Utc>
+	pub reset: DateTime<Utc>,
+	#[serde(default = "def_0")]
+	pub economy: i64,
+	#[serde(default = "def_item")]
+	pub item: String
 }
 
 impl TokenShop {
-	async fn new(ctx: &Context) -> Self {
+	async fn new(ctx: &Context, economy: i64) -> BotResult<Self> {
 		let sets = get_sets().await;
 		let mut weighted_sets = vec![];
 		for set in sets {
 			let weight = set.release_date.year() - 1998;
 			weighted_sets.push((set, weight));
 		}
+		// Enough sets for a player with every tokenshop_slot upgrade, the rest are hidden
+		let set_count = config::get().effects.tokenshop_slots + find_upgrade("tokenshop_slot").unwrap().tuning().max_level;
 		let store_sets = weighted_sets
 			.choose_multiple_weighted(
 				&mut thread_rng(),
-				3,
+				set_count as usize,
 				|ws| ws.1
 			)
-			.unwrap()
+			.map_err(|_| Error::NotFound(String::from("sets for the token shop")))?
 			.collect::<Vec<_>>()
 			.iter()
 			.map(|ws| ws.0.clone())
@@ -217,71 +265,87 @@ impl TokenShop {
 			.collect();
 		let rare_cards = get_rare_cards_from_cache(ctx).await;
 		let rainbows = get_rainbow_cards_from_cache(ctx).await;
-		let rare_card = rare_cards
-			.iter()
-			.choose(&mut thread_rng())
-			.unwrap()
-			.clone()
-			.id();
-		let rainbow_card = rainbows
+		let rare_card = match rare_cards.iter().choose(&mut thread_rng()) {
+			Some(x) => x.id(),
+			None => return Err(Error::NotFound(String::from("rare card for the token shop")))
+		};
+		let rainbow_card = match rainbows.iter().choose(&mut thread_rng()) {
+			Some(x) => x.id(),
+			None => return Err(Error::NotFound(String::from("rainbow card for the token shop")))
+		};
+		let item = ITEMS
 			.iter()
+			.filter(|i| i.price().tokens.is_some())
 			.choose(&mut thread_rng())
-			.unwrap()
-			.clone()
-			.id();
+			.map(|i| String::from(i.key))
+			.unwrap_or(def_item());
 		let now = Utc::now() + Duration::days(1);
 
-		Self {
+		Ok(Self {
 			id: None,
 			sets: store_sets,
 			rare_card,
 			rainbow_card,
-			reset: Utc.ymd(now.year(), now.month(), now.day()).and_hms(0, 0, 0)
-		}
+			reset: Utc.ymd(now.year(), now.month(), now.day()).and_hms(0, 0, 0),
+			economy,
+			item
+		})
 	}
 
-	async fn update_shop(&self, ctx: &Context) -> Self {
-		let tmp_tokenshop = TokenShop::new(ctx).await;
+	async fn update_shop(&self, ctx: &Context) -> BotResult<Self> {
+		let tmp_tokenshop = TokenShop::new(ctx, self.economy).await?;
 		let now = Utc::now() + Duration::days(1);
 
-		Self {
+		Ok(Self {
 			id: self.id,
 			sets: tmp_tokenshop.sets,
 			rare_card: tmp_tokenshop.rare_card,
 			rainbow_card: tmp_tokenshop.rainbow_card,
-			reset: Utc.ymd(now.year(), now.month(), now.day()).and_hms(0, 0, 0)
-		}
+			reset: Utc.ymd(now.year(), now.month(), now.day()).and_hms(0, 0, 0),
+			economy: self.economy,
+			item: tmp_tokenshop.item
+		})
+	}
+
+	/// How many of the shop's sets the player can buy from.
+	pub fn visible_sets(&self, player: &Player) -> usize {
+		let visible = config::get().effects.tokenshop_slots + player.upgrades.level("tokenshop_slot");
+
+		min(visible as usize, self.sets.len())
 	}
 
-	pub async fn embed_with_player(&self, ctx: &Context, player: Player) -> CreateEmbed {
+	pub async fn embed_with_player(&self, ctx: &Context, player: Player) -> BotResult<CreateEmbed> {
 		let mut ret = CreateEmbed::default();
 		let mut desc = String::from("Welcome to the **Token Shop**! Here you can spend tokens for prized\n");
 		desc.push_str(&format!("You have **{}** tokens\n", player.tokens));
 		desc.push_str("Here are the prizes available today. To purchase one, use **.gamecorner tokenstore (b)uy <slot no> [amount - Default 1]**\n\n");
-		let discount = 1.0 + player.upgrades.tokenshop_discount as f64 * 0.05;
-		for (i, set_id) in self.sets.iter().enumerate() {
+		let discount = 1.0 + player.upgrades.level("tokenshop_discount") as f64 * 0.05;
+		let visible_sets = self.visible_sets(&player);
+		for (i, set_id) in self.sets.iter().take(visible_sets).enumerate() {
 			let num = i + 1;
-			let set = get_set(set_id).await.unwrap();
+			let set = get_set(set_id).await?;
 			desc.push_str(&format!("**{}:** {} (_{}_) - {} tokens\n", num, set.name, set.id(), (to_tokens(set.pack_price()) as f64 / discount) as i64));
 		}
 		let rare_card = get_card(ctx, &self.rare_card).await;
-		desc.push_str(&format!("**4:** {} (_{}_) - {} tokens\n", rare_card.name, rare_card.id(), ((to_tokens(rare_card.price) * 10) as f64 / discount) as i64));
+		desc.push_str(&format!("**{}:** {} (_{}_) - {} tokens\n", visible_sets + 1, rare_card.name, rare_card.id(), ((to_tokens(rare_card.price) * 10) as f64 / discount) as i64));
 		let rainbow_card = get_card(ctx, &self.rainbow_card).await;
-		desc.push_str(&format!("**5:** {} (_{}_) - {} tokens", rainbow_card.name, rainbow_card.id(), ((to_tokens(rainbow_card.price) * 10) as f64 / discount) as i64));
+		desc.push_str(&format!("**{}:** {} (_{}_) - {} tokens\n", visible_sets + 2, rainbow_card.name, rainbow_card.id(), ((to_tokens(rainbow_card.price) * 10) as f64 / discount) as i64));
+		if let Some(item) = find_item(&self.item) {
+			desc.push_str(&format!("**{}:** {} (_item_) - {} tokens", visible_sets + 3, item.name, (item.price().tokens.unwrap_or(0) as f64 / discount) as i64));
+		}
+		if player.prestige_points > 0 {
+			desc.push_str(&format!("\n\nYou have **{}** prestige points to spend with **.gamecorner tokenshop prestige**", player.prestige_points));
+		}
 		ret
 			.description(&desc)
 			.colour(Colour::from_rgb(255, 50, 20))
-			.footer(|f| {
-				let local_timer: DateTime<Local> = DateTime::from(self.reset);
-
-				f.text(&format!("Resets {}", local_timer.format("%h %d %H:%M")))
-			})
+			.footer(|f| f.text(&format!("Resets {}", player.format_time(self.reset))))
 			.author(|a| a
 				.icon_url("https://archives.bulbagarden.net/media/upload/9/92/Bag_Coin_Case_Sprite.png")
 				.name("Token Shop")
 			);
 
-		ret
+		Ok(ret)
 	}
 }
 
@@ -291,65 +355,85 @@ pub fn to_tokens(price: f64) -> i64 {
 	inflation as i64
 }
 
-async fn get_token_shop_collection() -> Collection<TokenShop> {
-	let client = get_client().await.unwrap();
+async fn get_token_shop_collection() -> BotResult<Collection<TokenShop>> {
+	let client = get_client().await?;
 	let collection = client.database("poketcg").collection::<TokenShop>("tokenshop");
 
-	collection
+	Ok(collection)
 }
 
-pub async fn get_token_shop(ctx: &Context) -> TokenShop {
-	let token_shop_collection = get_token_shop_collection().await;
-	let token_shop = token_shop_collection
-		.find_one(None, None)
-		.await
-		.unwrap();
-	let token_shop = match token_shop {
+pub async fn get_token_shop(ctx: &Context, economy: i64) -> BotResult<TokenShop> {
+	let token_shop = match find_token_shop(economy).await? {
 		Some(x) => x,
-		None => add_token_shop(ctx).await
+		None => add_token_shop(ctx, economy).await?
 	};
 	if token_shop.reset < Utc::now() {
-		let token_shop = token_shop.update_shop(ctx).await;
-		update_token_shop(&token_shop).await;
-		return token_shop;
+		let token_shop = token_shop.update_shop(ctx).await?;
+		update_token_shop(&token_shop).await?;
+		return Ok(token_shop);
 	}
 	
-	token_shop
+	Ok(token_shop)
 }
 
-async fn add_token_shop(ctx: &Context) -> TokenShop {
-	let ret = TokenShop::new(ctx).await;
-	let token_shop_collection = get_token_shop_collection().await;
-	token_shop_collection
+/// The economy's token shop as it was last stocked, without adding or restocking it.
+pub async fn find_token_shop(economy: i64) -> BotResult<Option<TokenShop>> {
+	let token_shop_collection = get_token_shop_collection().await?;
+	let token_shop = token_shop_collection
+		.find_one(economy_filter(economy), None)
+		.await?;
+
+	Ok(token_shop)
+}
+
+/// Restocks the token shop with new prizes right away.
+pub async fn refresh_token_shop(ctx: &Context, economy: i64) -> BotResult<TokenShop> {
+	let token_shop = get_token_shop(ctx, economy).await?.update_shop(ctx).await?;
+	update_token_shop(&token_shop).await?;
+
+	Ok(token_shop)
+}
+
+async fn add_token_shop(ctx: &Context, economy: i64) -> BotResult<TokenShop> {
+	let mut ret = TokenShop::new(ctx, economy).await?;
+	let token_shop_collection = get_token_shop_collection().await?;
+	let result = token_shop_collection
 		.insert_one(&ret, None)
-		.await
-		.unwrap();
+		.await?;
+	ret.id = result.inserted_id.as_object_id();
 	
-	ret
+	Ok(ret)
 }
 
-async fn update_token_shop(token_shop: &TokenShop) {
-	let token_shop_collection = get_token_shop_collection().await;
+async fn update_token_shop(token_shop: &TokenShop) -> BotResult<()> {
+	let id = match token_shop.id {
+		Some(x) => x,
+		None => return Err(Error::NotFound(String::from("token shop")))
+	};
+	let token_shop_collection = get_token_shop_collection().await?;
 	token_shop_collection
 		.update_one(
-			doc! {"_id": &token_shop.id.unwrap() }, 
+			doc! {"_id": id }, 
 			doc! {"$set": {
 				"sets": &token_shop.sets,
 				"rare_card": &token_shop.rare_card,
 				"rainbow_card": &token_shop.rainbow_card,
-				"reset": &token_shop.reset
+				"reset": &token_shop.reset,
+				"item": &token_shop.item
 			}},
 			None)
-		.await
-		.unwrap();
+		.await?;
+
+	Ok(())
 }
 
 #[command("gamecorner")]
 #[aliases("gc", "game", "corner", "gamec")]
 #[sub_commands(game_corner_payouts, game_corner_slots, game_corner_tokens_main)]
+#[description("Shows the Game Corner commands")]
 async fn game_corner_main(ctx: &Context, msg: &Message) -> CommandResult {
-	let player = get_player(msg.author.id.0).await;
-	let timer = timers::get_timer().await;
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let timer = timers::get_timer(player.economy).await?;
 	let mut desc = String::from("Welcome to the **Game Corner**!\n");
 	desc.push_str("Here you can play the slot machines to earn tokens that you\n");
 	desc.push_str("can convert to cash or spend at the token shop\n\n");
@@ -358,7 +442,8 @@ async fn game_corner_main(ctx: &Context, msg: &Message) -> CommandResult {
 	desc.push_str("**.gamecorner slots:** Rolls the slot machine\n");
 	desc.push_str("**.gamecorner tokenshop:** View the rewards available for purchase\n");
 	desc.push_str("**.gamecorner tokenshop buy:** Buys an item from the token shop\n");
-	desc.push_str("**.gamecorner tokenshop convert:** Converts your tokens to cash\n\n");
+	desc.push_str("**.gamecorner tokenshop convert:** Converts your tokens to cash\n");
+	desc.push_str("**.gamecorner tokenshop prestige:** Spend prestige points on exclusive items\n\n");
 	desc.push_str(&format!("You have **{}** slot rolls remaining", player.daily_slots));
 	msg
 		.channel_id
@@ -367,11 +452,7 @@ async fn game_corner_main(ctx: &Context, msg: &Message) -> CommandResult {
 				e
 					.description(&desc)
 					.colour(Colour::from_rgb(255, 50, 20))
-					.footer(|f| {
-						let local_timer: DateTime<Local> = DateTime::from(timer.slot_reset);
-
-						f.text(&format!("Resets {}", local_timer.format("%h %d %H:%M")))
-					})
+					.footer(|f| f.text(&format!("Resets {}", player.format_time(timer.slot_reset))))
 					.author(|a| a
 						.icon_url("https://archives.bulbagarden.net/media/upload/9/92/Bag_Coin_Case_Sprite.png")
 						.name("Game Corner")
@@ -385,18 +466,19 @@ async fn game_corner_main(ctx: &Context, msg: &Message) -> CommandResult {
 
 #[command("payouts")]
 #[aliases("p")]
+#[description("Shows the slot machine payouts")]
 async fn game_corner_payouts(ctx: &Context, msg: &Message) -> CommandResult {
-	let player = get_player(msg.author.id.0).await;
-	let player_slot_mult = 1.0 + player.upgrades.slot_reward_mult as f64 * 0.1;
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let economy_config = config::get();
+	let slot_config = &economy_config.slots;
+	let player_slot_mult = 1.0 + player.upgrades.level("slot_reward_mult") as f64 * slot_config.reward_mult_per_level;
 	let mut desc = String::from("Here are the token payouts for the slot machines\n");
-	desc.push_str(&format!("<:GameCorner:967522912242384906><:GameCorner:967522912242384906><:GameCorner:967522912242384906> **{}**\n", (500.0 * player_slot_mult) as i64));
-	desc.push_str(&format!("<:GameCorner:967522912166903858><:GameCorner:967522912166903858><:GameCorner:967522912166903858> **{}**\n", (200.0 * player_slot_mult) as i64));
-	desc.push_str(&format!("<:GameCorner:967522912196239510><:GameCorner:967522912196239510><:GameCorner:967522912196239510> **{}**\n", (120.0 * player_slot_mult) as i64));
-	desc.push_str(&format!("<:GameCorner:967522912275922995><:GameCorner:967522912275922995><:GameCorner:967522912275922995> **{}**\n", (80.0 * player_slot_mult) as i64));
-	desc.push_str(&format!("<:GameCorner:967522912154296410><:GameCorner:967522912154296410><:GameCorner:967522912154296410> **{}**\n", (50.0 * player_slot_mult) as i64));
-	desc.push_str(&format!("<:GameCorner:967522912229793882><:GameCorner:967522912229793882><:GameCorner:967522912229793882> **{}**\n", (30.0 * player_slot_mult) as i64));
-	desc.push_str(&format!("<:GameCorner:967522912166871080><:GameCorner:967522912166871080><:GameCorner:967522912166871080> **{}**\n", (15.0 * player_slot_mult) as i64));
-	desc.push_str(&format!("<:GameCorner:967522912166871080><:GameCorner:967522912166871080><:GameCorner:967591653135228988> **{}**", (5.0 * player_slot_mult) as i64));
+	for slot_option in SLOT_OPTIONS {
+		let slot_id = SLOT_OPTION_IDS.get(slot_option).unwrap();
+		desc.push_str(&format!("<:GameCorner:{}><:GameCorner:{}><:GameCorner:{}> **{}**\n", slot_id, slot_id, slot_id, (slot_config.payout(slot_option) * player_slot_mult) as i64));
+	}
+	let cherry_id = SLOT_OPTION_IDS.get("Cherry").unwrap();
+	desc.push_str(&format!("<:GameCorner:{}><:GameCorner:{}><:GameCorner:967591653135228988> **{}**", cherry_id, cherry_id, (slot_config.payout("CherryPair") * player_slot_mult) as i64));
 	msg
 		.channel_id
 		.send_message(&ctx.http, |m| {
@@ -417,8 +499,11 @@ async fn game_corner_payouts(ctx: &Context, msg: &Message) -> CommandResult {
 
 #[command("slots")]
 #[aliases("s")]
+#[description("Rolls the slot machine")]
+#[usage("[amount - Default: 1]")]
+#[example("5")]
 async fn game_corner_slots(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-	let mut player = get_player(msg.author.id.0).await;
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
 	if player.daily_slots <= 0 {
 		msg.reply(&ctx.http, "You're out of slot rolls for today!").await?;
 		return Ok(());
@@ -434,21 +519,24 @@ async fn game_corner_slots(ctx: &Context, msg: &Message, mut args: Args) -> Comm
 	};
 	let amounts = vec![player.daily_slots, amount]; 
 	let amount = *amounts.iter().min().unwrap();
-	let slots = Slot::new(amount);
+	let slots = Slot::new(amount, player.upgrades.level("rare_slot_luck"));
 	let mut roll_displays = vec![];
 	let mut under_2k_reply = String::from("");
 	for roll in slots.rolls {
-		let reward = roll.reward(player.upgrades.slot_reward_mult);
+		let reward = roll.reward(player.upgrades.level("slot_reward_mult"));
 		player.tokens += reward;
 		player.total_tokens += reward;
 		player.slots_rolled += 1;
 		player.daily_slots -= 1;
 		match (roll.slot1.as_str(), roll.slot2.as_str(), roll.slot3.as_str()) {
-			("7", "7", "7") => player.jackpots += 1,
+			("7", "7", "7") => {
+				player.jackpots += 1;
+				info!(target: logging::GAMES, "{} hit a jackpot for {} tokens", player.discord_id, reward);
+			},
 			("7", "7", "R") => player.boofs += 1,
 			_ => ()
 		}
-		let roll_display = roll.reward_display(player.upgrades.slot_reward_mult);
+		let roll_display = roll.reward_display(player.upgrades.level("slot_reward_mult"));
 		if roll_display.len() + under_2k_reply.len() >= 1900 { // Much lower than 2000 to account for the varying reward amount and new lines
 			roll_displays.push(under_2k_reply);
 			under_2k_reply = String::from("");
@@ -468,18 +556,19 @@ async fn game_corner_slots(ctx: &Context, msg: &Message, mut args: Args) -> Comm
 	player_update.insert("daily_slots", player.daily_slots);
 	player_update.insert("jackpots", player.jackpots);
 	player_update.insert("boofs", player.boofs);
-	update_player(&player, doc! { "$set": player_update }).await;
+	update_player(&player, doc! { "$set": player_update }).await?;
 
 	Ok(())
 }
 
 #[command("tokenshop")]
 #[aliases("ts", "tokens")]
-#[sub_commands(game_corner_tokens_buy, game_corner_tokens_convert)]
+#[sub_commands(game_corner_tokens_buy, game_corner_tokens_convert, game_corner_tokens_prestige)]
+#[description("Shows the token shop")]
 async fn game_corner_tokens_main(ctx: &Context, msg: &Message) -> CommandResult {
-	let token_shop = get_token_shop(ctx).await;
-	let player = get_player(msg.author.id.0).await;
-	let embed = token_shop.embed_with_player(ctx, player).await;
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let token_shop = get_token_shop(ctx, player.economy).await?;
+	let embed = token_shop.embed_with_player(ctx, player).await?;
 	let _ = msg
 		.channel_id
 		.send_message(&ctx.http, |m| {
@@ -494,29 +583,33 @@ async fn game_corner_tokens_main(ctx: &Context, msg: &Message) -> CommandResult
 
 #[command("buy")]
 #[aliases("b")]
+#[description("Buys a prize from the token shop")]
+#[usage("<slot no.> [amount - Default: 1]")]
+#[example("1 2")]
+#[min_args(1)]
 async fn game_corner_tokens_buy(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-	let selection = match args.single::<i32>() {
+	let selection = match args.single::<usize>() {
 		Ok(x) => x,
 		Err(_) => 0
 	};
-	let token_shop = get_token_shop(ctx).await;
-	if !(1..=5).contains(&selection) {
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let token_shop = get_token_shop(ctx, player.economy).await?;
+	let visible_sets = token_shop.visible_sets(&player);
+	if !(1..=visible_sets + 3).contains(&selection) {
 		msg.channel_id.send_message(&ctx.http, |m| m.content("A selection was not made.")).await?;
 		return Ok(());
 	}
 	let amount = match args.single::<i64>() {
-		Ok(x) => x,
-		Err(_) => 1
+		Ok(x) if x > 0 => x,
+		_ => 1
 	};
 	let mut update = Document::new();
-	let mut player = get_player(msg.author.id.0).await;
-	let discount = 1.0 + player.upgrades.tokenshop_discount as f64 * 0.05;
-	if selection <= 3 {
-		let set = get_set(token_shop.sets.get((selection - 1) as usize).unwrap()).await.unwrap();
+	let discount = 1.0 + player.upgrades.level("tokenshop_discount") as f64 * 0.05;
+	if selection <= visible_sets {
+		let set = get_set(token_shop.sets.get(selection - 1).unwrap()).await?;
 		let base_cost = (to_tokens(set.pack_price()) as f64 / discount) as i64;
 		if player.tokens < base_cost {
-			msg.reply(&ctx.http, &format!("You don't have enough... You need **{}** more tokens", base_cost - player.tokens)).await?;
-			return Ok(());
+			return Err(Error::InsufficientFunds(format!("{} tokens", base_cost - player.tokens)).into());
 		}
 		let total_cost = base_cost * amount;
 		let amount = vec![total_cost / base_cost, player.tokens / base_cost]
@@ -534,15 +627,41 @@ async fn game_corner_tokens_buy(ctx: &Context, msg: &Message, mut args: Args) ->
 			player_packs.insert(set_id, amt.clone());
 		}
 		update.insert("packs", player_packs);
+	} else if selection == visible_sets + 3 {
+		let item = match find_item(&token_shop.item) {
+			Some(x) => x,
+			None => {
+				msg.channel_id.send_message(&ctx.http, |m| m.content("A selection was not made.")).await?;
+				return Ok(());
+			}
+		};
+		let tokens = match item.price().tokens {
+			Some(x) if x > 0 => x,
+			_ => {
+				msg.reply(&ctx.http, format!("**{}** can't be bought with tokens", item.name)).await?;
+				return Ok(());
+			}
+		};
+		// Discounts can't make an item free
+		let base_cost = ((tokens as f64 / discount) as i64).max(1);
+		if player.tokens < base_cost {
+			return Err(Error::InsufficientFunds(format!("{} tokens", base_cost - player.tokens)).into());
+		}
+		// Either the most they can afford or the amount they wanted
+		let amount = amount.min(player.tokens / base_cost);
+		player.tokens -= base_cost * amount;
+		give_item(&mut player, item, amount);
+		msg.reply(&ctx.http, format!("You bought {} **{}**!", amount, item.name)).await?;
+		update.insert("tokens", player.tokens);
+		update.insert("items", player.items_doc());
 	} else {
-		let card = match selection {
-			4 => get_card(ctx, &token_shop.rare_card).await,
+		let card = match selection - visible_sets {
+			1 => get_card(ctx, &token_shop.rare_card).await,
 			_ => get_card(ctx, &token_shop.rainbow_card).await
 		};
 		let base_cost = ((to_tokens(card.price) * 10) as f64 / discount) as i64;
 		if player.tokens < base_cost {
-			msg.reply(&ctx.http, &format!("You don't have enough... You need **{}** more tokens", base_cost - player.tokens)).await?;
-			return Ok(());
+			return Err(Error::InsufficientFunds(format!("{} tokens", base_cost - player.tokens)).into());
 		}
 		let total_cost = base_cost * amount;
 		let amount = vec![total_cost / base_cost, player.tokens / base_cost]
@@ -559,19 +678,22 @@ async fn game_corner_tokens_buy(ctx: &Context, msg: &Message, mut args: Args) ->
 		}
 		update.insert("cards", player_cards);
 	}
-	update_player(&player, doc! { "$set": update }).await;
+	update_player(&player, doc! { "$set": update }).await?;
 
 	Ok(())
 }
 
 #[command("convert")]
 #[aliases("c")]
+#[description("Converts tokens to cash")]
+#[usage("[amount - Default: 1]")]
+#[example("1000")]
 async fn game_corner_tokens_convert(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
 	let amount = match args.single::<i64>() {
 		Ok(x) => x,
 		Err(_) => 1
 	};
-	let mut player = get_player(msg.author.id.0).await;
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
 	if player.tokens <= 0 {
 		msg.reply(&ctx.http, "You don't have any tokens").await?;
 		return Ok(());
@@ -590,7 +712,7 @@ async fn game_corner_tokens_convert(ctx: &Context, msg: &Message, mut args: Args
 	update.insert("tokens", player.tokens);
 	update.insert("cash", player.cash);
 	update.insert("total_cash", player.total_cash);
-	update_player(&player, doc!{ "$set": update}).await;
+	update_player(&player, doc!{ "$set": update}).await?;
 
 	Ok(())
 }
@@ -13,7 +13,6 @@ use chrono::{
 	Utc,
 	Datelike,
 	Duration,
-	Local,
 };
 use rand::{
 	seq::{
@@ -49,27 +48,36 @@ use crate::{sets::{
 	Set,
 	get_sets,
 	get_set
-}, commands::get_client};
+}, commands::get_client, error::{BotResult, Error}};
 
 use super::{
 	player::{
 		Player,
 		get_player,
 		update_player
-	}, Idable
+	},
+	guild::economy_filter,
+	config,
+	Idable
 };
 
+fn def_0() -> i64 {
+	0
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Store {
 	#[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
 	id: Option<ObjectId>,
 	pub sets: Vec<String>,
 	#[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
-	pub reset: DateTime<Utc>
+	pub reset: DateTime<Utc>,
+	#[serde(default = "def_0")]
+	pub economy: i64
 }
 
 impl Store {
-	async fn new() -> Self {
+	async fn new(economy: i64) -> BotResult<Self> {
 		let sets = get_sets().await;
 		let mut weighted_sets = vec![];
 		for set in sets {
@@ -79,10 +87,10 @@ impl Store {
 		let store_sets = weighted_sets
 			.choose_multiple_weighted(
 				&mut thread_rng(),
-				10,
+				config::get().store_slots.len(),
 				|ws| ws.1
 			)
-			.unwrap()
+			.map_err(|_| Error::NotFound(String::from("sets for the store")))?
 			.collect::<Vec<_>>()
 			.iter()
 			.map(|ws| ws.0.clone())
@@ -92,14 +100,15 @@ impl Store {
 			.collect();
 		let now = Utc::now() + Duration::days(1);
 
-		Self {
+		Ok(Self {
 			id: None,
 			sets: store_sets,
-			reset: Utc.ymd(now.year(), now.month(), now.day()).and_hms(0, 0, 0)
-		}
+			reset: Utc.ymd(now.year(), now.month(), now.day()).and_hms(0, 0, 0),
+			economy
+		})
 	}
 
-	async fn update_sets(&self) -> Self {
+	async fn update_sets(&self) -> BotResult<Self> {
 		let sets = get_sets().await;
 		let mut weighted_sets = vec![];
 		for set in sets {
@@ -109,10 +118,10 @@ impl Store {
 		let store_sets = weighted_sets
 			.choose_multiple_weighted(
 				&mut thread_rng(),
-				10,
+				config::get().store_slots.len(),
 				|ws| ws.1
 			)
-			.unwrap()
+			.map_err(|_| Error::NotFound(String::from("sets for the store")))?
 			.collect::<Vec<_>>()
 			.iter()
 			.map(|ws| ws.0.clone())
@@ -122,31 +131,26 @@ impl Store {
 			.collect();
 		let now = Utc::now() + Duration::days(1);
 
-		Self {
+		Ok(Self {
 			id: self.id,
 			sets: store_sets,
-			reset: Utc.ymd(now.year(), now.month(), now.day()).and_hms(0, 0, 0)
-		}
+			reset: Utc.ymd(now.year(), now.month(), now.day()).and_hms(0, 0, 0),
+			economy: self.economy
+		})
 	}
 
-	pub async fn embed_with_player(&self, player: Player) -> CreateEmbed {
+	pub async fn embed_with_player(&self, player: Player) -> BotResult<CreateEmbed> {
 		let mut ret = CreateEmbed::default();
 		let mut desc = String::from("Welcome to the Card Store! Here you can spend cash for Packs of cards\n");
 		desc.push_str(&format!("You have **${:.2}**\n", player.cash));
 		desc.push_str("Here are the packs available today. To purchase packs, use **.(st)ore (b)uy <slot no. | slot id.> (amount)**\n\n");
-		let discount = 1.0 + player.upgrades.store_discount as f64 * 0.05;
+		let discount = 1.0 + player.upgrades.level("store_discount") as f64 * 0.05;
+		let economy_config = config::get();
 		for (i, set_id) in self.sets.iter().enumerate() {
 			let num = i + 1;
-			let set = get_set(set_id).await.unwrap();
-			let (pack_type, price_mult) = if num <= 4 {
-				("Pack", 1.0)
-			} else if 5 <= num && num <= 7 {
-				("Collection", 2.5)
-			} else if 8 <= num && num <= 9 {
-				("Trainer Box", 10.0)
-			} else {
-				("Booster Box", 30.0)
-			};
+			let set = get_set(set_id).await?;
+			let store_slot = economy_config.store_slot(i);
+			let (pack_type, price_mult) = (store_slot.name, store_slot.price_mult);
 			match player.completed_binders.contains(&set_id) {
 				true => {
 					let discount_mod = discount + 0.15;
@@ -159,72 +163,87 @@ impl Store {
 			.title("Card Store")
 			.description(&desc)
 			.colour(Colour::from_rgb(255, 50, 20))
-			.footer(|f| {
-				let local_timer: DateTime<Local> = DateTime::from(self.reset);
+			.footer(|f| f.text(&format!("Resets {}", player.format_time(self.reset))));
 
-				f.text(&format!("Resets {}", local_timer.format("%h %d %H:%M")))
-			});
-
-		ret
+		Ok(ret)
 	}
 }
 
-async fn get_store_collection() -> Collection<Store> {
-	let client = get_client().await.unwrap();
+async fn get_store_collection() -> BotResult<Collection<Store>> {
+	let client = get_client().await?;
 	let collection = client.database("poketcg").collection::<Store>("store");
 
-	collection
+	Ok(collection)
 }
 
-pub async fn get_store() -> Store {
-	let store_collection = get_store_collection().await;
-	let store = store_collection
-		.find_one(None, None)
-		.await
-		.unwrap();
-	let store = match store {
+pub async fn get_store(economy: i64) -> BotResult<Store> {
+	let store = match find_store(economy).await? {
 		Some(x) => x,
-		None => add_store().await
+		None => add_store(economy).await?
 	};
 	if store.reset < Utc::now() {
-		let store = store.update_sets().await;
-		update_store(&store).await;
-		return store;
+		let store = store.update_sets().await?;
+		update_store(&store).await?;
+		return Ok(store);
 	}
-	
 
-	store
+	Ok(store)
 }
 
-async fn add_store() -> Store {
-	let ret = Store::new().await;
-	let store_collection = get_store_collection().await;
-	store_collection
+/// The economy's store as it was last stocked, without adding or restocking it.
+pub async fn find_store(economy: i64) -> BotResult<Option<Store>> {
+	let store_collection = get_store_collection().await?;
+	let store = store_collection
+		.find_one(economy_filter(economy), None)
+		.await?;
+
+	Ok(store)
+}
+
+/// Restocks the store with new sets right away.
+pub async fn refresh_store(economy: i64) -> BotResult<Store> {
+	let store = get_store(economy).await?.update_sets().await?;
+	update_store(&store).await?;
+
+	Ok(store)
+}
+
+async fn add_store(economy: i64) -> BotResult<Store> {
+	let mut ret = Store::new(economy).await?;
+	let store_collection = get_store_collection().await?;
+	let result = store_collection
 		.insert_one(&ret, None)
-		.await
-		.unwrap();
+		.await?;
+	// Restocks update the store by id, so the new store needs it
+	ret.id = result.inserted_id.as_object_id();
 	
-	ret
+	Ok(ret)
 }
 
-async fn update_store(store: &Store) {
-	let store_collection = get_store_collection().await;
+async fn update_store(store: &Store) -> BotResult<()> {
+	let id = match store.id {
+		Some(x) => x,
+		None => return Err(Error::NotFound(String::from("store")))
+	};
+	let store_collection = get_store_collection().await?;
 	store_collection
 		.update_one(
-			doc! {"_id": &store.id.unwrap() }, 
+			doc! {"_id": id }, 
 			doc! {"$set": {"sets": &store.sets, "reset": &store.reset}}, 
 			None)
-		.await
-		.unwrap();
+		.await?;
+
+	Ok(())
 }
 
 #[command("store")]
 #[aliases("st")]
 #[sub_commands(store_buy)]
+#[description("Shows the packs for sale today")]
 async fn store_main(ctx: &Context, msg: &Message) -> CommandResult {
-	let store = get_store().await;
-	let player = get_player(msg.author.id.0).await;
-	let embed = store.embed_with_player(player).await;
+	let player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let store = get_store(player.economy).await?;
+	let embed = store.embed_with_player(player).await?;
 	let _ = msg
 		.channel_id
 		.send_message(&ctx.http, |m| {
@@ -239,6 +258,11 @@ async fn store_main(ctx: &Context, msg: &Message) -> CommandResult {
 
 #[command("buy")]
 #[aliases("b")]
+#[description("Buys packs from the store")]
+#[usage("<slot no. | set id> [amount - Default: 1]")]
+#[example("1 3")]
+#[example("swsh1")]
+#[min_args(1)]
 async fn store_buy(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
 	let mut selection = match args.single::<i32>() {
 		Ok(x) => x,
@@ -248,11 +272,13 @@ async fn store_buy(ctx: &Context, msg: &Message, mut args: Args) -> CommandResul
 		Ok(x) => x,
 		Err(_) => String::from("")
 	};
-	let store_ = get_store().await;
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
+	let store_ = get_store(player.economy).await?;
+	let slot_count = store_.sets.len();
 	if selection_str != "" && selection == 0 {
-		selection = (store_.sets.iter().position(|r| r == &selection_str).unwrap_or(10) + 1) as i32;
+		selection = (store_.sets.iter().position(|r| r == &selection_str).unwrap_or(slot_count) + 1) as i32;
 	}
-	if !(1..=10).contains(&selection) {
+	if !(1..=slot_count as i32).contains(&selection) {
 		msg.channel_id.send_message(&ctx.http, |m| m.content("A selection was not made.")).await?;
 		return Ok(());
 	}
@@ -260,25 +286,16 @@ async fn store_buy(ctx: &Context, msg: &Message, mut args: Args) -> CommandResul
 		Ok(x) => x,
 		Err(_) => 1
 	};
-	let set = get_set(store_.sets.get((selection - 1) as usize).unwrap()).await.unwrap();
-	let mut player = get_player(msg.author.id.0).await;
-	let (price_mult, pack_count) = if selection <= 4 {
-		(1.0, 1)
-	} else if 5 <= selection && selection <= 7 {
-		(2.5, 4)
-	} else if 8 <= selection && selection <= 9 {
-		(10.0, 12)
-	} else {
-		(30.0, 36)
-	};
-	let mut discount = 1.0 + player.upgrades.store_discount as f64 * 0.05;
+	let set = get_set(store_.sets.get((selection - 1) as usize).unwrap()).await?;
+	let store_slot = config::get().store_slot((selection - 1) as usize);
+	let (price_mult, pack_count) = (store_slot.price_mult, store_slot.packs as i32);
+	let mut discount = 1.0 + player.upgrades.level("store_discount") as f64 * 0.05;
 	if player.completed_binders.contains(&set.id()) {
 		discount += 0.15;
 	}
 	let base_cost = (set.pack_price() * &price_mult) / discount;
 	if player.cash < base_cost {
-		msg.channel_id.send_message(&ctx.http, |m| m.content(&format!("You don't have enough... You need **${:.2}** more", base_cost - player.cash))).await?;
-		return Ok(());
+		return Err(Error::InsufficientFunds(format!("${:.2}", base_cost - player.cash)).into());
 	}
 	let total_cost = base_cost * amount as f64;
 	let amount = vec![(total_cost / base_cost).floor(), (player.cash / base_cost).floor()]
@@ -302,7 +319,7 @@ async fn store_buy(ctx: &Context, msg: &Message, mut args: Args) -> CommandResul
 				"packs": player_packs
 			}
 		}
-	).await;
+	).await?;
 
 	Ok(())
 }
\ No newline at end of file
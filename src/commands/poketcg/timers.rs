@@ -14,7 +14,21 @@ use mongodb::{
 	Collection
 };
 
-use crate::commands::get_client;
+use futures::stream::TryStreamExt;
+
+use crate::{
+	commands::get_client,
+	error::{
+		BotResult,
+		Error
+	}
+};
+
+use super::guild::economy_filter;
+
+fn def_0() -> i64 {
+	0
+}
 
 fn utc_now() -> DateTime<Utc> {
 	let now = Utc::now() + Duration::days(1);
@@ -29,17 +43,20 @@ pub struct Timer {
 	#[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
 	pub pack_reset: DateTime<Utc>,
 	#[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime", default = "utc_now")]
-	pub slot_reset: DateTime<Utc>
+	pub slot_reset: DateTime<Utc>,
+	#[serde(default = "def_0")]
+	pub economy: i64
 }
 
 impl Timer {
-	fn new() -> Self {
+	fn new(economy: i64) -> Self {
 		let now = Utc::now() + Duration::days(1);
 
 		Self {
 			id: None,
 			pack_reset: Utc.ymd(now.year(), now.month(), now.day()).and_hms(0, 0, 0),
-			slot_reset: Utc.ymd(now.year(), now.month(), now.day()).and_hms(0, 0, 0)
+			slot_reset: Utc.ymd(now.year(), now.month(), now.day()).and_hms(0, 0, 0),
+			economy
 		}
 	}
 
@@ -49,51 +66,68 @@ impl Timer {
 		Self {
 			id: self.id,
 			pack_reset: Utc.ymd(now.year(), now.month(), now.day()).and_hms(0, 0, 0),
-			slot_reset: Utc.ymd(now.year(), now.month(), now.day()).and_hms(0, 0, 0)
+			slot_reset: Utc.ymd(now.year(), now.month(), now.day()).and_hms(0, 0, 0),
+			economy: self.economy
 		}
 	}
 }
 
-async fn get_timer_collection() -> Collection<Timer> {
-	let client = get_client().await.unwrap();
+async fn get_timer_collection() -> BotResult<Collection<Timer>> {
+	let client = get_client().await?;
 	let collection = client.database("poketcg").collection::<Timer>("timers");
 
-	collection
+	Ok(collection)
+}
+
+pub async fn get_timers() -> BotResult<Vec<Timer>> {
+	let timer_collection = get_timer_collection().await?;
+	let timers = timer_collection
+		.find(None, None)
+		.await?
+		.try_collect::<Vec<Timer>>()
+		.await?;
+
+	Ok(timers)
 }
 
-pub async fn get_timer() -> Timer {
-	let timer_collection = get_timer_collection().await;
+pub async fn get_timer(economy: i64) -> BotResult<Timer> {
+	let timer_collection = get_timer_collection().await?;
 	let timer = timer_collection
-		.find_one(None, None)
-		.await
-		.unwrap();
+		.find_one(economy_filter(economy), None)
+		.await?;
 	let timer = match timer {
 		Some(x) => x,
-		None => add_timer().await
+		None => add_timer(economy).await?
 	};
 	
-	timer
+	Ok(timer)
 }
 
-async fn add_timer() -> Timer {
-	let ret = Timer::new();
-	let timer_collection = get_timer_collection().await;
-	timer_collection
+async fn add_timer(economy: i64) -> BotResult<Timer> {
+	let mut ret = Timer::new(economy);
+	let timer_collection = get_timer_collection().await?;
+	let result = timer_collection
 		.insert_one(&ret, None)
-		.await
-		.unwrap();
+		.await?;
+	// Resets update the timer by id, so the new timer needs it
+	ret.id = result.inserted_id.as_object_id();
 
-	ret
+	Ok(ret)
 }
 
-pub async fn update_timer(timer: &Timer) {
+pub async fn update_timer(timer: &Timer) -> BotResult<()> {
 	let timer = timer.update_timers();
-	let timer_collection = get_timer_collection().await;
+	let id = match timer.id {
+		Some(x) => x,
+		None => return Err(Error::NotFound(String::from("timer")))
+	};
+	let timer_collection = get_timer_collection().await?;
 	timer_collection
 		.update_one(
-			doc! { "_id": &timer.id.unwrap() }, 
+			doc! { "_id": id }, 
 			doc! {"$set": {"pack_reset": &timer.pack_reset, "slot_reset": &timer.slot_reset}}, 
 			None)
-		.await
-		.unwrap();
-}
\ No newline at end of file
+		.await?;
+
+	Ok(())
+}
@@ -1,4 +1,5 @@
 use crate::{
+	logging,
 	player::{
 		Player,
 		get_player,
@@ -106,8 +107,12 @@ impl Trade {
 
 #[command("with")]
 #[aliases("w")]
+#[description("Offers a trade to another player")]
+#[usage("<@player> <trade offer>")]
+#[example("@player2 bwp:2/$10")]
+#[min_args(2)]
 async fn trade_with(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-	let mut player = get_player(msg.author.id.0).await;
+	let mut player = get_player(msg.author.id.0, msg.guild_id).await?;
 	let tradee_mention = msg.mentions.iter().nth(0);
 	match tradee_mention {
 		Some(x) => {
@@ -131,13 +136,13 @@ async fn trade_with(ctx: &Context, msg: &Message, mut args: Args) -> CommandResu
 		}
 	}
 	let trade_offer = Trade::from_trade_str(trade_str.unwrap());
-	println!("{:?}", &trade_offer);
+	debug!(target: logging::ECONOMY, "Trade offer from {}: {:?}", msg.author.id, &trade_offer);
 	if !trade_offer.player_has_all(&player) {
 		msg.reply(&ctx.http, "You don't own all of what you're offering!").await?;
 		return Ok(());
 	}
 	let tradee_mention = tradee_mention.unwrap();
-	let mut tradee = get_player(tradee_mention.id.0).await;
+	let mut tradee = get_player(tradee_mention.id.0, msg.guild_id).await?;
 	let counteroffer_ask = msg
 		.channel_id
 		.send_message(&ctx.http, |m| {m
@@ -171,8 +176,9 @@ async fn trade_with(ctx: &Context, msg: &Message, mut args: Args) -> CommandResu
 			return Ok(());
 		}
 		// Remove items from trade_offer from player and add them to tradee
+		// Cash loses a fee on the way, reduced by the sender's trade_fee_reduction upgrade
 		player.cash -= trade_offer.cash;
-		tradee.cash += trade_offer.cash;
+		tradee.cash += trade_offer.cash * (1.0 - player.trade_fee());
 		for (card_id, amount) in trade_offer.cards {
 			*player.cards.entry(card_id.clone()).or_insert(0) -= amount;
 			if *player.cards.entry(card_id.clone()).or_insert(0) == 0 {
@@ -188,7 +194,7 @@ async fn trade_with(ctx: &Context, msg: &Message, mut args: Args) -> CommandResu
 			*tradee.packs.entry(pack_id.clone()).or_insert(0) += amount;
 		}
 		// Remove items from counteroffer from tradee and add them to player
-		player.cash += counteroffer.cash;
+		player.cash += counteroffer.cash * (1.0 - tradee.trade_fee());
 		tradee.cash -= counteroffer.cash;
 		for (card_id, amount) in counteroffer.cards {
 			*tradee.cards.entry(card_id.clone()).or_insert(0) -= amount;
@@ -217,7 +223,7 @@ async fn trade_with(ctx: &Context, msg: &Message, mut args: Args) -> CommandResu
 			player_packs_doc.insert(pck, amt);
 		}
 		player_update.insert("packs", player_packs_doc);
-		update_player(&player, doc! { "$set": player_update }).await;
+		update_player(&player, doc! { "$set": player_update }).await?;
 		// Update the tradee
 		let mut tradee_update = Document::new();
 		tradee_update.insert("cash", tradee.cash);
@@ -231,9 +237,14 @@ async fn trade_with(ctx: &Context, msg: &Message, mut args: Args) -> CommandResu
 			tradee_packs_doc.insert(pck, amt);
 		}
 		tradee_update.insert("packs", tradee_packs_doc);
-		update_player(&tradee, doc! { "$set": tradee_update }).await;
+		update_player(&tradee, doc! { "$set": tradee_update }).await?;
+		let fees = trade_offer.cash * player.trade_fee() + counteroffer.cash * tradee.trade_fee();
+		let fee_str = match fees > 0.0 {
+			true => format!(" _(${:.2} in trade fees)_", fees),
+			false => String::from("")
+		};
 		msg
-			.reply(&ctx.http, format!("Traded <@{}> **{}** for **{}**", tradee_mention.id.0, trade_str.unwrap(), tradee_reply.content)).await?;
+			.reply(&ctx.http, format!("Traded <@{}> **{}** for **{}**{}", tradee_mention.id.0, trade_str.unwrap(), tradee_reply.content, fee_str)).await?;
 	} else {
 		msg.reply(&ctx.http, format!("<@{}> didn't reply \\:(", tradee_mention.id.0)).await?;
 		return Ok(());
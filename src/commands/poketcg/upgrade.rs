@@ -1,80 +1,340 @@
+use std::collections::HashMap;
+
 use bson::Document;
+use chrono::{
+	Utc,
+	Duration
+};
 use serde::{Serialize, Deserialize};
 use serenity::{builder::CreateEmbed, utils::Colour};
 
-use super::{player::Player};
+use super::{
+	player::Player,
+	config::{
+		self,
+		CostCurve,
+		UpgradeTuning
+	}
+};
+
+pub struct UpgradeDef {
+	pub key: &'static str,
+	// The name shown in the shop, also accepted when buying
+	pub name: &'static str,
+	pub aliases: &'static [&'static str],
+	pub description: &'static str,
+	pub curve: CostCurve,
+	pub base: f64,
+	pub step: f64,
+	pub max_level: i64,
+	// Anything that should change on the player right away when a level is bought
+	pub on_buy: Option<fn(&mut Player)>,
+	// Whether the upgrade can be bought, for upgrades that depend on the economy config
+	pub available: Option<fn() -> bool>
+}
+
+impl UpgradeDef {
+	/// The cost curve and max level, from the economy config if it overrides this upgrade.
+	pub fn tuning(&self) -> UpgradeTuning {
+		match config::get().upgrade(self.key) {
+			Some(x) => x.clone(),
+			None => UpgradeTuning {
+				curve: self.curve,
+				base: self.base,
+				step: self.step,
+				max_level: self.max_level
+			}
+		}
+	}
+
+	pub fn is_available(&self) -> bool {
+		match self.available {
+			Some(available) => available(),
+			None => true
+		}
+	}
+
+	fn matches(&self, name: &str) -> bool {
+		let name = name.to_lowercase();
+
+		self.key == name || self.name == name || self.aliases.contains(&name.as_str())
+	}
+}
+
+fn daily_time_reset_bought(player: &mut Player) {
+	if player.daily_reset >= Utc::now() {
+		player.daily_reset = player.daily_reset - Duration::hours(1);
+	}
+}
+
+fn daily_pack_amount_bought(player: &mut Player) {
+	player.daily_packs += config::get().packs.daily_packs_per_level;
+}
 
-fn def_0() -> i64 {
-	0
+fn daily_slot_amount_bought(player: &mut Player) {
+	player.daily_slots += config::get().packs.daily_slots_per_level;
+}
+
+fn quiz_time_reset_bought(player: &mut Player) {
+	if player.quiz_reset >= Utc::now() {
+		player.quiz_reset = player.quiz_reset - Duration::minutes(config::get().quiz.reset_minutes_per_level);
+	}
+}
+
+fn quiz_question_amount_bought(player: &mut Player) {
+	player.quiz_questions += 1;
+}
+
+// There's nothing to reduce while trades don't have a fee
+fn has_trade_fee() -> bool {
+	config::get().effects.trade_fee > 0.0
+}
+
+// The order here is the order of the upgrade shop
+pub const UPGRADES: &'static [UpgradeDef] = &[
+	UpgradeDef {
+		key: "daily_time_reset",
+		name: "dailytime",
+		aliases: &["dt"],
+		description: "Decreases the time between daily resets",
+		curve: CostCurve::Flat,
+		base: 200.0,
+		step: 0.0,
+		max_level: 12,
+		on_buy: Some(daily_time_reset_bought),
+		available: None
+	},
+	UpgradeDef {
+		key: "daily_reward_mult",
+		name: "dailyreward",
+		aliases: &["dr"],
+		description: "Increases your daily rewards",
+		curve: CostCurve::Linear,
+		base: 100.0,
+		step: 100.0,
+		max_level: 20,
+		on_buy: None,
+		available: None
+	},
+	UpgradeDef {
+		key: "daily_pack_amount",
+		name: "dailypacks",
+		aliases: &["dp"],
+		description: "Increases your daily packs",
+		curve: CostCurve::Triangular,
+		base: 1000.0,
+		step: 1000.0,
+		max_level: 5,
+		on_buy: Some(daily_pack_amount_bought),
+		available: None
+	},
+	UpgradeDef {
+		key: "store_discount",
+		name: "storediscount",
+		aliases: &["sd"],
+		description: "Gives a discount at the store",
+		curve: CostCurve::Linear,
+		base: 250.0,
+		step: 250.0,
+		max_level: 5,
+		on_buy: None,
+		available: None
+	},
+	UpgradeDef {
+		key: "tokenshop_discount",
+		name: "tokenshopdiscount",
+		aliases: &["tsd"],
+		description: "Gives a discount at the token shop",
+		curve: CostCurve::Linear,
+		base: 250.0,
+		step: 250.0,
+		max_level: 5,
+		on_buy: None,
+		available: None
+	},
+	UpgradeDef {
+		key: "slot_reward_mult",
+		name: "slotreward",
+		aliases: &["sr"],
+		description: "Increases your slot machine rewards",
+		curve: CostCurve::Linear,
+		base: 500.0,
+		step: 500.0,
+		max_level: 20,
+		on_buy: None,
+		available: None
+	},
+	UpgradeDef {
+		key: "daily_slot_amount",
+		name: "dailyslots",
+		aliases: &["ds"],
+		description: "Increases your daily slots",
+		curve: CostCurve::Linear,
+		base: 750.0,
+		step: 750.0,
+		max_level: 90,
+		on_buy: Some(daily_slot_amount_bought),
+		available: None
+	},
+	UpgradeDef {
+		key: "quiz_time_reset",
+		name: "quizreset",
+		aliases: &["qr"],
+		description: "Decreases the time between your quiz attempts",
+		curve: CostCurve::Flat,
+		base: 300.0,
+		step: 0.0,
+		max_level: 9,
+		on_buy: Some(quiz_time_reset_bought),
+		available: None
+	},
+	UpgradeDef {
+		key: "quiz_question_amount",
+		name: "quizattempts",
+		aliases: &["qa"],
+		description: "Increases your quiz questions per reset period",
+		curve: CostCurve::Linear,
+		base: 500.0,
+		step: 100.0,
+		max_level: 20,
+		on_buy: Some(quiz_question_amount_bought),
+		available: None
+	},
+	UpgradeDef {
+		key: "quiz_mult_limit",
+		name: "quizmultiplier",
+		aliases: &["qm"],
+		description: "Increases your max quiz multiplier",
+		curve: CostCurve::Linear,
+		base: 400.0,
+		step: 250.0,
+		max_level: 50,
+		on_buy: None,
+		available: None
+	},
+	UpgradeDef {
+		key: "pack_limit",
+		name: "packlimit",
+		aliases: &["pl"],
+		description: "Increases your max pack storage limit",
+		curve: CostCurve::Triangular,
+		base: 2500.0,
+		step: 1250.0,
+		max_level: 15,
+		on_buy: None,
+		available: None
+	},
+	UpgradeDef {
+		key: "rare_slot_luck",
+		name: "slotluck",
+		aliases: &["sluck", "luck"],
+		description: "Makes the 7 and R more likely to show up on the slot machine",
+		curve: CostCurve::Linear,
+		base: 1000.0,
+		step: 750.0,
+		max_level: 10,
+		on_buy: None,
+		available: None
+	},
+	UpgradeDef {
+		key: "sell_price_bonus",
+		name: "sellbonus",
+		aliases: &["sb", "sell"],
+		description: "Increases how much you get for selling cards and packs",
+		curve: CostCurve::Linear,
+		base: 500.0,
+		step: 500.0,
+		max_level: 10,
+		on_buy: None,
+		available: None
+	},
+	UpgradeDef {
+		key: "trade_fee_reduction",
+		name: "tradefee",
+		aliases: &["tf", "trade"],
+		description: "Reduces the fee on cash you send in trades",
+		curve: CostCurve::Linear,
+		base: 400.0,
+		step: 400.0,
+		max_level: 5,
+		on_buy: None,
+		available: Some(has_trade_fee)
+	},
+	UpgradeDef {
+		key: "tokenshop_slot",
+		name: "tokenshopslot",
+		aliases: &["tss"],
+		description: "Unlocks another pack in the token shop",
+		curve: CostCurve::Linear,
+		base: 5000.0,
+		step: 5000.0,
+		max_level: 2,
+		on_buy: None,
+		available: None
+	},
+	UpgradeDef {
+		key: "binder_completion_bonus",
+		name: "binderbonus",
+		aliases: &["bb", "binder"],
+		description: "Pays out cash whenever you complete a binder",
+		curve: CostCurve::Linear,
+		base: 750.0,
+		step: 750.0,
+		max_level: 10,
+		on_buy: None,
+		available: None
+	},
+];
+
+pub fn find_upgrade(name: &str) -> Option<&'static UpgradeDef> {
+	UPGRADES.iter().find(|u| u.matches(name))
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct Upgrade {
-	pub daily_time_reset: i64,
-	pub daily_reward_mult: i64,
-	pub daily_pack_amount: i64,
-	pub store_discount: i64,
-	pub tokenshop_discount: i64,
-	pub slot_reward_mult: i64,
-	pub daily_slot_amount: i64,
-	#[serde(default = "def_0")]
-	pub quiz_time_reset: i64,
-	#[serde(default = "def_0")]
-	pub quiz_question_amount: i64,
-	#[serde(default = "def_0")]
-	pub quiz_mult_limit: i64,
-	#[serde(default = "def_0")]
-	pub pack_limit: i64
+	levels: HashMap<String, i64>
 }
 
 impl Upgrade {
 	pub fn new() -> Self {
 		Self {
-			daily_time_reset: 0,
-			daily_reward_mult: 0,
-			daily_pack_amount: 0,
-			store_discount: 0,
-			tokenshop_discount: 0,
-			slot_reward_mult: 0,
-			daily_slot_amount: 0,
-			quiz_time_reset: 0,
-			quiz_question_amount: 0,
-			quiz_mult_limit: 0,
-			pack_limit: 0
+			levels: HashMap::new()
 		}
 	}
 
+	pub fn level(&self, upgrade: &str) -> i64 {
+		*self.levels.get(upgrade).unwrap_or(&0)
+	}
+
 	pub fn upgrade_cost(&self, upgrade: &str) -> f64 {
-		match upgrade {
-			"daily_time_reset" => 200.0,
-			"daily_reward_mult" => 100.0 + (100.0 * self.daily_reward_mult as f64),
-			"daily_pack_amount" => (1000 + (0..=self.daily_pack_amount).map(|i| i * 1000).sum::<i64>()) as f64,
-			"store_discount" => 250.0 + (250.0 * self.store_discount as f64),
-			"tokenshop_discount" => 250.0 + (250.0 * self.tokenshop_discount as f64),
-			"slot_reward_mult" => 500.0 + (500.0 * self.slot_reward_mult as f64),
-			"daily_slot_amount" => 750.0 + (750.0 * self.daily_slot_amount as f64),
-			"quiz_time_reset" =>  300.0,
-			"quiz_question_amount" =>  500.0 + (100.0 * self.quiz_question_amount as f64),
-			"quiz_mult_limit" => 400.0 + (250.0 * self.quiz_mult_limit as f64),
-			"pack_limit" => (2500 + (0..=self.pack_limit).map(|i| i * 1250).sum::<i64>()) as f64,
-			_ => 0.0
+		match find_upgrade(upgrade) {
+			Some(def) => def.tuning().cost(self.level(def.key)),
+			None => 0.0
 		}
 	}
 
 	pub fn is_max_upgrade(&self, upgrade: &str) -> bool {
-		match upgrade {
-			"daily_time_reset" => self.daily_time_reset >= 12,
-			"daily_reward_mult" => self.daily_reward_mult >= 20,
-			"daily_pack_amount" => self.daily_pack_amount >= 5,
-			"store_discount" => self.store_discount >= 5,
-			"tokenshop_discount" => self.tokenshop_discount >= 5,
-			"slot_reward_mult" => self.slot_reward_mult >= 20,
-			"daily_slot_amount" => self.daily_slot_amount >= 90,
-			"quiz_time_reset" => self.quiz_time_reset >= 9,
-			"quiz_question_amount" => self.quiz_question_amount >= 20,
-			"quiz_mult_limit" => self.quiz_mult_limit >= 50,
-			"pack_limit" => self.pack_limit >= 15,
-			_ => false
+		match find_upgrade(upgrade) {
+			Some(def) => self.level(def.key) >= def.tuning().max_level,
+			None => false
+		}
+	}
+
+	pub fn total_levels(&self) -> i64 {
+		self.levels.values().sum()
+	}
+
+	pub fn reset(&mut self) {
+		self.levels.clear();
+	}
+
+	/// Buys a single level of the upgrade for the player and applies its effect.
+	pub fn buy(player: &mut Player, def: &UpgradeDef) {
+		player.cash -= player.upgrades.upgrade_cost(def.key);
+		*player.upgrades.levels.entry(String::from(def.key)).or_insert(0) += 1;
+		if let Some(on_buy) = def.on_buy {
+			on_buy(player);
 		}
 	}
 
@@ -83,38 +343,10 @@ impl Upgrade {
 		let mut desc = String::from("Welcome to the Upgrade Store! Here you can spend cash for various upgrades\n");
 		desc.push_str(&format!("You have **${:.2}**\n", player.cash));
 		desc.push_str("Here are the upgrades available. To purchase an upgrade, use **.(up)grades (b)uy <slot no. | name.> [amount]**\n\n");
-		if !self.is_max_upgrade("daily_time_reset") {
-			desc.push_str(&format!("**1 dailytime:** Decreases the time between daily resets - ${:.2} _(lvl {})_\n", self.upgrade_cost("daily_time_reset"), self.daily_time_reset));
-		}
-		if !self.is_max_upgrade("daily_reward_mult") {
-			desc.push_str(&format!("**2 dailyreward:** Increases your daily rewards - ${:.2} _(lvl {})_\n", self.upgrade_cost("daily_reward_mult"), self.daily_reward_mult));
-		}
-		if !self.is_max_upgrade("daily_pack_amount") {
-			desc.push_str(&format!("**3 dailypacks:** Increases your daily packs - ${:.2} _(lvl {})_\n", self.upgrade_cost("daily_pack_amount"), self.daily_pack_amount));
-		}
-		if !self.is_max_upgrade("store_discount") {
-			desc.push_str(&format!("**4 storediscount:** Gives a discount at the store - ${:.2} _(lvl {})_\n", self.upgrade_cost("store_discount"), self.store_discount));
-		}
-		if !self.is_max_upgrade("tokenshop_discount") {
-			desc.push_str(&format!("**5 tokenshopdiscount:** Gives a discount at the token shop - ${:.2} _(lvl {})_\n", self.upgrade_cost("tokenshop_discount"), self.tokenshop_discount));
-		}
-		if !self.is_max_upgrade("slot_reward_mult") {
-			desc.push_str(&format!("**6 slotreward:** Increases your slot machine rewards - ${:.2} _(lvl {})_\n", self.upgrade_cost("slot_reward_mult"), self.slot_reward_mult));
-		}
-		if !self.is_max_upgrade("daily_slot_amount") {
-			desc.push_str(&format!("**7 dailyslots:** Increases your daily slots - ${:.2} _(lvl {})_\n", self.upgrade_cost("daily_slot_amount"), self.daily_slot_amount));
-		}
-		if !self.is_max_upgrade("quiz_time_reset") {
-			desc.push_str(&format!("**8 quizreset:** Decreases the time between your quiz attempts - ${:.2} _(lvl {})_\n", self.upgrade_cost("quiz_time_reset"), self.quiz_time_reset));
-		}
-		if !self.is_max_upgrade("quiz_question_amount") {
-			desc.push_str(&format!("**9 quizattempts:** Increases your quiz questions per reset period - ${:.2} _(lvl {})_\n", self.upgrade_cost("quiz_question_amount"), self.quiz_question_amount));
-		}
-		if !self.is_max_upgrade("quiz_mult_limit") {
-			desc.push_str(&format!("**10 quizmultiplier:** Increases your max quiz multiplier - ${:.2} _(lvl {})_\n", self.upgrade_cost("quiz_mult_limit"), self.quiz_mult_limit));
-		}
-		if !self.is_max_upgrade("pack_limit") {
-			desc.push_str(&format!("**11 packlimit:** Increases your max pack storage limit - ${:.2} _(lvl {})_\n", self.upgrade_cost("pack_limit"), self.pack_limit));
+		for (i, def) in UPGRADES.iter().enumerate() {
+			if def.is_available() && !self.is_max_upgrade(def.key) {
+				desc.push_str(&format!("**{} {}:** {} - ${:.2} _(lvl {})_\n", i + 1, def.name, def.description, self.upgrade_cost(def.key), self.level(def.key)));
+			}
 		}
 		ret
 			.title("Upgrade Shop")
@@ -126,68 +358,22 @@ impl Upgrade {
 
 	pub fn to_doc(&self) -> Document {
 		let mut d = Document::new();
-		d.insert("daily_time_reset", self.daily_time_reset);
-		d.insert("daily_reward_mult", self.daily_reward_mult);
-		d.insert("daily_pack_amount", self.daily_pack_amount);
-		d.insert("store_discount", self.store_discount);
-		d.insert("tokenshop_discount", self.tokenshop_discount);
-		d.insert("slot_reward_mult", self.slot_reward_mult);
-		d.insert("daily_slot_amount", self.daily_slot_amount);
-		d.insert("quiz_time_reset", self.quiz_time_reset);
-		d.insert("quiz_question_amount", self.quiz_question_amount);
-		d.insert("quiz_mult_limit", self.quiz_mult_limit);
-		d.insert("pack_limit", self.pack_limit);
+		for def in UPGRADES {
+			d.insert(def.key, self.level(def.key));
+		}
 
 		d
 	}
 
 	pub fn desc(&self) -> String {
 		let mut ret = String::from("");
-		match self.is_max_upgrade("daily_time_reset") {
-			true => ret.push_str(&format!("**dailytime:** {} ***MAX***\n", self.daily_time_reset)),
-			false => ret.push_str(&format!("**dailytime:** {}\n", self.daily_time_reset))
-		}
-		match self.is_max_upgrade("daily_reward_mult") {
-			true => ret.push_str(&format!("**dailyreward:** {} ***MAX***\n", self.daily_reward_mult)),
-			false => ret.push_str(&format!("**dailyreward:** {}\n", self.daily_reward_mult))
-		}
-		match self.is_max_upgrade("daily_pack_amount") {
-			true => ret.push_str(&format!("**dailypacks:** {} ***MAX***\n", self.daily_pack_amount)),
-			false => ret.push_str(&format!("**dailypacks:** {}\n", self.daily_pack_amount))
-		}
-		match self.is_max_upgrade("store_discount") {
-			true => ret.push_str(&format!("**storediscount:** {} ***MAX***\n", self.store_discount)),
-			false => ret.push_str(&format!("**storediscount:** {}\n", self.store_discount))
-		}
-		match self.is_max_upgrade("tokenshop_discount") {
-			true => ret.push_str(&format!("**tokenshopdiscount:** {} ***MAX***\n", self.tokenshop_discount)),
-			false => ret.push_str(&format!("**tokenshopdiscount:** {}\n", self.tokenshop_discount))
-		}
-		match self.is_max_upgrade("slot_reward_mult") {
-			true => ret.push_str(&format!("**slotreward:** {} ***MAX***\n", self.slot_reward_mult)),
-			false => ret.push_str(&format!("**slotreward:** {}\n", self.slot_reward_mult))
-		}
-		match self.is_max_upgrade("daily_slot_amount") {
-			true => ret.push_str(&format!("**dailyslots:** {} ***MAX***\n", self.daily_slot_amount)),
-			false => ret.push_str(&format!("**dailyslots:** {}\n", self.daily_slot_amount))
-		}
-		match self.is_max_upgrade("quiz_time_reset") {
-			true => ret.push_str(&format!("**quizreset:** {} ***MAX***\n", self.quiz_time_reset)),
-			false => ret.push_str(&format!("**quizreset:** {}\n", self.quiz_time_reset))
-		}
-		match self.is_max_upgrade("quiz_question_amount") {
-			true => ret.push_str(&format!("**quizattempts:** {} ***MAX***\n", self.quiz_question_amount)),
-			false => ret.push_str(&format!("**quizattempts:** {}\n", self.quiz_question_amount))
-		}
-		match self.is_max_upgrade("quiz_mult_limit") {
-			true => ret.push_str(&format!("**quizmultiplier:** {} ***MAX***\n", self.quiz_mult_limit)),
-			false => ret.push_str(&format!("**quizmultiplier:** {}\n", self.quiz_mult_limit))
-		}
-		match self.is_max_upgrade("pack_limit") {
-			true => ret.push_str(&format!("**packlimit:** {} ***MAX***\n", self.pack_limit)),
-			false => ret.push_str(&format!("**packlimit:** {}\n", self.pack_limit))
+		for def in UPGRADES {
+			match self.is_max_upgrade(def.key) {
+				true => ret.push_str(&format!("**{}:** {} ***MAX***\n", def.name, self.level(def.key))),
+				false => ret.push_str(&format!("**{}:** {}\n", def.name, self.level(def.key)))
+			}
 		}
 
 		ret
 	}
-}
\ No newline at end of file
+}
@@ -0,0 +1,88 @@
+use std::fmt;
+
+/// Everything a command can fail with. Commands return these with `?` and the
+/// framework's `after` hook replies with [`Error::user_message`] and logs them.
+#[derive(Debug)]
+pub enum Error {
+	// The Pokemon TCG API couldn't be reached or sent something unexpected
+	Api(String),
+	Database(mongodb::error::Error),
+	Discord(serenity::Error),
+	// Data that couldn't be read, like a card image or an API response
+	Parse(String),
+	// What wasn't found, eg. "set swsh99"
+	NotFound(String),
+	// How much more the player needs, already formatted, eg. "$2.50" or "300 tokens"
+	InsufficientFunds(String),
+}
+
+pub type BotResult<T> = Result<T, Error>;
+
+impl Error {
+	/// What to tell the player. Errors that aren't their fault don't give any details away.
+	pub fn user_message(&self) -> String {
+		match self {
+			Error::Api(_) => String::from("I couldn't reach the Pokemon TCG API. Try again in a bit"),
+			Error::Database(_) | Error::Discord(_) | Error::Parse(_) => String::from("Something went wrong... Try again in a bit"),
+			Error::NotFound(what) => format!("No {} found", what),
+			Error::InsufficientFunds(short) => format!("You don't have enough... You need **{}** more", short)
+		}
+	}
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Error::Api(why) => write!(f, "API error: {}", why),
+			Error::Database(why) => write!(f, "Database error: {}", why),
+			Error::Discord(why) => write!(f, "Discord error: {}", why),
+			Error::Parse(why) => write!(f, "Parse error: {}", why),
+			Error::NotFound(what) => write!(f, "Not found: {}", what),
+			Error::InsufficientFunds(short) => write!(f, "Insufficient funds: needs {} more", short)
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+impl From<mongodb::error::Error> for Error {
+	fn from(why: mongodb::error::Error) -> Self {
+		Error::Database(why)
+	}
+}
+
+impl From<mongodb::bson::ser::Error> for Error {
+	fn from(why: mongodb::bson::ser::Error) -> Self {
+		Error::Parse(why.to_string())
+	}
+}
+
+impl From<serenity::Error> for Error {
+	fn from(why: serenity::Error) -> Self {
+		Error::Discord(why)
+	}
+}
+
+impl From<reqwest::Error> for Error {
+	fn from(why: reqwest::Error) -> Self {
+		Error::Api(why.to_string())
+	}
+}
+
+impl From<image::ImageError> for Error {
+	fn from(why: image::ImageError) -> Self {
+		Error::Parse(why.to_string())
+	}
+}
+
+impl From<std::io::Error> for Error {
+	fn from(why: std::io::Error) -> Self {
+		Error::Parse(why.to_string())
+	}
+}
+
+impl From<base64::DecodeError> for Error {
+	fn from(why: base64::DecodeError) -> Self {
+		Error::Parse(why.to_string())
+	}
+}
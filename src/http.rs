@@ -0,0 +1,51 @@
+use std::{
+	convert::Infallible,
+	future::Future,
+	net::SocketAddr
+};
+use hyper::{
+	service::{
+		make_service_fn,
+		service_fn
+	},
+	Body,
+	Request,
+	Response,
+	Server
+};
+
+use crate::logging;
+
+/// Serves `handler` on the address in `addr_var` (or `default_addr`) until the bot shuts down.
+/// `name` is what the logs call the server, `path` is only used to log where it's served.
+pub async fn serve<F, R>(name: &str, addr_var: &str, default_addr: &str, path: &str, handler: F)
+where
+	F: Fn(Request<Body>) -> R + Clone + Send + Sync + 'static,
+	R: Future<Output = Result<Response<Body>, Infallible>> + Send + 'static
+{
+	let addr = dotenv::var(addr_var).unwrap_or(String::from(default_addr));
+	let addr = match addr.parse::<SocketAddr>() {
+		Ok(x) => x,
+		Err(why) => {
+			error!(target: logging::BOT, "Couldn't serve {}, {} isn't an address: {}", name, addr, why);
+			return;
+		}
+	};
+	let make_service = make_service_fn(move |_| {
+		let handler = handler.clone();
+		async move {
+			Ok::<_, Infallible>(service_fn(handler))
+		}
+	});
+	let server = match Server::try_bind(&addr) {
+		Ok(x) => x.serve(make_service),
+		Err(why) => {
+			error!(target: logging::BOT, "Couldn't serve {} on {}: {}", name, addr, why);
+			return;
+		}
+	};
+	info!(target: logging::BOT, "Serving {} on http://{}{}", name, addr, path);
+	if let Err(why) = server.await {
+		error!(target: logging::BOT, "The {} server stopped: {}", name, why);
+	}
+}
@@ -0,0 +1,178 @@
+use std::{
+	fs::{
+		File,
+		OpenOptions
+	},
+	io::Write,
+	sync::{
+		Arc,
+		Mutex
+	}
+};
+use chrono::Utc;
+use log::{
+	Level,
+	LevelFilter,
+	Log,
+	Metadata,
+	Record
+};
+use serenity::{
+	http::Http,
+	model::id::ChannelId
+};
+use tokio::sync::mpsc::{
+	self,
+	UnboundedReceiver,
+	UnboundedSender
+};
+
+// The subsystems the bot logs under, used as the `target` of every log call
+pub const CATALOG: &str = "catalog";
+pub const ECONOMY: &str = "economy";
+pub const GAMES: &str = "games";
+pub const SCHEDULER: &str = "scheduler";
+pub const BOT: &str = "bot";
+
+const TARGETS: &'static [&str] = &[CATALOG, ECONOMY, GAMES, SCHEDULER, BOT];
+
+// Discord's limit for a message
+static MAX_MESSAGE_LEN: usize = 2000;
+
+lazy_static! {
+	// The admin channel and the warnings and errors waiting to be sent to it, taken when the bot is ready
+	static ref ADMIN_RECEIVER: Mutex<Option<(ChannelId, UnboundedReceiver<String>)>> = Mutex::new(None);
+}
+
+struct Logger {
+	// The level for each of our targets, anything else only logs warnings and errors
+	levels: Vec<(&'static str, LevelFilter)>,
+	default_level: LevelFilter,
+	file: Option<Mutex<File>>,
+	admin: Option<UnboundedSender<String>>
+}
+
+impl Logger {
+	fn level_for(&self, target: &str) -> LevelFilter {
+		match self.levels.iter().find(|(t, _)| *t == target) {
+			Some((_, level)) => *level,
+			None if TARGETS.contains(&target) => self.default_level,
+			None => LevelFilter::Warn
+		}
+	}
+}
+
+impl Log for Logger {
+	fn enabled(&self, metadata: &Metadata) -> bool {
+		metadata.level() <= self.level_for(metadata.target())
+	}
+
+	fn log(&self, record: &Record) {
+		if !self.enabled(record.metadata()) {
+			return;
+		}
+		let line = format!("{} {:<5} [{}] {}", Utc::now().format("%Y-%m-%d %H:%M:%S"), record.level(), record.target(), record.args());
+		println!("{}", line);
+		if let Some(file) = &self.file {
+			let _ = writeln!(file.lock().unwrap(), "{}", line);
+		}
+		// Only our own warnings are mirrored, so a failed send from the library can't loop back
+		if let Some(admin) = &self.admin {
+			if record.level() <= Level::Warn && TARGETS.contains(&record.target()) {
+				let _ = admin.send(format!("**{}** [{}] {}", record.level(), record.target(), record.args()));
+			}
+		}
+	}
+
+	fn flush(&self) {
+		if let Some(file) = &self.file {
+			let _ = file.lock().unwrap().flush();
+		}
+	}
+}
+
+// Reads directives like "info,scheduler=debug,catalog=warn"
+fn parse_levels(spec: &str) -> (LevelFilter, Vec<(&'static str, LevelFilter)>) {
+	let mut default_level = LevelFilter::Info;
+	let mut levels = vec![];
+	for directive in spec.split(',').map(|d| d.trim()).filter(|d| *d != "") {
+		match directive.split_once('=') {
+			Some((target, level)) => {
+				let target = TARGETS.iter().find(|t| **t == target.trim());
+				if let (Some(target), Ok(level)) = (target, level.trim().parse::<LevelFilter>()) {
+					levels.push((*target, level));
+				}
+			},
+			None => {
+				if let Ok(level) = directive.parse::<LevelFilter>() {
+					default_level = level;
+				}
+			}
+		}
+	}
+
+	(default_level, levels)
+}
+
+/// Sets up logging from the environment:
+/// LOG_LEVEL for the levels (eg. "info,scheduler=debug"), LOG_FILE to also write to a file
+/// and LOG_CHANNEL_ID to mirror warnings and errors to a Discord channel.
+pub fn init() {
+	let (default_level, levels) = parse_levels(&dotenv::var("LOG_LEVEL").unwrap_or(String::from("info")));
+	let file = match dotenv::var("LOG_FILE") {
+		Ok(path) => match OpenOptions::new().create(true).append(true).open(&path) {
+			Ok(x) => Some(Mutex::new(x)),
+			Err(why) => {
+				println!("Couldn't open the log file {}: {}", path, why);
+				None
+			}
+		},
+		Err(_) => None
+	};
+	// Nothing is queued for a channel id that can't be used, or it would never be sent
+	let admin = match dotenv::var("LOG_CHANNEL_ID").map(|id| id.parse::<u64>()) {
+		Ok(Ok(channel_id)) => {
+			let (sender, receiver) = mpsc::unbounded_channel();
+			*ADMIN_RECEIVER.lock().unwrap() = Some((ChannelId(channel_id), receiver));
+			Some(sender)
+		},
+		Ok(Err(why)) => {
+			println!("Not sending logs to the admin channel, LOG_CHANNEL_ID isn't a channel id: {}", why);
+			None
+		},
+		Err(_) => None
+	};
+	let max_level = levels
+		.iter()
+		.map(|(_, level)| *level)
+		.chain([default_level, LevelFilter::Warn])
+		.max()
+		.unwrap();
+	let logger = Logger {
+		levels,
+		default_level,
+		file,
+		admin
+	};
+	if log::set_logger(Box::leak(Box::new(logger))).is_ok() {
+		log::set_max_level(max_level);
+	}
+}
+
+/// Sends mirrored warnings and errors to the admin channel until the bot shuts down.
+/// Does nothing if there's no admin channel or it's already running.
+pub async fn run_admin_channel(http: Arc<Http>) {
+	let (channel_id, mut receiver) = match ADMIN_RECEIVER.lock().unwrap().take() {
+		Some(x) => x,
+		None => return
+	};
+	while let Some(message) = receiver.recv().await {
+		let message = match message.char_indices().nth(MAX_MESSAGE_LEN) {
+			Some((i, _)) => &message[..i],
+			None => &message
+		};
+		if let Err(why) = channel_id.say(&http, message).await {
+			println!("Couldn't send a log to the admin channel: {}", why);
+		}
+	}
+}
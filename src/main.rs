@@ -4,14 +4,15 @@
 #[macro_use]
 extern crate lazy_static;
 extern crate base64;
+#[macro_use]
+extern crate log;
 
 use std::{
 	sync::{
 		atomic::{AtomicBool, Ordering},
 		Arc,
 	},
-	time::Duration as StdDuration,
-	collections::HashMap, fmt::Write
+	collections::{HashMap, HashSet}, fmt::Write
 };
 use tokio::sync::RwLock;
 use dotenv;
@@ -20,6 +21,7 @@ use serenity::{async_trait, model::channel::{Message}, framework::standard::{Com
 use serenity::client::{Client, Context, EventHandler};
 use serenity::model::{
 	gateway::{
+		Activity,
 		Ready,
 		GatewayIntents
 	},
@@ -28,15 +30,34 @@ use serenity::framework::standard::{
     StandardFramework,
     macros::{
         group,
-		check
+		check,
+		help,
+		hook
     },
+	help_commands,
 	Args,
+	Command,
+	CommandGroup,
+	CommandResult,
+	DispatchError,
+	HelpOptions,
 };
+use serenity::model::id::UserId;
 use chrono::{DateTime, Utc, Duration};
 use indicatif::*;
 use rand::prelude::*;
 
+#[cfg(feature = "http-api")]
+mod api;
 mod commands;
+mod error;
+#[cfg(any(feature = "metrics", feature = "http-api"))]
+mod http;
+mod logging;
+#[cfg(feature = "metrics")]
+mod metrics;
+
+use error::Error;
 
 use commands::{
 	poketcg::{
@@ -48,11 +69,21 @@ use commands::{
 		slot::*,
 		quiz::*,
 		decks::*,
+		guild::*,
+		prestige::*,
+		settings::*,
+		items::*,
+		calendar::*,
+		community::*,
+		changelog::{self, *},
+		config,
+		scheduler,
 		card::Card
 	}
 };
 
 #[group]
+#[description("Collect, trade and sell Pokemon TCG cards")]
 #[commands(
 	search_main,
 	sets_command,
@@ -64,6 +95,7 @@ use commands::{
 	open_pack_command,
 	sell_main,
 	savelist_main,
+	wishlist_main,
 	trade_main,
 	game_corner_main,
 	upgrades_main,
@@ -73,6 +105,14 @@ use commands::{
 	binder_main,
 	deck_main,
 	decks_command,
+	economy_command,
+	prestige_command,
+	settings_main,
+	items_main,
+	use_command,
+	calendar_command,
+	community_main,
+	changelog_command,
 )]
 struct PokeTCG;
 
@@ -86,6 +126,86 @@ async fn owner_check(_: &Context, msg: &Message, _: &mut Args, _: &CommandOption
 	Ok(())
 }
 
+#[help]
+#[individual_command_tip = "To learn more about a command, use **.help <command>**, eg. **.help deck add**"]
+#[command_not_found_text = "There's no command called **{}**"]
+#[max_levenshtein_distance(3)]
+#[lacking_permissions = "Hide"]
+#[lacking_conditions = "Hide"]
+#[strikethrough_commands_tip_in_guild = ""]
+async fn help_command(ctx: &Context, msg: &Message, args: Args, help_options: &'static HelpOptions, groups: &[&'static CommandGroup], owners: HashSet<UserId>) -> CommandResult {
+	let _ = help_commands::with_embeds(ctx, msg, args, help_options, groups, owners).await;
+
+	Ok(())
+}
+
+// The full name of the command the message ran, eg. "deck energy add" for ".dk e a"
+fn command_path(content: &str) -> String {
+	let mut path = vec![];
+	let mut commands: &[&Command] = POKETCG_GROUP.options.commands;
+	for word in content.trim_start_matches('.').split_whitespace() {
+		let word = word.to_lowercase();
+		let command = commands
+			.iter()
+			.find(|c| c.options.names.contains(&word.as_str()));
+		match command {
+			Some(x) => {
+				path.push(x.options.names[0]);
+				commands = x.options.sub_commands;
+			},
+			None => break
+		}
+	}
+
+	path.join(" ")
+}
+
+#[hook]
+async fn before(ctx: &Context, msg: &Message, command_name: &str) -> bool {
+	#[cfg(feature = "metrics")]
+	metrics::start_command(msg.id.0);
+	if let Err(why) = changelog::check_whats_new(ctx, msg, command_name).await {
+		warn!(target: logging::BOT, "Couldn't check what's new for {}: {}", msg.author.id, why);
+	}
+
+	true
+}
+
+#[hook]
+async fn after(ctx: &Context, msg: &Message, command_name: &str, result: CommandResult) {
+	#[cfg(feature = "metrics")]
+	metrics::finish_command(msg.id.0, &command_path(&msg.content), result.is_ok());
+	let why = match result {
+		Ok(()) => return,
+		Err(why) => why
+	};
+	let content = match why.downcast_ref::<Error>() {
+		// Not finding something or not affording it is part of playing, not worth logging
+		Some(error @ (Error::NotFound(_) | Error::InsufficientFunds(_))) => error.user_message(),
+		Some(error) => {
+			error!(target: logging::BOT, "Command {} failed for {} in {:?} ({:?}): {}", command_name, msg.author.id, msg.guild_id, msg.content, error);
+			error.user_message()
+		},
+		None => {
+			error!(target: logging::BOT, "Command {} failed for {} in {:?} ({:?}): {}", command_name, msg.author.id, msg.guild_id, msg.content, why);
+			String::from("Something went wrong... Try again in a bit")
+		}
+	};
+	let _ = msg.reply(&ctx.http, content).await;
+}
+
+#[hook]
+async fn dispatch_error(ctx: &Context, msg: &Message, error: DispatchError, _command_name: &str) {
+	let content = match error {
+		DispatchError::NotEnoughArguments { min, given: _ } => format!("That command needs at least **{}** argument(s). Use **.help {}** to see how to use it", min, command_path(&msg.content)),
+		DispatchError::TooManyArguments { max, given: _ } => format!("That command takes at most **{}** argument(s). Use **.help {}** to see how to use it", max, command_path(&msg.content)),
+		DispatchError::OnlyForGuilds => String::from("That command only works in a server"),
+		DispatchError::LackingPermissions(_) => String::from("You don't have permission to use that command"),
+		_ => return
+	};
+	let _ = msg.reply(&ctx.http, content).await;
+}
+
 struct Cache;
 
 impl TypeMapKey for Cache {
@@ -136,33 +256,20 @@ impl EventHandler for Handler {
 			pb.inc(1);
 		}
 		pb.finish_with_message("Fetched all the rare and rainbow cards.");
+		_ctx.set_activity(Activity::playing(&format!(".help | v{}", changelog::VERSION))).await;
 		let ctx = Arc::new(_ctx);
 		
-		if !self.is_loop_running.load(Ordering::Relaxed) {
-			let ctx1 = Arc::clone(&ctx);
-			tokio::spawn(async move {
-				loop {
-					commands::poketcg::refresh_dailys(Arc::clone(&ctx1)).await;
-					tokio::time::sleep(StdDuration::from_secs(60)).await;
-				}
-			});
-			let ctx2 = Arc::clone(&ctx);
-			tokio::spawn(async move {
-				loop {
-					commands::poketcg::refresh_card_prices(Arc::clone(&ctx2)).await;
-					tokio::time::sleep(StdDuration::from_secs(3600)).await;
-				}
-			});
-			let ctx3 = Arc::clone(&ctx);
-			tokio::spawn(async move {
-				loop {
-					commands::poketcg::check_daily_streaks(Arc::clone(&ctx3)).await;
-					tokio::time::sleep(StdDuration::from_secs(1800)).await;
-				}
-			});
+		// Ready fires again on every reconnect, only the first one starts the scheduler
+		if self.is_loop_running.compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst).is_ok() {
+			tokio::spawn(logging::run_admin_channel(Arc::clone(&ctx.http)));
+			tokio::spawn(scheduler::run_scheduler(Arc::clone(&ctx)));
+			#[cfg(feature = "metrics")]
+			tokio::spawn(metrics::serve(Arc::clone(&ctx)));
+			#[cfg(feature = "http-api")]
+			tokio::spawn(api::serve(Arc::clone(&ctx)));
 		}
 
-		println!("{} is connected and ready!", ready.user.name);
+		info!(target: logging::BOT, "{} is connected and ready!", ready.user.name);
 	}
 
 	// Here for getting custom emoji IDs
@@ -179,9 +286,17 @@ impl EventHandler for Handler {
 async fn main() {
 	let framework = StandardFramework::new()
 		.configure(|c| c.prefix("."))
+		.before(before)
+		.after(after)
+		.on_dispatch_error(dispatch_error)
+		.help(&HELP_COMMAND)
 		.group(&POKETCG_GROUP);
 
 	dotenv::dotenv().ok();
+	logging::init();
+	if let Err(why) = config::reload() {
+		warn!(target: logging::ECONOMY, "Using the default economy config. {}", why);
+	}
 	// Configure the client with the discord token. Make sure one is commented out.
 	let token = dotenv::var("BOTTOKEN").expect("Expected a token in the environment");
 	let intents = GatewayIntents::GUILD_MESSAGES
@@ -207,6 +322,6 @@ async fn main() {
 
 	// Finally start a shard and listen for events.
 	if let Err(why) = client.start().await {
-		println!("Client error: {:?}", why);
+		error!(target: logging::BOT, "Client error: {:?}", why);
 	}
 }
\ No newline at end of file
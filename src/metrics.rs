@@ -0,0 +1,252 @@
+use std::{
+	collections::HashMap,
+	convert::Infallible,
+	fmt::Write,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc,
+		Mutex
+	},
+	time::{Duration, Instant}
+};
+use hyper::{
+	Body,
+	Method,
+	Request,
+	Response,
+	StatusCode
+};
+use serenity::prelude::*;
+
+use crate::{
+	commands::poketcg::player,
+	http,
+	logging,
+	Cache
+};
+
+static DEFAULT_ADDR: &str = "127.0.0.1:9184";
+
+// Upper bounds of the latency buckets in seconds, opening packs draws images so they go up high
+const LATENCY_BUCKETS: &'static [f64] = &[0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+#[derive(Default)]
+struct Histogram {
+	buckets: Vec<u64>,
+	sum: f64,
+	count: u64
+}
+
+impl Histogram {
+	fn observe(&mut self, seconds: f64) {
+		if self.buckets.len() == 0 {
+			self.buckets = vec![0; LATENCY_BUCKETS.len()];
+		}
+		for (i, bound) in LATENCY_BUCKETS.iter().enumerate() {
+			if seconds <= *bound {
+				self.buckets[i] += 1;
+			}
+		}
+		self.sum += seconds;
+		self.count += 1;
+	}
+}
+
+#[derive(Default)]
+struct CommandStats {
+	ok: u64,
+	err: u64,
+	latency: Histogram
+}
+
+#[derive(Default)]
+struct ApiStats {
+	requests: u64,
+	errors: u64
+}
+
+#[derive(Default)]
+struct JobStats {
+	runs: u64,
+	failures: u64,
+	last_duration: f64,
+	latency: Histogram
+}
+
+lazy_static! {
+	// When each running command started, keyed by message id
+	static ref COMMAND_STARTS: Mutex<HashMap<u64, Instant>> = Mutex::new(HashMap::new());
+	// Keyed by the full command path, eg. "deck energy add"
+	static ref COMMANDS: Mutex<HashMap<String, CommandStats>> = Mutex::new(HashMap::new());
+	// Keyed by the API resource, eg. "cards" or "sets"
+	static ref API_CALLS: Mutex<HashMap<String, ApiStats>> = Mutex::new(HashMap::new());
+	static ref JOBS: Mutex<HashMap<String, JobStats>> = Mutex::new(HashMap::new());
+}
+
+static CACHE_HITS: AtomicU64 = AtomicU64::new(0);
+static CACHE_MISSES: AtomicU64 = AtomicU64::new(0);
+static PACKS_OPENED: AtomicU64 = AtomicU64::new(0);
+
+pub fn start_command(message_id: u64) {
+	COMMAND_STARTS.lock().unwrap().insert(message_id, Instant::now());
+}
+
+pub fn finish_command(message_id: u64, command: &str, ok: bool) {
+	let started = COMMAND_STARTS.lock().unwrap().remove(&message_id);
+	let mut commands = COMMANDS.lock().unwrap();
+	let stats = commands.entry(String::from(command)).or_default();
+	match ok {
+		true => stats.ok += 1,
+		false => stats.err += 1
+	}
+	if let Some(started) = started {
+		stats.latency.observe(started.elapsed().as_secs_f64());
+	}
+}
+
+pub fn record_api_call(endpoint: &str, ok: bool) {
+	// Card and set ids would give every card its own series
+	let resource = endpoint.split('/').next().unwrap_or(endpoint);
+	let mut api_calls = API_CALLS.lock().unwrap();
+	let stats = api_calls.entry(String::from(resource)).or_default();
+	stats.requests += 1;
+	if !ok {
+		stats.errors += 1;
+	}
+}
+
+pub fn record_cache_lookups(hits: u64, misses: u64) {
+	CACHE_HITS.fetch_add(hits, Ordering::Relaxed);
+	CACHE_MISSES.fetch_add(misses, Ordering::Relaxed);
+}
+
+pub fn record_packs_opened(amount: u64) {
+	PACKS_OPENED.fetch_add(amount, Ordering::Relaxed);
+}
+
+pub fn record_job(name: &str, duration: Duration, ok: bool) {
+	let mut jobs = JOBS.lock().unwrap();
+	let stats = jobs.entry(String::from(name)).or_default();
+	stats.runs += 1;
+	if !ok {
+		stats.failures += 1;
+	}
+	stats.last_duration = duration.as_secs_f64();
+	stats.latency.observe(duration.as_secs_f64());
+}
+
+fn escape(label: &str) -> String {
+	label
+		.replace('\\', "\\\\")
+		.replace('"', "\\\"")
+		.replace('\n', "\\n")
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+	let _ = writeln!(out, "# HELP {} {}", name, help);
+	let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+fn write_histogram(out: &mut String, name: &str, labels: &str, histogram: &Histogram) {
+	for (i, bound) in LATENCY_BUCKETS.iter().enumerate() {
+		let count = histogram.buckets.get(i).unwrap_or(&0);
+		let _ = writeln!(out, "{}_bucket{{{},le=\"{}\"}} {}", name, labels, bound, count);
+	}
+	let _ = writeln!(out, "{}_bucket{{{},le=\"+Inf\"}} {}", name, labels, histogram.count);
+	let _ = writeln!(out, "{}_sum{{{}}} {}", name, labels, histogram.sum);
+	let _ = writeln!(out, "{}_count{{{}}} {}", name, labels, histogram.count);
+}
+
+/// Everything in the Prometheus text format. The cache size and money supply are read as it's scraped.
+async fn render(ctx: &Context) -> String {
+	let mut out = String::new();
+
+	header(&mut out, "poketcg_commands_total", "counter", "Commands run, by command and result");
+	header(&mut out, "poketcg_command_duration_seconds", "histogram", "How long commands take to run");
+	{
+		let commands = COMMANDS.lock().unwrap();
+		for (command, stats) in commands.iter() {
+			let command = escape(command);
+			let _ = writeln!(out, "poketcg_commands_total{{command=\"{}\",result=\"ok\"}} {}", command, stats.ok);
+			let _ = writeln!(out, "poketcg_commands_total{{command=\"{}\",result=\"error\"}} {}", command, stats.err);
+			write_histogram(&mut out, "poketcg_command_duration_seconds", &format!("command=\"{}\"", command), &stats.latency);
+		}
+	}
+
+	header(&mut out, "poketcg_api_requests_total", "counter", "Requests to the Pokemon TCG API, by resource");
+	header(&mut out, "poketcg_api_errors_total", "counter", "Failed requests to the Pokemon TCG API, by resource");
+	{
+		let api_calls = API_CALLS.lock().unwrap();
+		for (resource, stats) in api_calls.iter() {
+			let resource = escape(resource);
+			let _ = writeln!(out, "poketcg_api_requests_total{{resource=\"{}\"}} {}", resource, stats.requests);
+			let _ = writeln!(out, "poketcg_api_errors_total{{resource=\"{}\"}} {}", resource, stats.errors);
+		}
+	}
+
+	let cache_size = {
+		let cache_read = ctx.data.read().await;
+		let cache_lock = cache_read.get::<Cache>().expect("Expected Cache in TypeMap").clone();
+		let cache = cache_lock.read().await;
+
+		cache.len()
+	};
+	header(&mut out, "poketcg_card_cache_size", "gauge", "Cards in the card cache");
+	let _ = writeln!(out, "poketcg_card_cache_size {}", cache_size);
+	header(&mut out, "poketcg_card_cache_hits_total", "counter", "Card lookups answered by the cache");
+	let _ = writeln!(out, "poketcg_card_cache_hits_total {}", CACHE_HITS.load(Ordering::Relaxed));
+	header(&mut out, "poketcg_card_cache_misses_total", "counter", "Card lookups that weren't in the cache");
+	let _ = writeln!(out, "poketcg_card_cache_misses_total {}", CACHE_MISSES.load(Ordering::Relaxed));
+
+	header(&mut out, "poketcg_packs_opened_total", "counter", "Packs opened by players");
+	let _ = writeln!(out, "poketcg_packs_opened_total {}", PACKS_OPENED.load(Ordering::Relaxed));
+
+	match player::get_money_supply().await {
+		Ok(supply) => {
+			header(&mut out, "poketcg_money_supply_cash", "gauge", "Cash held by all players, by economy");
+			for x in &supply {
+				let _ = writeln!(out, "poketcg_money_supply_cash{{economy=\"{}\"}} {}", x.economy, x.cash);
+			}
+			header(&mut out, "poketcg_money_supply_tokens", "gauge", "Tokens held by all players, by economy");
+			for x in &supply {
+				let _ = writeln!(out, "poketcg_money_supply_tokens{{economy=\"{}\"}} {}", x.economy, x.tokens);
+			}
+		},
+		Err(why) => warn!(target: logging::BOT, "Couldn't get the money supply for metrics: {}", why)
+	}
+
+	header(&mut out, "poketcg_job_runs_total", "counter", "Scheduler job runs, by job");
+	header(&mut out, "poketcg_job_failures_total", "counter", "Scheduler job runs that panicked, by job");
+	header(&mut out, "poketcg_job_last_duration_seconds", "gauge", "How long the last run of each job took");
+	header(&mut out, "poketcg_job_duration_seconds", "histogram", "How long scheduler jobs take to run");
+	{
+		let jobs = JOBS.lock().unwrap();
+		for (job, stats) in jobs.iter() {
+			let job = escape(job);
+			let _ = writeln!(out, "poketcg_job_runs_total{{job=\"{}\"}} {}", job, stats.runs);
+			let _ = writeln!(out, "poketcg_job_failures_total{{job=\"{}\"}} {}", job, stats.failures);
+			let _ = writeln!(out, "poketcg_job_last_duration_seconds{{job=\"{}\"}} {}", job, stats.last_duration);
+			write_histogram(&mut out, "poketcg_job_duration_seconds", &format!("job=\"{}\"", job), &stats.latency);
+		}
+	}
+
+	out
+}
+
+async fn handle(ctx: Arc<Context>, req: Request<Body>) -> Result<Response<Body>, Infallible> {
+	let response = match (req.method(), req.uri().path()) {
+		(&Method::GET, "/metrics") => Response::builder()
+			.header("Content-Type", "text/plain; version=0.0.4")
+			.body(Body::from(render(&ctx).await)),
+		_ => Response::builder()
+			.status(StatusCode::NOT_FOUND)
+			.body(Body::from("Not found"))
+	};
+
+	Ok(response.unwrap())
+}
+
+/// Serves the metrics at /metrics on METRICS_ADDR (default 127.0.0.1:9184) until the bot shuts down.
+pub async fn serve(ctx: Arc<Context>) {
+	http::serve("metrics", "METRICS_ADDR", DEFAULT_ADDR, "/metrics", move |req| handle(Arc::clone(&ctx), req)).await;
+}